@@ -6,6 +6,7 @@ use structopt::StructOpt;
 async fn main() -> anyhow::Result<()> {
     if let Err(e) = HcScaffold::from_args().run().await {
         eprintln!("{}", e.to_string().red());
+        std::process::exit(1);
     }
     Ok(())
 }
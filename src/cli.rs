@@ -1,21 +1,35 @@
 #![doc = include_str!("../guides/cli.md")]
 
 use crate::error::ScaffoldError;
-use crate::file_tree::load_directory_into_memory;
+use crate::file_tree::{history::save_operation, load_directory_into_memory, BuildMode};
+use crate::output::{Output, OutputFormat};
 use crate::scaffold::config::ScaffoldConfig;
 use crate::scaffold::example::ExampleType;
 use crate::scaffold::web_app::template_type::TemplateType;
+use crate::utils::Interactivity;
 
 use colored::Colorize;
-use std::{path::Path, str::FromStr};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use structopt::StructOpt;
 
+mod apply;
+mod codegen;
 mod collection;
 mod dna;
+mod doctor;
 mod entry_type;
 mod example;
+mod field;
+mod inspect;
 mod link_type;
+mod remove;
+mod rename;
 mod template;
+mod undo;
+mod upgrade;
 mod web_app;
 mod zome;
 
@@ -27,6 +41,28 @@ pub struct HcScaffold {
     /// Or a path to a custom template
     template: Option<TemplateType>,
 
+    #[structopt(long, global = true)]
+    /// Print the changes the command would make to each file as a unified diff, without writing anything to disk
+    dry_run: bool,
+
+    #[structopt(long, global = true, conflicts_with = "dry_run")]
+    /// Print the changes the command would make to each file as a unified diff, and ask for confirmation before writing them
+    preview: bool,
+
+    #[structopt(long, short = "y", alias = "yes", global = true)]
+    /// Never prompt: values that would be prompted for must be passed as arguments,
+    /// and yes/no questions are answered with their recommended option
+    non_interactive: bool,
+
+    #[structopt(long, global = true, default_value = "text", parse(try_from_str = OutputFormat::from_str))]
+    /// The format of the output: "text", or "json" to print a single JSON document describing the run,
+    /// with the files it created and modified and its errors, and send everything else to stderr
+    output: OutputFormat,
+
+    #[structopt(short = "C", long, global = true, parse(from_os_str))]
+    /// Run as if hc-scaffold was started in the given folder instead of the current one
+    project_dir: Option<PathBuf>,
+
     #[structopt(subcommand)]
     command: HcScaffoldCommand,
 }
@@ -43,29 +79,208 @@ pub enum HcScaffoldCommand {
     LinkType(link_type::LinkType),
     Collection(collection::Collection),
     Example(example::Example),
+    Apply(apply::Apply),
+    Remove(remove::Remove),
+    Rename(rename::Rename),
+    Field(field::Field),
+    Inspect(inspect::Inspect),
+    Undo(undo::Undo),
+    Doctor(doctor::Doctor),
+    Upgrade(upgrade::Upgrade),
+    Codegen(codegen::Codegen),
 }
 
 impl HcScaffold {
     pub async fn run(self) -> anyhow::Result<()> {
-        let current_dir = std::env::current_dir()?;
-        let scaffold_config = ScaffoldConfig::from_package_json_path(&current_dir)?;
-        let template_type = self.get_template_type(&current_dir, scaffold_config.as_ref())?;
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let command_line = format!("hc-scaffold {}", args.join(" "));
+
+        let output_format = self.output;
+        let mut output = Output::new(output_format, &command_line);
+
+        let result = self.run_command(&command_line, &mut output).await;
+
+        // The report is the only output on stdout in JSON output mode
+        if output_format == OutputFormat::Json {
+            let report = output.finish(&result);
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+
+        result
+    }
+
+    async fn run_command(self, command_line: &str, output: &mut Output) -> anyhow::Result<()> {
+        let project_dir = self.project_dir()?;
+        output.record_project_dir(&project_dir);
+
+        // The project is checked even if its template can't be resolved
+        if let HcScaffoldCommand::Doctor(doctor) = &self.command {
+            return doctor.run(&project_dir, output);
+        }
+
+        let scaffold_config = ScaffoldConfig::from_package_json_path(&project_dir)?;
+        let template_type = self.get_template_type(&project_dir, scaffold_config.as_ref())?;
+        output.record_template(&template_type.name());
+        let build_mode = self.build_mode();
+        let interactivity = self.interactivity();
+        if build_mode == BuildMode::Preview && !interactivity.is_interactive() {
+            return Err(ScaffoldError::InvalidArguments(String::from(
+                "--preview asks for confirmation before writing, so it can't be used with --non-interactive or --output json",
+            )))?;
+        }
+        let records_history = self.records_history();
 
         match self.command {
-            HcScaffoldCommand::WebApp(web_app) => web_app.run(&template_type).await,
-            HcScaffoldCommand::Template(template) => template.run(&template_type),
-            HcScaffoldCommand::Dna(dna) => dna.run(&template_type),
-            HcScaffoldCommand::Zome(zome) => zome.run(&template_type),
-            HcScaffoldCommand::EntryType(entry_type) => entry_type.run(&template_type),
-            HcScaffoldCommand::LinkType(link_type) => link_type.run(&template_type),
-            HcScaffoldCommand::Collection(collection) => collection.run(&template_type),
-            HcScaffoldCommand::Example(example) => example.run(&template_type).await,
+            HcScaffoldCommand::WebApp(web_app) => {
+                web_app
+                    .run(
+                        &project_dir,
+                        &template_type,
+                        build_mode,
+                        interactivity,
+                        output,
+                    )
+                    .await
+            }
+            HcScaffoldCommand::Template(template) => template.run(
+                &project_dir,
+                &template_type,
+                build_mode,
+                interactivity,
+                output,
+            ),
+            HcScaffoldCommand::Dna(dna) => dna.run(
+                &project_dir,
+                &template_type,
+                build_mode,
+                interactivity,
+                output,
+            ),
+            HcScaffoldCommand::Zome(zome) => zome.run(
+                &project_dir,
+                &template_type,
+                build_mode,
+                interactivity,
+                output,
+            ),
+            HcScaffoldCommand::EntryType(entry_type) => entry_type.run(
+                &project_dir,
+                &template_type,
+                build_mode,
+                interactivity,
+                output,
+            ),
+            HcScaffoldCommand::LinkType(link_type) => link_type.run(
+                &project_dir,
+                &template_type,
+                build_mode,
+                interactivity,
+                output,
+            ),
+            HcScaffoldCommand::Collection(collection) => collection.run(
+                &project_dir,
+                &template_type,
+                build_mode,
+                interactivity,
+                output,
+            ),
+            HcScaffoldCommand::Example(example) => {
+                example
+                    .run(
+                        &project_dir,
+                        &template_type,
+                        build_mode,
+                        interactivity,
+                        output,
+                    )
+                    .await
+            }
+            HcScaffoldCommand::Apply(apply) => apply.run(
+                &project_dir,
+                &template_type,
+                build_mode,
+                interactivity,
+                output,
+            ),
+            HcScaffoldCommand::Remove(remove) => {
+                remove.run(&project_dir, build_mode, interactivity, output)
+            }
+            HcScaffoldCommand::Rename(rename) => rename.run(
+                &project_dir,
+                &template_type,
+                build_mode,
+                interactivity,
+                output,
+            ),
+            HcScaffoldCommand::Field(field) => field.run(
+                &project_dir,
+                &template_type,
+                build_mode,
+                interactivity,
+                output,
+            ),
+            HcScaffoldCommand::Inspect(inspect) => inspect.run(&project_dir, output),
+            HcScaffoldCommand::Undo(undo) => undo.run(&project_dir, build_mode, output),
+            HcScaffoldCommand::Doctor(doctor) => doctor.run(&project_dir, output),
+            HcScaffoldCommand::Upgrade(upgrade) => upgrade.run(&project_dir, build_mode, output),
+            HcScaffoldCommand::Codegen(codegen) => codegen.run(&project_dir, build_mode, output),
+        }?;
+
+        if records_history {
+            save_operation(&project_dir, command_line, output.take_files_before())?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the files written by the command are recorded in the scaffolding history of the project,
+    /// which excludes the commands creating a new project, the undo command itself and the commands that keep running
+    fn records_history(&self) -> bool {
+        match &self.command {
+            HcScaffoldCommand::Codegen(codegen) => !codegen.is_watching(),
+            command => !matches!(
+                command,
+                HcScaffoldCommand::WebApp(_)
+                    | HcScaffoldCommand::Example(_)
+                    | HcScaffoldCommand::Inspect(_)
+                    | HcScaffoldCommand::Undo(_)
+                    | HcScaffoldCommand::Doctor(_)
+            ),
+        }
+    }
+
+    /// The folder the command runs in, which is the root of the project for the commands that modify one
+    fn project_dir(&self) -> Result<PathBuf, ScaffoldError> {
+        let current_dir = std::env::current_dir()?;
+        match &self.project_dir {
+            Some(dir) => current_dir
+                .join(dir)
+                .canonicalize()
+                .map_err(|_| ScaffoldError::PathNotFound(dir.clone())),
+            None => Ok(current_dir),
+        }
+    }
+
+    fn build_mode(&self) -> BuildMode {
+        match (self.dry_run, self.preview) {
+            (true, _) => BuildMode::DryRun,
+            (_, true) => BuildMode::Preview,
+            _ => BuildMode::Write,
+        }
+    }
+
+    fn interactivity(&self) -> Interactivity {
+        // Prompts would mix with the JSON output, and can't be answered by the tools reading it
+        if self.non_interactive || self.output == OutputFormat::Json {
+            Interactivity::NonInteractive
+        } else {
+            Interactivity::Interactive
         }
     }
 
     fn get_template_type(
         &self,
-        current_dir: &Path,
+        project_dir: &Path,
         scaffold_config: Option<&ScaffoldConfig>,
     ) -> Result<TemplateType, ScaffoldError> {
         // Read template_type config if no `--template` flag is provided and use it or
@@ -87,15 +302,20 @@ impl HcScaffold {
             Some(template) => Ok(template.clone()),
             None => {
                 let template_type = match &self.command {
-                    HcScaffoldCommand::WebApp { .. } => TemplateType::choose()?,
+                    HcScaffoldCommand::WebApp { .. } => TemplateType::choose(self.interactivity())?,
                     HcScaffoldCommand::Example(example::Example { ref example, .. }) => {
                         match example {
                             Some(ExampleType::HelloWorld) => TemplateType::Vanilla,
-                            Some(ExampleType::Forum) => TemplateType::choose_non_vanilla()?,
-                            None => TemplateType::choose_non_headless()?,
+                            Some(ExampleType::Forum) => {
+                                TemplateType::choose_non_vanilla(self.interactivity())?
+                            }
+                            None => TemplateType::choose_non_headless(self.interactivity())?,
                         }
                     }
-                    _ => TemplateType::try_from(&load_directory_into_memory(current_dir)?)?,
+                    _ => TemplateType::from_file_tree(
+                        &load_directory_into_memory(project_dir)?,
+                        self.interactivity(),
+                    )?,
                 };
                 Ok(template_type)
             }
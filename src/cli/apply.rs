@@ -0,0 +1,90 @@
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use structopt::StructOpt;
+
+use crate::{
+    error::ScaffoldError,
+    file_tree::BuildMode,
+    output::Output,
+    scaffold::{
+        happ_spec::{apply_happ_spec, AppliedHappSpec, HappSpec},
+        web_app::template_type::TemplateType,
+    },
+    scaffolder::Scaffolder,
+    utils::Interactivity,
+};
+
+#[derive(Debug, StructOpt)]
+/// Scaffold all the DNAs, zomes, entry types, link types and collections described in a hApp spec file,
+/// skipping the ones that already exist
+pub struct Apply {
+    #[structopt(parse(from_os_str))]
+    /// Path to the hApp spec file, in YAML or JSON format
+    pub spec: PathBuf,
+
+    #[structopt(long)]
+    /// Skips UI generation for the scaffolded items.
+    pub no_ui: bool,
+
+    #[structopt(long)]
+    /// Skips test generation for the scaffolded items.
+    pub no_spec: bool,
+}
+
+impl Apply {
+    pub fn run(
+        self,
+        project_dir: &Path,
+        template_type: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        let content = std::fs::read_to_string(&self.spec)
+            .map_err(|e| ScaffoldError::MalformedFile(self.spec.clone(), e.to_string()))?;
+        let spec = HappSpec::from_file_content(&self.spec, &content)?;
+
+        let scaffolder = Scaffolder::with_template_type(project_dir, template_type)?
+            .with_interactivity(interactivity);
+
+        let AppliedHappSpec {
+            file_tree,
+            scaffolded,
+            skipped,
+        } = apply_happ_spec(
+            scaffolder.load_project()?,
+            scaffolder.template_file_tree(),
+            &spec,
+            self.no_ui,
+            self.no_spec,
+            scaffolder.interactivity(),
+        )?;
+
+        if !skipped.is_empty() {
+            output.println("\nSkipped, as they already exist:");
+            for item in &skipped {
+                output.println(format!("  {}", item.italic()));
+            }
+        }
+
+        if scaffolded.is_empty() {
+            output.println("\nNothing to scaffold, the project already matches the hApp spec.\n");
+            return Ok(());
+        }
+
+        let built = scaffolder.write(file_tree, build_mode)?;
+        output.record_build(scaffolder.project_dir(), build_mode, &built);
+        if !built.written {
+            return Ok(());
+        }
+
+        output.println("\nScaffolded:");
+        for item in &scaffolded {
+            output.println(format!("  {}", item.italic()));
+        }
+        output.println("");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use colored::Colorize;
+use ignore::WalkBuilder;
+use structopt::StructOpt;
+
+use crate::{
+    error::ScaffoldResult,
+    file_tree::{build_file_tree_with_mode, load_directory_into_memory, BuildMode, FileTree},
+    output::Output,
+    scaffold::codegen::{
+        client::codegen_client,
+        rust_client::{codegen_rust_client, DEFAULT_CLIENTS_PATH},
+        types::codegen_types,
+    },
+};
+
+#[derive(Debug, StructOpt)]
+#[structopt(setting = structopt::clap::AppSettings::InferSubcommands)]
+/// Generate code from the Rust source of the zomes of the project
+pub enum Codegen {
+    /// Regenerate the TypeScript types of the entry types and signals of each coordinator zome,
+    /// in "ui/src/<DNA>/<COORDINATOR_ZOME>/types.ts"
+    Types {
+        #[structopt(long)]
+        /// Keep running, and regenerate the types whenever a Rust file of the project changes
+        watch: bool,
+    },
+    /// Generate a typed TypeScript client for the zome functions of each coordinator zome,
+    /// in "ui/src/<DNA>/<COORDINATOR_ZOME>/<COORDINATOR_ZOME>ZomeClient.ts"
+    Client,
+    /// Generate a Rust crate with an async client for the zome functions of each coordinator zome of a DNA,
+    /// in "clients/<DNA>_client"
+    RustClient {
+        #[structopt(long)]
+        /// Folder to generate the client crates in, relative to the root of the project
+        path: Option<PathBuf>,
+    },
+}
+
+impl Codegen {
+    pub fn run(
+        self,
+        project_dir: &Path,
+        build_mode: BuildMode,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        match self {
+            Codegen::Types { watch: false } => generate_types(project_dir, build_mode, output),
+            Codegen::Client => generate_client(project_dir, build_mode, output),
+            Codegen::RustClient { path } => {
+                let clients_path = path.unwrap_or_else(|| PathBuf::from(DEFAULT_CLIENTS_PATH));
+                generate(
+                    project_dir,
+                    build_mode,
+                    |file_tree| codegen_rust_client(file_tree, &clients_path),
+                    "The Rust clients",
+                    output,
+                )
+            }
+            Codegen::Types { watch: true } => {
+                output.println(format!(
+                    "Watching the Rust files of the project, press {} to stop.\n",
+                    "Ctrl+C".bold()
+                ));
+                let mut last_sources = vec![];
+                loop {
+                    let sources = rust_sources(project_dir);
+                    if sources != last_sources {
+                        if let Err(e) = generate_types(project_dir, build_mode, output) {
+                            output.println(format!("Error: {e}").red());
+                        }
+                        last_sources = sources;
+                    }
+                    std::thread::sleep(Duration::from_secs(1));
+                }
+            }
+        }
+    }
+
+    /// Whether the command keeps running until it's interrupted
+    pub fn is_watching(&self) -> bool {
+        matches!(self, Codegen::Types { watch: true })
+    }
+}
+
+fn generate_types(
+    project_dir: &Path,
+    build_mode: BuildMode,
+    output: &mut Output,
+) -> anyhow::Result<()> {
+    generate(
+        project_dir,
+        build_mode,
+        codegen_types,
+        "The TypeScript types",
+        output,
+    )
+}
+
+fn generate_client(
+    project_dir: &Path,
+    build_mode: BuildMode,
+    output: &mut Output,
+) -> anyhow::Result<()> {
+    generate(
+        project_dir,
+        build_mode,
+        codegen_client,
+        "The zome clients",
+        output,
+    )
+}
+
+/// Runs the given code generation on the project, and writes the files it changed
+fn generate(
+    project_dir: &Path,
+    build_mode: BuildMode,
+    codegen: impl FnOnce(&mut FileTree) -> ScaffoldResult<Vec<PathBuf>>,
+    generated: &str,
+    output: &mut Output,
+) -> anyhow::Result<()> {
+    let mut file_tree = load_directory_into_memory(project_dir)?;
+
+    let changed = codegen(&mut file_tree)?;
+    if changed.is_empty() {
+        output.println(format!("{generated} are up to date."));
+        return Ok(());
+    }
+
+    let built = build_file_tree_with_mode(file_tree, project_dir, build_mode)?;
+    output.record_build(project_dir, build_mode, &built);
+    if !built.written {
+        return Ok(());
+    }
+
+    for path in changed {
+        output.println(format!("  {} {}", "✓".green(), path.display()));
+    }
+
+    Ok(())
+}
+
+/// The Rust files of the project with the time they were last modified, which are ignored if
+/// they are in the .gitignore, like the files in the target folder
+fn rust_sources(project_dir: &Path) -> Vec<(PathBuf, Option<SystemTime>)> {
+    WalkBuilder::new(project_dir)
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|e| e == "rs"))
+        .map(|entry| {
+            let modified = entry.metadata().ok().and_then(|m| m.modified().ok());
+            (entry.into_path(), modified)
+        })
+        .collect()
+}
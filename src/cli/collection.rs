@@ -1,20 +1,19 @@
-use std::str::FromStr;
+use std::{path::Path, str::FromStr};
 
 use colored::Colorize;
 use convert_case::Case;
 use structopt::StructOpt;
 
 use crate::{
-    file_tree::{build_file_tree, load_directory_into_memory},
+    file_tree::BuildMode,
+    output::Output,
     scaffold::{
-        collection::{scaffold_collection, CollectionType},
-        dna::DnaFileTree,
-        entry_type::definitions::EntryTypeReference,
+        collection::CollectionType, entry_type::definitions::EntryTypeReference,
         web_app::template_type::TemplateType,
-        zome::ZomeFileTree,
     },
+    scaffolder::{CollectionAnswers, Scaffolder},
     templates::ScaffoldedTemplate,
-    utils::{check_case, input_with_case, run_cargo_fmt_if_available},
+    utils::{check_case, input_with_case, Interactivity},
 };
 
 #[derive(Debug, StructOpt)]
@@ -28,7 +27,8 @@ pub struct Collection {
     /// Name of the integrity zome in which you want to scaffold the link type
     pub zome: Option<String>,
 
-    /// Collection type: "global" or "by-author"
+    /// Collection type: "global", "by-author", or "by-time:<GRANULARITY>" to link the entries from a path
+    /// for the year, month, day or hour they were created in, eg. "by-time:day"
     pub collection_type: Option<CollectionType>,
 
     /// Collection name, just to differentiate it from other collections
@@ -38,6 +38,10 @@ pub struct Collection {
     /// Entry type that is going to be added to the collection
     pub entry_type: Option<EntryTypeReference>,
 
+    #[structopt(long)]
+    /// Whether the getter of the collection takes a cursor and a limit, to get its entries one page at a time
+    pub paginated: bool,
+
     #[structopt(long)]
     /// Skips UI generation for this collection.
     pub no_ui: bool,
@@ -48,53 +52,62 @@ pub struct Collection {
 }
 
 impl Collection {
-    pub fn run(self, template_type: &TemplateType) -> anyhow::Result<()> {
-        let current_dir = std::env::current_dir()?;
-        let file_tree = load_directory_into_memory(&current_dir)?;
-
-        let dna_file_tree = DnaFileTree::get_or_choose(file_tree, self.dna.as_deref())?;
-        let zome_file_tree =
-            ZomeFileTree::get_or_choose_integrity(dna_file_tree, self.zome.as_deref())?;
-
+    pub fn run(
+        self,
+        project_dir: &Path,
+        template_type: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        let scaffolder = Scaffolder::with_template_type(project_dir, template_type)?
+            .with_interactivity(interactivity);
         let name = match self.collection_name {
             Some(n) => {
                 check_case(&n, "collection name", Case::Snake)?;
                 n
             }
-            None => input_with_case(
-                "Collection name (snake_case, eg. \"all_posts\"):",
-                None,
-                Case::Snake,
-            )?,
+            None => {
+                interactivity.ensure_interactive("<collection-name>")?;
+                input_with_case(
+                    "Collection name (snake_case, eg. \"all_posts\"):",
+                    None,
+                    Case::Snake,
+                    interactivity,
+                )?
+            }
         };
+        output.record_choice("collection", &name);
+
+        let (dna, zome) =
+            scaffolder.choose_integrity_zome(self.dna.as_deref(), self.zome.as_deref())?;
+        output.record_choice("dna", &dna);
+        output.record_choice("integrity_zome", &zome);
 
         let ScaffoldedTemplate {
             file_tree,
             next_instructions,
-        } = scaffold_collection(
-            zome_file_tree,
-            &template_type.file_tree()?,
-            &name,
-            self.collection_type,
-            self.entry_type,
-            self.no_ui,
-            self.no_spec,
-        )?;
-
-        build_file_tree(file_tree, ".")?;
-
-        if let Err(e) = run_cargo_fmt_if_available() {
-            println!(
-                "{}: {}",
-                "rustfmt exec failed: ".yellow(),
-                e.to_string().yellow()
-            );
+        } = scaffolder.collection(CollectionAnswers {
+            dna: Some(dna),
+            zome: Some(zome),
+            name: name.clone(),
+            collection_type: self.collection_type,
+            entry_type: self.entry_type,
+            paginated: self.paginated,
+            no_ui: self.no_ui,
+            no_spec: self.no_spec,
+        })?;
+
+        let built = scaffolder.write(file_tree, build_mode)?;
+        output.record_build(scaffolder.project_dir(), build_mode, &built);
+        if !built.written {
+            return Ok(());
         }
 
-        println!("\nCollection {} scaffolded!\n", name.italic());
+        output.println(format!("\nCollection {} scaffolded!\n", name.italic()));
 
         if let Some(i) = next_instructions {
-            println!("{}", i);
+            output.print_next_instructions(&i);
         }
 
         Ok(())
@@ -1,12 +1,16 @@
+use std::path::Path;
+
 use colored::Colorize;
 use convert_case::Case;
 use structopt::StructOpt;
 
 use crate::{
-    file_tree::{build_file_tree, load_directory_into_memory},
-    scaffold::{app::AppFileTree, dna::scaffold_dna, web_app::template_type::TemplateType},
+    file_tree::BuildMode,
+    output::Output,
+    scaffold::web_app::template_type::TemplateType,
+    scaffolder::{DnaAnswers, Scaffolder},
     templates::ScaffoldedTemplate,
-    utils::{check_case, input_with_case},
+    utils::{check_case, input_with_case, Interactivity},
 };
 
 #[derive(Debug, StructOpt)]
@@ -21,33 +25,51 @@ pub struct Dna {
 }
 
 impl Dna {
-    pub fn run(self, template_type: &TemplateType) -> anyhow::Result<()> {
-        let current_dir = std::env::current_dir()?;
-        let file_tree = load_directory_into_memory(&current_dir)?;
-
+    pub fn run(
+        self,
+        project_dir: &Path,
+        template_type: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        let scaffolder = Scaffolder::with_template_type(project_dir, template_type)?
+            .with_interactivity(interactivity);
         let name = match self.name {
             Some(n) => {
                 check_case(&n, "dna name", Case::Snake)?;
                 n
             }
-            None => input_with_case("DNA name (snake_case):", None, Case::Snake)?,
+            None => {
+                interactivity.ensure_interactive("<name>")?;
+                input_with_case("DNA name (snake_case):", None, Case::Snake, interactivity)?
+            }
         };
+        output.record_choice("dna", &name);
 
-        let app_file_tree = AppFileTree::get_or_choose(file_tree, self.app.as_deref())?;
+        let app = scaffolder.choose_app(self.app.as_deref())?;
+        output.record_choice("app", &app);
 
         let ScaffoldedTemplate {
             file_tree,
             next_instructions,
-        } = scaffold_dna(app_file_tree, &template_type.file_tree()?, &name)?;
+        } = scaffolder.dna(DnaAnswers {
+            app: Some(app),
+            name: name.clone(),
+        })?;
 
-        build_file_tree(file_tree, ".")?;
+        let built = scaffolder.write(file_tree, build_mode)?;
+        output.record_build(scaffolder.project_dir(), build_mode, &built);
+        if !built.written {
+            return Ok(());
+        }
 
-        println!("\nDNA {} scaffolded!", name.italic());
+        output.println(format!("\nDNA {} scaffolded!", name.italic()));
 
         if let Some(i) = next_instructions {
-            println!("\n{}", i);
+            output.print_next_instructions(&format!("\n{i}"));
         } else {
-            println!(
+            output.print_next_instructions(
                 r#"
 Add new zomes to your DNA with:
 
@@ -0,0 +1,39 @@
+use std::path::Path;
+
+use colored::Colorize;
+use structopt::StructOpt;
+
+use crate::{
+    error::ScaffoldError, file_tree::load_directory_into_memory, output::Output,
+    scaffold::doctor::diagnose_project,
+};
+
+#[derive(Debug, StructOpt)]
+/// Check the versions of the holochain dependencies, the app and DNA manifests and the template configuration of the project,
+/// exiting with an error if any problem is found
+pub struct Doctor {}
+
+impl Doctor {
+    pub fn run(&self, project_dir: &Path, output: &Output) -> anyhow::Result<()> {
+        let file_tree = load_directory_into_memory(project_dir)?;
+
+        let findings = diagnose_project(&file_tree)?;
+
+        if findings.is_empty() {
+            output.println("No problems were found in the project.".green());
+            return Ok(());
+        }
+
+        for finding in &findings {
+            output.println(format!(
+                "{} {}: {}",
+                "✗".red(),
+                finding.path.display().to_string().bold(),
+                finding.problem
+            ));
+            output.println(format!("  {} {}\n", "fix:".yellow(), finding.fix));
+        }
+
+        Err(ScaffoldError::ProblemsFound(findings.len()))?
+    }
+}
@@ -1,19 +1,27 @@
-use std::str::FromStr;
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use colored::Colorize;
-use convert_case::Case;
+use convert_case::{Case, Casing};
 use structopt::StructOpt;
 
 use crate::{
-    file_tree::{build_file_tree, load_directory_into_memory},
+    error::ScaffoldError,
+    file_tree::BuildMode,
+    output::Output,
     scaffold::{
-        dna::DnaFileTree,
-        entry_type::{crud::Crud, definitions::FieldDefinition, scaffold_entry_type},
+        entry_type::{
+            crud::Crud,
+            definitions::{parse_fields, FieldDefinition, Permission, Visibility},
+            schema::{entry_type_name_from_schema, fields_from_sample, fields_from_schema},
+        },
         web_app::template_type::TemplateType,
-        zome::ZomeFileTree,
     },
+    scaffolder::{EntryTypeAnswers, Scaffolder},
     templates::ScaffoldedTemplate,
-    utils::{check_case, input_with_case, run_cargo_fmt_if_available},
+    utils::{check_case, input_with_case, Interactivity},
 };
 
 #[derive(Debug, StructOpt)]
@@ -45,11 +53,44 @@ pub struct EntryType {
     /// Only applies if update is selected in the "crud" argument
     pub link_from_original_to_each_update: Option<bool>,
 
-    #[structopt(long, value_delimiter = ",", parse(try_from_str = FieldDefinition::from_str))]
+    #[structopt(long)]
+    /// Whether "get_all_revisions_for_<ENTRY_TYPE>" takes a cursor and a limit, to get the revisions one page at a time
+    /// Only applies if the original entry is linked to each update
+    pub paginated_revisions: bool,
+
+    #[structopt(long, parse(try_from_str = Visibility::from_str))]
+    /// Whether the entries are "public", or "private" to their author: private entries are read from
+    /// the source chain of the agent, and no links are created to them
+    pub visibility: Option<Visibility>,
+
+    #[structopt(long, parse(try_from_str = Permission::from_str))]
+    /// Which agents can update and delete the entries: "author", the agents listed in a
+    /// Vec<AgentPubKey> field with "editors:<FIELD_NAME>", or the "progenitor" set in the DNA properties
+    pub permission: Option<Permission>,
+
+    #[structopt(long, parse(try_from_str = parse_fields))]
     /// The fields that the entry type struct should contain
     /// Syntax: <FIELD_NAME>:<FIELD_TYPE>:<WIDGET>:<LINKED_FROM> , (widget and linked_from are optional)
     /// Eg. "title:String:TextField" , "posts_hashes:Vec\<ActionHash\>::Post"
-    pub fields: Option<Vec<FieldDefinition>>,
+    /// Struct fields have their fields between braces, separated by semicolons
+    /// Eg. "address:Struct:Address{street:String:TextField;city:String:TextField}:Fieldset"
+    /// Enum variants can carry named fields between braces, or a single value between parentheses
+    /// Eg. "status:Enum:Select:Status:Draft.Scheduled{at:Timestamp:DateTimePicker}.Fixed(u32:Slider)"
+    /// String and number fields can be constrained between braces after their type
+    /// Eg. "title:String{min=1,max=140}:TextField" , "rating:u8{range=1..=5}:Slider"
+    /// Fields prefixed with "immutable" can't be changed when the entry is updated
+    /// Eg. "immutable slug:String:TextField"
+    pub fields: Option<Vec<Vec<FieldDefinition>>>,
+
+    #[structopt(long, conflicts_with_all = &["fields", "from-sample"])]
+    /// Path to a JSON Schema describing the entry type, whose properties are mapped onto its fields
+    /// If the name is not given, it's taken from the title of the schema or from the name of the file
+    pub from_schema: Option<PathBuf>,
+
+    #[structopt(long, conflicts_with = "fields")]
+    /// Path to a sample JSON document of the entry type, from whose values its fields are inferred
+    /// If the name is not given, it's taken from the name of the file
+    pub from_sample: Option<PathBuf>,
 
     #[structopt(long)]
     /// Skips UI generation for this entry-type, overriding any specified widgets in the --fields option.
@@ -61,52 +102,69 @@ pub struct EntryType {
 }
 
 impl EntryType {
-    pub fn run(self, template_type: &TemplateType) -> anyhow::Result<()> {
-        let current_dir = std::env::current_dir()?;
-        let file_tree = load_directory_into_memory(&current_dir)?;
-        let name = match self.name {
+    pub fn run(
+        self,
+        project_dir: &Path,
+        template_type: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        let scaffolder = Scaffolder::with_template_type(project_dir, template_type)?
+            .with_interactivity(interactivity);
+        let (fields, name_from_file) = self.fields_from_file(project_dir)?;
+        let name = match self.name.or(name_from_file) {
             Some(n) => {
                 check_case(&n, "entry type name", Case::Snake)?;
                 n
             }
-            None => input_with_case("Entry type name (snake_case):", None, Case::Snake)?,
+            None => {
+                interactivity.ensure_interactive("<name>")?;
+                input_with_case(
+                    "Entry type name (snake_case):",
+                    None,
+                    Case::Snake,
+                    interactivity,
+                )?
+            }
         };
+        output.record_choice("entry_type", &name);
 
-        let dna_file_tree = DnaFileTree::get_or_choose(file_tree, self.dna.as_deref())?;
-        let zome_file_tree =
-            ZomeFileTree::get_or_choose_integrity(dna_file_tree, self.zome.as_deref())?;
+        let (dna, zome) =
+            scaffolder.choose_integrity_zome(self.dna.as_deref(), self.zome.as_deref())?;
+        output.record_choice("dna", &dna);
+        output.record_choice("integrity_zome", &zome);
 
         let ScaffoldedTemplate {
             file_tree,
             next_instructions,
-        } = scaffold_entry_type(
-            zome_file_tree,
-            &template_type.file_tree()?,
-            &name,
-            self.crud,
-            self.reference_entry_hash,
-            self.link_from_original_to_each_update,
-            self.fields.as_ref(),
-            self.no_ui,
-            self.no_spec,
-        )?;
-
-        build_file_tree(file_tree, ".")?;
-
-        if let Err(e) = run_cargo_fmt_if_available() {
-            println!(
-                "{}: {}",
-                "rustfmt exec failed: ".yellow(),
-                e.to_string().yellow()
-            );
+        } = scaffolder.entry_type(EntryTypeAnswers {
+            dna: Some(dna),
+            zome: Some(zome),
+            name: name.clone(),
+            crud: self.crud,
+            reference_entry_hash: self.reference_entry_hash,
+            link_from_original_to_each_update: self.link_from_original_to_each_update,
+            paginated_revisions: self.paginated_revisions,
+            visibility: self.visibility,
+            permission: self.permission,
+            fields: fields.or(self.fields.map(|f| f.concat())),
+            no_ui: self.no_ui,
+            no_spec: self.no_spec,
+        })?;
+
+        let built = scaffolder.write(file_tree, build_mode)?;
+        output.record_build(scaffolder.project_dir(), build_mode, &built);
+        if !built.written {
+            return Ok(());
         }
 
-        println!("\nEntry type {} scaffolded!", name.italic());
+        output.println(format!("\nEntry type {} scaffolded!", name.italic()));
 
         if let Some(i) = next_instructions {
-            println!("\n{}", i);
+            output.print_next_instructions(&format!("\n{i}"));
         } else {
-            println!(
+            output.print_next_instructions(
                 r#"
 Add new collections for that entry type with:
 
@@ -117,4 +175,41 @@ Add new collections for that entry type with:
 
         Ok(())
     }
+
+    /// Reads the fields from the schema or the sample given as argument, if any,
+    /// with the entry type name that they imply
+    fn fields_from_file(
+        &self,
+        project_dir: &Path,
+    ) -> anyhow::Result<(Option<Vec<FieldDefinition>>, Option<String>)> {
+        let (path, is_schema) = match (&self.from_schema, &self.from_sample) {
+            (Some(path), _) => (path, true),
+            (None, Some(path)) => (path, false),
+            (None, None) => return Ok((None, None)),
+        };
+        let path = project_dir.join(path);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|_| ScaffoldError::PathNotFound(path.clone()))?;
+        let value: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| ScaffoldError::MalformedFile(path.clone(), e.to_string()))?;
+
+        let fields = if is_schema {
+            fields_from_schema(&value)
+        } else {
+            fields_from_sample(&value)
+        }?;
+
+        let name = is_schema
+            .then(|| entry_type_name_from_schema(&value))
+            .flatten()
+            .or_else(|| {
+                // "post.schema.json" describes the "post" entry type
+                path.file_name()
+                    .and_then(|f| f.to_str())
+                    .and_then(|f| f.split('.').next())
+                    .map(|f| f.to_case(Case::Snake))
+            });
+
+        Ok((Some(fields), name))
+    }
 }
@@ -0,0 +1,217 @@
+use std::path::Path;
+
+use colored::Colorize;
+use structopt::StructOpt;
+
+use crate::{
+    file_tree::{
+        build_file_tree_with_mode, build_file_tree_with_removals, load_directory_into_memory,
+        BuildMode,
+    },
+    output::Output,
+    scaffold::{
+        dna::DnaFileTree,
+        entry_type::{
+            add_fields,
+            definitions::{parse_fields, FieldDefinition},
+            remove_fields,
+        },
+        remove::Removal,
+        web_app::template_type::TemplateType,
+        zome::ZomeFileTree,
+    },
+    templates::field::ScaffoldedFields,
+    utils::{run_cargo_fmt_if_available, Interactivity},
+};
+
+#[derive(Debug, StructOpt)]
+#[structopt(setting = structopt::clap::AppSettings::InferSubcommands)]
+/// Add fields to or remove fields from an existing entry type
+pub enum Field {
+    /// Add fields to an existing entry type, in its integrity zome, its UI and its tests
+    Add {
+        #[structopt(long)]
+        /// Name of the dna in which the entry type is defined
+        dna: Option<String>,
+
+        #[structopt(long)]
+        /// Name of the integrity zome in which the entry type is defined
+        zome: Option<String>,
+
+        #[structopt(long)]
+        /// Name of the entry type to add the fields to
+        entry_type: String,
+
+        #[structopt(long, parse(try_from_str = parse_fields))]
+        /// The fields to add to the entry type struct
+        /// Syntax: <FIELD_NAME>:<FIELD_TYPE>:<WIDGET>:<LINKED_FROM> , (widget and linked_from are optional)
+        /// Eg. "tags:Vec\<String\>:TextField" , "author:AgentPubKey"
+        /// String and number fields can be constrained between braces after their type
+        /// Eg. "slug:String{regex=^[a-z-]+$}:TextField"
+        /// Fields prefixed with "immutable" can't be changed when the entry is updated
+        /// Eg. "immutable kind:Enum:Select:Kind:Task.Event"
+        fields: Option<Vec<Vec<FieldDefinition>>>,
+
+        #[structopt(long)]
+        /// Skips adding the fields to the UI of the entry type
+        no_ui: bool,
+    },
+    /// Remove fields from an existing entry type, with their validation and their typescript definitions
+    /// The code generated for the entry type in its coordinator zome, its UI and its tests must stop using the fields first
+    Remove {
+        #[structopt(long)]
+        /// Name of the dna in which the entry type is defined
+        dna: Option<String>,
+
+        #[structopt(long)]
+        /// Name of the integrity zome in which the entry type is defined
+        zome: Option<String>,
+
+        #[structopt(long)]
+        /// Name of the entry type to remove the fields from
+        entry_type: String,
+
+        #[structopt(required = true)]
+        /// Names of the fields to remove
+        fields: Vec<String>,
+    },
+}
+
+impl Field {
+    pub fn run(
+        self,
+        project_dir: &Path,
+        template_type: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        let (dna, zome, entry_type) = match &self {
+            Field::Add {
+                dna,
+                zome,
+                entry_type,
+                ..
+            }
+            | Field::Remove {
+                dna,
+                zome,
+                entry_type,
+                ..
+            } => (dna, zome, entry_type),
+        };
+        output.record_choice("entry_type", entry_type);
+
+        let file_tree = load_directory_into_memory(project_dir)?;
+
+        let dna_file_tree = DnaFileTree::get_or_choose(file_tree, dna.as_deref(), interactivity)?;
+        let zome_file_tree =
+            ZomeFileTree::get_or_choose_integrity(dna_file_tree, zome.as_deref(), interactivity)?;
+        output.record_choice("dna", &zome_file_tree.dna_file_tree.dna_manifest.name());
+        output.record_choice("integrity_zome", &zome_file_tree.zome_manifest.name.0);
+
+        match self {
+            Field::Add {
+                entry_type,
+                fields,
+                no_ui,
+                ..
+            } => {
+                let ScaffoldedFields {
+                    file_tree,
+                    skipped_files,
+                } = add_fields(
+                    zome_file_tree,
+                    &template_type.file_tree()?,
+                    &entry_type,
+                    fields.map(|f| f.concat()).as_ref(),
+                    no_ui,
+                    interactivity,
+                )?;
+
+                let built = build_file_tree_with_mode(file_tree, project_dir, build_mode)?;
+                output.record_build(project_dir, build_mode, &built);
+                if !built.written {
+                    return Ok(());
+                }
+
+                format_rust_code(project_dir, output);
+
+                output.println(format!(
+                    "\nFields added to entry type {}!\n",
+                    entry_type.italic()
+                ));
+
+                if !skipped_files.is_empty() {
+                    output.println(format!(
+                        "{}",
+                        "These files couldn't be merged with the new fields, add the fields to them by hand:"
+                            .yellow()
+                    ));
+                    for (path, reason) in skipped_files {
+                        output.println(format!("  {}: {}", path.display(), reason));
+                    }
+                    output.println("");
+                }
+
+                output.print_next_instructions(
+                    r#"Fields linked from other entry types don't get link types between them, add those with:
+
+  hc scaffold link-type
+"#,
+                );
+            }
+            Field::Remove {
+                entry_type, fields, ..
+            } => {
+                let Removal {
+                    file_tree,
+                    removed_files,
+                    leftover_references,
+                } = remove_fields(zome_file_tree, &entry_type, &fields)?;
+
+                let built = build_file_tree_with_removals(
+                    file_tree,
+                    &removed_files,
+                    project_dir,
+                    build_mode,
+                )?;
+                output.record_build(project_dir, build_mode, &built);
+                if !built.written {
+                    return Ok(());
+                }
+
+                format_rust_code(project_dir, output);
+
+                output.println(format!(
+                    "\nFields removed from entry type {}!\n",
+                    entry_type.italic()
+                ));
+
+                if !leftover_references.is_empty() {
+                    output.println(format!(
+                        "{}",
+                        "These references to the removed fields were left in place, review them by hand:"
+                            .yellow()
+                    ));
+                    for reference in leftover_references {
+                        output.println(format!("  {reference}"));
+                    }
+                    output.println("");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn format_rust_code(project_dir: &Path, output: &Output) {
+    if let Err(e) = run_cargo_fmt_if_available(project_dir) {
+        output.println(format!(
+            "{}: {}",
+            "rustfmt exec failed: ".yellow(),
+            e.to_string().yellow()
+        ));
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::Path;
+
+use structopt::StructOpt;
+
+use crate::{
+    file_tree::load_directory_into_memory, output::Output, scaffold::inspect::inspect_project,
+};
+
+#[derive(Debug, StructOpt)]
+/// Print a report of the apps, DNAs, zomes, entry types, link types, collections and zome functions of the project
+pub struct Inspect {
+    #[structopt(long)]
+    /// Print the report as JSON instead of as a tree
+    pub json: bool,
+}
+
+impl Inspect {
+    pub fn run(self, project_dir: &Path, output: &Output) -> anyhow::Result<()> {
+        let file_tree = load_directory_into_memory(project_dir)?;
+
+        let report = inspect_project(&file_tree)?;
+
+        if self.json {
+            output.println(serde_json::to_string_pretty(&report)?);
+        } else {
+            print!("{}", report.tree());
+        }
+
+        Ok(())
+    }
+}
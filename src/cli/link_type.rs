@@ -1,16 +1,14 @@
-use std::str::FromStr;
+use std::{path::Path, str::FromStr};
 
-use colored::Colorize;
 use structopt::StructOpt;
 
 use crate::{
-    file_tree::{build_file_tree, load_directory_into_memory},
-    scaffold::{
-        dna::DnaFileTree, entry_type::definitions::Referenceable, link_type::scaffold_link_type,
-        web_app::template_type::TemplateType, zome::ZomeFileTree,
-    },
+    file_tree::BuildMode,
+    output::Output,
+    scaffold::{entry_type::definitions::Referenceable, web_app::template_type::TemplateType},
+    scaffolder::{LinkTypeAnswers, Scaffolder},
     templates::ScaffoldedTemplate,
-    utils::run_cargo_fmt_if_available,
+    utils::Interactivity,
 };
 
 #[derive(Debug, StructOpt)]
@@ -50,41 +48,45 @@ pub struct LinkType {
 }
 
 impl LinkType {
-    pub fn run(self, template_type: &TemplateType) -> anyhow::Result<()> {
-        let current_dir = std::env::current_dir()?;
-        let file_tree = load_directory_into_memory(&current_dir)?;
-
-        let dna_file_tree = DnaFileTree::get_or_choose(file_tree, self.dna.as_deref())?;
-        let zome_file_tree =
-            ZomeFileTree::get_or_choose_integrity(dna_file_tree, self.zome.as_deref())?;
+    pub fn run(
+        self,
+        project_dir: &Path,
+        template_type: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        let scaffolder = Scaffolder::with_template_type(project_dir, template_type)?
+            .with_interactivity(interactivity);
+
+        let (dna, zome) =
+            scaffolder.choose_integrity_zome(self.dna.as_deref(), self.zome.as_deref())?;
+        output.record_choice("dna", &dna);
+        output.record_choice("integrity_zome", &zome);
 
         let ScaffoldedTemplate {
             file_tree,
             next_instructions,
-        } = scaffold_link_type(
-            zome_file_tree,
-            &template_type.file_tree()?,
-            self.from_referenceable.as_ref(),
-            self.to_referenceable.as_ref(),
-            self.delete,
-            self.bidirectional,
-            self.no_ui,
-            self.no_spec,
-        )?;
-
-        build_file_tree(file_tree, ".")?;
-
-        if let Err(e) = run_cargo_fmt_if_available() {
-            println!(
-                "{}: {}",
-                "rustfmt exec failed: ".yellow(),
-                e.to_string().yellow()
-            );
+        } = scaffolder.link_type(LinkTypeAnswers {
+            dna: Some(dna),
+            zome: Some(zome),
+            from_referenceable: self.from_referenceable,
+            to_referenceable: self.to_referenceable,
+            delete: self.delete,
+            bidirectional: self.bidirectional,
+            no_ui: self.no_ui,
+            no_spec: self.no_spec,
+        })?;
+
+        let built = scaffolder.write(file_tree, build_mode)?;
+        output.record_build(scaffolder.project_dir(), build_mode, &built);
+        if !built.written {
+            return Ok(());
         }
 
-        println!("\nLink type scaffolded!\n");
+        output.println("\nLink type scaffolded!\n");
         if let Some(i) = next_instructions {
-            println!("{}", i);
+            output.print_next_instructions(&i);
         }
 
         Ok(())
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use colored::Colorize;
+use structopt::StructOpt;
+
+use crate::{
+    file_tree::{build_file_tree_with_removals, load_directory_into_memory, BuildMode},
+    output::Output,
+    scaffold::{
+        collection::remove_collection, dna::DnaFileTree, entry_type::remove_entry_type,
+        link_type::remove_link_type, remove::Removal, zome::ZomeFileTree,
+    },
+    utils::{run_cargo_fmt_if_available, Interactivity},
+};
+
+#[derive(Debug, StructOpt)]
+#[structopt(setting = structopt::clap::AppSettings::InferSubcommands)]
+/// Remove an entry type, link type or collection that was previously scaffolded
+pub enum Remove {
+    /// Remove an entry type, with its CRUD functions, the link types and collections scaffolded with it, its UI and its tests
+    EntryType {
+        #[structopt(long)]
+        /// Name of the dna from which you want to remove the entry type
+        dna: Option<String>,
+
+        #[structopt(long)]
+        /// Name of the integrity zome from which you want to remove the entry type
+        zome: Option<String>,
+
+        /// Name of the entry type to remove
+        name: String,
+    },
+    /// Remove a link type, with its zome functions, its UI and its tests
+    LinkType {
+        #[structopt(long)]
+        /// Name of the dna from which you want to remove the link type
+        dna: Option<String>,
+
+        #[structopt(long)]
+        /// Name of the integrity zome from which you want to remove the link type
+        zome: Option<String>,
+
+        /// Name of the link type to remove, in PascalCase (eg. "PostToComments")
+        name: String,
+    },
+    /// Remove a collection, with its link type, its zome functions, its UI and its tests
+    Collection {
+        #[structopt(long)]
+        /// Name of the dna from which you want to remove the collection
+        dna: Option<String>,
+
+        #[structopt(long)]
+        /// Name of the integrity zome from which you want to remove the collection
+        zome: Option<String>,
+
+        /// Name of the collection to remove
+        name: String,
+    },
+}
+
+impl Remove {
+    pub fn run(
+        self,
+        project_dir: &Path,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        let (dna, zome, name, item) = match &self {
+            Remove::EntryType { dna, zome, name } => (dna, zome, name, "Entry type"),
+            Remove::LinkType { dna, zome, name } => (dna, zome, name, "Link type"),
+            Remove::Collection { dna, zome, name } => (dna, zome, name, "Collection"),
+        };
+
+        let file_tree = load_directory_into_memory(project_dir)?;
+
+        let dna_file_tree = DnaFileTree::get_or_choose(file_tree, dna.as_deref(), interactivity)?;
+        let zome_file_tree =
+            ZomeFileTree::get_or_choose_integrity(dna_file_tree, zome.as_deref(), interactivity)?;
+        output.record_choice("dna", &zome_file_tree.dna_file_tree.dna_manifest.name());
+        output.record_choice("integrity_zome", &zome_file_tree.zome_manifest.name.0);
+
+        let Removal {
+            file_tree,
+            removed_files,
+            leftover_references,
+        } = match &self {
+            Remove::EntryType { .. } => remove_entry_type(zome_file_tree, name)?,
+            Remove::LinkType { .. } => remove_link_type(zome_file_tree, name)?,
+            Remove::Collection { .. } => remove_collection(zome_file_tree, name)?,
+        };
+
+        let built =
+            build_file_tree_with_removals(file_tree, &removed_files, project_dir, build_mode)?;
+        output.record_build(project_dir, build_mode, &built);
+        if !built.written {
+            return Ok(());
+        }
+
+        if let Err(e) = run_cargo_fmt_if_available(project_dir) {
+            output.println(format!(
+                "{}: {}",
+                "rustfmt exec failed: ".yellow(),
+                e.to_string().yellow()
+            ));
+        }
+
+        output.println(format!("\n{} {} removed!\n", item, name.italic()));
+
+        if !leftover_references.is_empty() {
+            output.println(format!(
+                "{}",
+                "These references were left in place since they were not generated by the scaffolding tool, review them by hand:"
+                    .yellow()
+            ));
+            for reference in leftover_references {
+                output.println(format!("  {reference}"));
+            }
+            output.println("");
+        }
+
+        Ok(())
+    }
+}
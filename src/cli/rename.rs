@@ -0,0 +1,128 @@
+use std::path::Path;
+
+use colored::Colorize;
+use structopt::StructOpt;
+
+use crate::{
+    file_tree::{build_file_tree_with_removals, load_directory_into_memory, BuildMode},
+    output::Output,
+    scaffold::{
+        dna::DnaFileTree, entry_type::rename_entry_type, rename::Renaming,
+        web_app::template_type::TemplateType, zome::rename_zome, zome::ZomeFileTree,
+    },
+    utils::{run_cargo_fmt_if_available, Interactivity},
+};
+
+#[derive(Debug, StructOpt)]
+#[structopt(setting = structopt::clap::AppSettings::InferSubcommands)]
+/// Rename an entry type or a zome consistently across the zomes, the UI and the tests
+pub enum Rename {
+    /// Rename an entry type in its integrity and coordinator zomes, and in its UI and tests
+    EntryType {
+        #[structopt(long)]
+        /// Name of the dna in which you want to rename the entry type
+        dna: Option<String>,
+
+        #[structopt(long)]
+        /// Name of the integrity zome in which you want to rename the entry type
+        zome: Option<String>,
+
+        /// Current name of the entry type
+        old_name: String,
+
+        /// New name for the entry type
+        new_name: String,
+    },
+    /// Rename a zome in the DNA manifest and the Cargo workspace, moving its crate, UI and tests.
+    /// Renaming a coordinator zome also renames its "<name>_integrity" zome
+    Zome {
+        #[structopt(long)]
+        /// Name of the dna in which you want to rename the zome
+        dna: Option<String>,
+
+        /// Current name of the zome
+        old_name: String,
+
+        /// New name for the zome
+        new_name: String,
+    },
+}
+
+impl Rename {
+    pub fn run(
+        self,
+        project_dir: &Path,
+        template_type: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        let file_tree = load_directory_into_memory(project_dir)?;
+
+        let (item, old_name, new_name, renaming) = match self {
+            Rename::EntryType {
+                dna,
+                zome,
+                old_name,
+                new_name,
+            } => {
+                let dna_file_tree =
+                    DnaFileTree::get_or_choose(file_tree, dna.as_deref(), interactivity)?;
+                let zome_file_tree = ZomeFileTree::get_or_choose_integrity(
+                    dna_file_tree,
+                    zome.as_deref(),
+                    interactivity,
+                )?;
+                output.record_choice("dna", &zome_file_tree.dna_file_tree.dna_manifest.name());
+                output.record_choice("integrity_zome", &zome_file_tree.zome_manifest.name.0);
+                let renaming = rename_entry_type(
+                    zome_file_tree,
+                    &template_type.file_tree()?,
+                    &old_name,
+                    &new_name,
+                )?;
+                ("Entry type", old_name, new_name, renaming)
+            }
+            Rename::Zome {
+                dna,
+                old_name,
+                new_name,
+            } => {
+                let dna_file_tree =
+                    DnaFileTree::get_or_choose(file_tree, dna.as_deref(), interactivity)?;
+                output.record_choice("dna", &dna_file_tree.dna_manifest.name());
+                let renaming = rename_zome(dna_file_tree, &old_name, &new_name)?;
+                ("Zome", old_name, new_name, renaming)
+            }
+        };
+
+        output.println(renaming.summary());
+
+        let removed_files = renaming.removed_files();
+        let Renaming { file_tree, .. } = renaming;
+
+        let built =
+            build_file_tree_with_removals(file_tree, &removed_files, project_dir, build_mode)?;
+        output.record_build(project_dir, build_mode, &built);
+        if !built.written {
+            return Ok(());
+        }
+
+        if let Err(e) = run_cargo_fmt_if_available(project_dir) {
+            output.println(format!(
+                "{}: {}",
+                "rustfmt exec failed: ".yellow(),
+                e.to_string().yellow()
+            ));
+        }
+
+        output.println(format!(
+            "\n{} {} renamed to {}!\n",
+            item,
+            old_name.italic(),
+            new_name.italic()
+        ));
+
+        Ok(())
+    }
+}
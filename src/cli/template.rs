@@ -1,9 +1,14 @@
-use std::{ffi::OsString, path::PathBuf};
+use std::path::Path;
 
-use build_fs_tree::{dir, file, Build, MergeableFileSystemTree};
+use build_fs_tree::{dir, file};
 use structopt::StructOpt;
 
-use crate::{scaffold::web_app::template_type::TemplateType, utils::input_with_case};
+use crate::{
+    file_tree::{build_file_tree_with_mode, BuildMode},
+    output::Output,
+    scaffold::web_app::template_type::TemplateType,
+    utils::{input_with_case, Interactivity},
+};
 
 #[derive(Debug, StructOpt)]
 #[structopt(setting = structopt::clap::AppSettings::InferSubcommands)]
@@ -20,18 +25,45 @@ pub enum Template {
 }
 
 impl Template {
-    pub fn run(self, template_type: &TemplateType) -> anyhow::Result<()> {
+    pub fn run(
+        self,
+        project_dir: &Path,
+        template_type: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
         match self {
-            Template::New => Template::new_template(template_type),
-            Template::Clone { to_template } => Template::clone_template(to_template, template_type),
+            Template::New => Template::new_template(
+                project_dir,
+                template_type,
+                build_mode,
+                interactivity,
+                output,
+            ),
+            Template::Clone { to_template } => Template::clone_template(
+                project_dir,
+                to_template,
+                template_type,
+                build_mode,
+                interactivity,
+                output,
+            ),
         }
     }
 
-    fn new_template(from_template: &TemplateType) -> anyhow::Result<()> {
+    fn new_template(
+        project_dir: &Path,
+        from_template: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
         let name = input_with_case(
             "Enter new template name (kebab-case):",
             Some(&from_template.name()),
             convert_case::Case::Kebab,
+            interactivity,
         )?;
 
         let template_file_tree = dir! {
@@ -43,33 +75,52 @@ impl Template {
             },
         };
 
-        let file_tree = MergeableFileSystemTree::<OsString, String>::from(template_file_tree);
-
-        file_tree.build(&PathBuf::from("."))?;
+        let built = build_file_tree_with_mode(template_file_tree, project_dir, build_mode)?;
+        output.record_build(project_dir, build_mode, &built);
+        if !built.written {
+            return Ok(());
+        }
 
-        println!(r#"Template initialized in path: ./{} "#, name);
+        output.println(format!(r#"Template initialized in path: ./{} "#, name));
 
         Ok(())
     }
 
     fn clone_template(
+        project_dir: &Path,
         to_template: Option<String>,
         template_type: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
     ) -> anyhow::Result<()> {
         let target_template = match to_template {
             Some(t) => t,
-            None => input_with_case("Enter new template name:", None, convert_case::Case::Kebab)?,
+            None => {
+                interactivity.ensure_interactive("--to-template")?;
+                input_with_case(
+                    "Enter new template name:",
+                    None,
+                    convert_case::Case::Kebab,
+                    interactivity,
+                )?
+            }
         };
 
         let template_file_tree = dir! {
             target_template.clone() => template_type.file_tree()?
         };
 
-        let file_tree = MergeableFileSystemTree::<OsString, String>::from(template_file_tree);
-
-        file_tree.build(&PathBuf::from("."))?;
+        let built = build_file_tree_with_mode(template_file_tree, project_dir, build_mode)?;
+        output.record_build(project_dir, build_mode, &built);
+        if !built.written {
+            return Ok(());
+        }
 
-        println!(r#"Template initialized in path: ./{} "#, target_template);
+        output.println(format!(
+            r#"Template initialized in path: ./{} "#,
+            target_template
+        ));
 
         Ok(())
     }
@@ -0,0 +1,159 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use structopt::StructOpt;
+
+use crate::{
+    error::ScaffoldError,
+    file_tree::{
+        build_file_tree_with_removals,
+        history::{list_operations, Operation},
+        unflatten_file_tree, BuildMode,
+    },
+    output::Output,
+};
+
+#[derive(Debug, StructOpt)]
+/// Revert the files changed by a previous scaffolding command, as recorded in .hc-scaffold/history
+pub struct Undo {
+    #[structopt(default_value = "1")]
+    /// Which operation to undo, counting from the most recent one (1 is the last operation)
+    pub n: usize,
+}
+
+impl Undo {
+    pub fn run(
+        self,
+        project_dir: &Path,
+        build_mode: BuildMode,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        let mut operations = list_operations(project_dir)?;
+        let operations_count = operations.len();
+
+        if self.n == 0 || self.n > operations_count {
+            return Err(ScaffoldError::OperationNotFound(self.n, operations_count))?;
+        }
+        let (entry_path, operation) = operations.remove(self.n - 1);
+
+        let changed_files = operation.files_changed_since(project_dir);
+        if !changed_files.is_empty() {
+            let changed_files: Vec<String> = changed_files
+                .iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect();
+            return Err(ScaffoldError::FilesChangedSinceOperation(
+                changed_files.join(", "),
+            ))?;
+        }
+
+        let (restored_files, removed_files) = files_to_restore(&operation);
+
+        let built = build_file_tree_with_removals(
+            unflatten_file_tree(&restored_files)?,
+            &removed_files,
+            project_dir,
+            build_mode,
+        )?;
+        output.record_build(project_dir, build_mode, &built);
+        if !built.written {
+            return Ok(());
+        }
+
+        fs::remove_file(entry_path)?;
+
+        output.println(format!(
+            "\nUndid {}, {} files were restored.\n",
+            operation.command.italic(),
+            operation.files.len()
+        ));
+
+        Ok(())
+    }
+}
+
+/// Splits the files of the operation into the ones that existed before it, with their content,
+/// and the ones it created
+fn files_to_restore(operation: &Operation) -> (BTreeMap<PathBuf, Option<String>>, Vec<PathBuf>) {
+    let mut restored_files = BTreeMap::new();
+    let mut removed_files = vec![];
+
+    for file in &operation.files {
+        match &file.before {
+            Some(before) => {
+                restored_files.insert(file.path.clone(), Some(before.clone()));
+            }
+            None => removed_files.push(file.path.clone()),
+        }
+    }
+
+    (restored_files, removed_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, path::PathBuf};
+
+    use crate::{
+        file_tree::history::tests::{run_operation, test_project_dir},
+        output::OutputFormat,
+    };
+
+    use super::*;
+
+    #[test]
+    fn restores_the_files_and_removes_the_created_ones() {
+        let project_dir = test_project_dir("undo-test");
+        fs::write(project_dir.join("changed.txt"), "before").unwrap();
+        run_operation(
+            &project_dir,
+            &[("changed.txt", Some("after")), ("created.txt", Some("new"))],
+        );
+
+        let mut output = Output::new(OutputFormat::Json, "hc-scaffold undo");
+        output.record_project_dir(&project_dir);
+        let result = Undo { n: 1 }.run(&project_dir, BuildMode::Write, &mut output);
+        let report = output.finish(&result);
+
+        assert!(report.success);
+        assert_eq!(
+            report.removed_files,
+            BTreeSet::from([PathBuf::from("created.txt")])
+        );
+        assert_eq!(
+            fs::read_to_string(project_dir.join("changed.txt")).unwrap(),
+            "before"
+        );
+        assert!(!project_dir.join("created.txt").exists());
+        assert!(list_operations(&project_dir).unwrap().is_empty());
+
+        fs::remove_dir_all(&project_dir).unwrap();
+    }
+
+    #[test]
+    fn refuses_to_undo_if_a_file_changed_after_the_operation() {
+        let project_dir = test_project_dir("undo-changed-test");
+        fs::write(project_dir.join("changed.txt"), "before").unwrap();
+        run_operation(&project_dir, &[("changed.txt", Some("after"))]);
+        fs::write(project_dir.join("changed.txt"), "edited").unwrap();
+
+        let result = Undo { n: 1 }.run(
+            &project_dir,
+            BuildMode::Write,
+            &mut Output::new(OutputFormat::Text, "hc-scaffold undo"),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            fs::read_to_string(project_dir.join("changed.txt")).unwrap(),
+            "edited"
+        );
+        assert_eq!(list_operations(&project_dir).unwrap().len(), 1);
+
+        fs::remove_dir_all(&project_dir).unwrap();
+    }
+}
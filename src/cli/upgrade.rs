@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use colored::Colorize;
+use structopt::StructOpt;
+
+use crate::{
+    file_tree::{build_file_tree_with_mode, load_directory_into_memory, BuildMode},
+    output::Output,
+    scaffold::upgrade::migration_steps,
+    utils::run_cargo_fmt_if_available,
+};
+
+#[derive(Debug, StructOpt)]
+/// Migrate the dependency versions and the generated code of the project to the ones of this version of the scaffolding tool
+pub struct Upgrade {}
+
+impl Upgrade {
+    pub fn run(
+        self,
+        project_dir: &Path,
+        build_mode: BuildMode,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        let mut file_tree = load_directory_into_memory(project_dir)?;
+
+        let mut changed = false;
+        for step in migration_steps() {
+            output.println(format!(
+                "{} {}",
+                step.description.bold(),
+                format!("(holochain {})", step.holochain_version).dimmed()
+            ));
+
+            let changes = (step.migrate)(&mut file_tree)?;
+            if changes.is_empty() {
+                output.println("  Already up to date.");
+            }
+            for change in &changes {
+                output.println(format!("  {} {change}", "✓".green()));
+            }
+            changed |= !changes.is_empty();
+        }
+        output.println("");
+
+        if !changed {
+            output.println("The project is already up to date.");
+            return Ok(());
+        }
+
+        let built = build_file_tree_with_mode(file_tree, project_dir, build_mode)?;
+        output.record_build(project_dir, build_mode, &built);
+        if !built.written {
+            return Ok(());
+        }
+
+        if let Err(e) = run_cargo_fmt_if_available(project_dir) {
+            output.println(format!(
+                "{}: {}",
+                "rustfmt exec failed: ".yellow(),
+                e.to_string().yellow()
+            ));
+        }
+
+        output.println(r#"Project upgraded! Install the new dependencies with your package manager, and check that everything still builds:
+
+  npm install
+  npm test
+"#);
+
+        Ok(())
+    }
+}
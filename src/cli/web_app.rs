@@ -1,8 +1,4 @@
-use std::{
-    env,
-    path::{Path, PathBuf},
-    str::FromStr,
-};
+use std::{path::Path, str::FromStr};
 
 use colored::Colorize;
 use convert_case::{Case, Casing};
@@ -11,11 +7,11 @@ use tokio::fs;
 
 use crate::{
     error::{ScaffoldError, ScaffoldResult},
-    file_tree::{build_file_tree, load_directory_into_memory, FileTree},
+    file_tree::{build_file_tree_with_mode, BuildMode, FileTree},
+    output::Output,
     scaffold::{
-        app::{git::setup_git_environment, nix::setup_nix_developer_environment, AppFileTree},
+        app::{git::setup_git_environment, nix::setup_nix_developer_environment},
         config::ScaffoldConfig,
-        dna::scaffold_dna,
         web_app::{
             package_manager::{PackageManager, SubCommand},
             scaffold_web_app,
@@ -23,8 +19,9 @@ use crate::{
         },
         zome::scaffold_zome_pair,
     },
+    scaffolder::{DnaAnswers, Scaffolder},
     templates::ScaffoldedTemplate,
-    utils::{input_no_whitespace, input_with_case, input_yes_or_no, validate_input},
+    utils::{input_no_whitespace, input_with_case, input_yes_or_no, validate_input, Interactivity},
 };
 
 #[derive(Debug, StructOpt)]
@@ -53,17 +50,26 @@ pub struct WebApp {
 }
 
 impl WebApp {
-    pub async fn run(self, template_type: &TemplateType) -> anyhow::Result<()> {
-        let current_dir = std::env::current_dir()?;
+    pub async fn run(
+        self,
+        project_dir: &Path,
+        template_type: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
         let name = match self.name {
             Some(n) => {
                 validate_input(&n, "app name")?;
                 n
             }
-            None => input_no_whitespace("App name (no whitespaces):")?,
+            None => {
+                interactivity.ensure_interactive("<name>")?;
+                input_no_whitespace("App name (no whitespaces):", interactivity)?
+            }
         };
 
-        let app_folder = current_dir.join(&name);
+        let app_folder = project_dir.join(&name);
 
         if app_folder.as_path().exists() {
             return Err(ScaffoldError::FolderAlreadyExists(app_folder.clone()))?;
@@ -79,12 +85,13 @@ impl WebApp {
             input_yes_or_no(
                 "Do you want to set up the holonix development environment for this project?",
                 Some(true),
+                interactivity,
             )?
         };
 
         let package_manager = match self.package_manager {
             Some(p) => p,
-            None => PackageManager::choose()?,
+            None => PackageManager::choose(interactivity)?,
         };
 
         let ScaffoldedTemplate {
@@ -97,13 +104,18 @@ impl WebApp {
             !setup_nix,
             &template_file_tree,
             self.holo_enabled,
+            interactivity,
         )?;
 
         if !template_type.is_nixified_custom_template() {
             ScaffoldConfig::write_to_package_json(&mut file_tree, template_type)?;
         }
 
-        build_file_tree(file_tree, &app_folder)?;
+        let built = build_file_tree_with_mode(file_tree, &app_folder, build_mode)?;
+        output.record_build(&app_folder, build_mode, &built);
+        if !built.written {
+            return Ok(());
+        }
 
         let mut nix_instructions = "";
 
@@ -115,14 +127,28 @@ impl WebApp {
             nix_instructions = "\n  nix develop";
         }
 
-        println!("Your Web hApp {} has been scaffolded!\n", name.italic());
+        output.println(format!(
+            "Your Web hApp {} has been scaffolded!\n",
+            name.italic()
+        ));
 
         let mut disable_fast_track = self.disable_fast_track;
 
         if !disable_fast_track
-            && input_yes_or_no("Do you want to scaffold an initial DNA? (y/n)", None)?
+            && input_yes_or_no(
+                "Do you want to scaffold an initial DNA? (y/n)",
+                Some(true),
+                interactivity,
+            )?
         {
-            WebApp::scaffold_initial_dna_and_zomes(&name, template_file_tree, &current_dir)?;
+            WebApp::scaffold_initial_dna_and_zomes(
+                &name,
+                template_file_tree,
+                &app_folder,
+                build_mode,
+                interactivity,
+                output,
+            )?;
         } else {
             disable_fast_track = true;
         }
@@ -130,14 +156,17 @@ impl WebApp {
         setup_git_environment(&app_folder)?;
 
         if let Some(instructions) = next_instructions {
-            println!("{instructions}");
+            output.print_next_instructions(&instructions);
         } else {
-            let dna_instructions = disable_fast_track.then_some(
+            let dna_instructions = if disable_fast_track {
                 r#"
 - Get your project to compile by adding a DNA and then following the next insturctions to add a zome to that DNA:
 
-  hc scaffold dna"#).unwrap_or_default();
-            println!(
+  hc scaffold dna"#
+            } else {
+                ""
+            };
+            output.print_next_instructions(&format!(
                 r#"
 This skeleton provides the basic structure for your Holochain web application.
 The UI is currently empty; you will need to import necessary components into the top-level app component to populate it.
@@ -159,7 +188,7 @@ Here's how you can get started with developing your application:
                 "#,
                 package_manager.run_command_string(SubCommand::Install, None),
                 package_manager.run_command_string(SubCommand::Run("start".to_string()), None)
-            );
+            ));
         }
 
         Ok(())
@@ -168,27 +197,45 @@ Here's how you can get started with developing your application:
     fn scaffold_initial_dna_and_zomes(
         name: &str,
         template_file_tree: FileTree,
-        path: &Path,
+        app_folder: &Path,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
     ) -> ScaffoldResult<()> {
-        env::set_current_dir(PathBuf::from(&name))?;
         let dna_name = input_with_case(
             "Initial DNA name (snake_case):",
             Some(&name.to_case(Case::Snake)),
             Case::Snake,
+            interactivity,
         )?;
 
-        let file_tree = load_directory_into_memory(&path.join(name))?;
-        let app_file_tree = AppFileTree::get_or_choose(file_tree, Some(name))?;
-
-        let ScaffoldedTemplate { file_tree, .. } =
-            scaffold_dna(app_file_tree, &template_file_tree, &dna_name)?;
-
-        if input_yes_or_no("Do you want to scaffold an initial coordinator/integrity zome pair for your DNA? (y/n)", None)? {
-            scaffold_zome_pair(file_tree, template_file_tree, &dna_name)?;
-            println!("Coordinator/integrity zome pair scaffolded.")
+        let scaffolder =
+            Scaffolder::new(app_folder, template_file_tree).with_interactivity(interactivity);
+        let ScaffoldedTemplate { file_tree, .. } = scaffolder.dna(DnaAnswers {
+            app: Some(name.to_owned()),
+            name: dna_name.clone(),
+        })?;
+
+        let (file_tree, scaffolded) = if input_yes_or_no(
+            "Do you want to scaffold an initial coordinator/integrity zome pair for your DNA? (y/n)",
+            Some(true),
+            interactivity,
+        )? {
+            let file_tree = scaffold_zome_pair(
+                file_tree,
+                scaffolder.template_file_tree().clone(),
+                &dna_name,
+                interactivity,
+            )?;
+            (file_tree, "Coordinator/integrity zome pair scaffolded.")
         } else {
-            build_file_tree(file_tree, ".")?;
-            println!("DNA scaffolded.");
+            (file_tree, "DNA scaffolded.")
+        };
+
+        let built = scaffolder.write(file_tree, build_mode)?;
+        output.record_build(app_folder, build_mode, &built);
+        if built.written {
+            output.println(scaffolded);
         }
 
         Ok(())
@@ -1,23 +1,20 @@
-use std::{ffi::OsString, path::PathBuf};
+use std::path::{Path, PathBuf};
 
-use build_fs_tree::{Build, MergeableFileSystemTree};
 use colored::Colorize;
 use convert_case::Case;
 use structopt::StructOpt;
 
 use crate::{
-    file_tree::load_directory_into_memory,
+    file_tree::BuildMode,
+    output::Output,
     scaffold::{
         app::cargo::exec_metadata,
-        dna::DnaFileTree,
         web_app::template_type::TemplateType,
-        zome::{
-            integrity_zome_name, scaffold_coordinator_zome, scaffold_integrity_zome,
-            utils::{select_integrity_zomes, select_scaffold_zome_options},
-        },
+        zome::{integrity_zome_name, utils::select_scaffold_zome_options},
     },
+    scaffolder::{Scaffolder, ZomeAnswers},
     templates::ScaffoldedTemplate,
-    utils::{check_case, input_with_case, run_cargo_fmt_if_available},
+    utils::{check_case, input_with_case, Interactivity},
 };
 
 #[derive(Debug, StructOpt)]
@@ -40,10 +37,16 @@ pub struct Zome {
 }
 
 impl Zome {
-    pub fn run(self, template_type: &TemplateType) -> anyhow::Result<()> {
-        let current_dir = std::env::current_dir()?;
-        let file_tree = load_directory_into_memory(&current_dir)?;
-        let template_file_tree = template_type.file_tree()?;
+    pub fn run(
+        self,
+        project_dir: &Path,
+        template_type: &TemplateType,
+        build_mode: BuildMode,
+        interactivity: Interactivity,
+        output: &mut Output,
+    ) -> anyhow::Result<()> {
+        let scaffolder = Scaffolder::with_template_type(project_dir, template_type)?
+            .with_interactivity(interactivity);
 
         if let Some(n) = self.name.clone() {
             check_case(&n, "zome name", Case::Snake)?;
@@ -51,7 +54,7 @@ impl Zome {
 
         let (scaffold_integrity, scaffold_coordinator) = match (&self.integrity, &self.coordinator)
         {
-            (None, None) => select_scaffold_zome_options()?,
+            (None, None) => select_scaffold_zome_options(interactivity)?,
             _ => (self.integrity.is_some(), self.coordinator.is_some()),
         };
 
@@ -63,13 +66,28 @@ impl Zome {
 
         let name = match self.name {
             Some(n) => n,
-            None => input_with_case(name_prompt, None, Case::Snake)?,
+            None => {
+                interactivity.ensure_interactive("<name>")?;
+                input_with_case(name_prompt, None, Case::Snake, interactivity)?
+            }
         };
-
-        let mut dna_file_tree = DnaFileTree::get_or_choose(file_tree, self.dna.as_deref())?;
-        let dna_manifest_path = dna_file_tree.dna_manifest_path.clone();
-
-        let mut zome_next_instructions: (Option<String>, Option<String>) = Default::default();
+        output.record_choice("zome", &name);
+
+        let dna = scaffolder.choose_dna(self.dna.as_deref())?;
+        output.record_choice("dna", &dna);
+
+        let ScaffoldedTemplate {
+            file_tree,
+            next_instructions,
+        } = scaffolder.zome(ZomeAnswers {
+            dna: Some(dna),
+            name: name.clone(),
+            integrity: scaffold_integrity,
+            integrity_path: self.integrity,
+            coordinator: scaffold_coordinator,
+            coordinator_path: self.coordinator,
+            dependencies: None,
+        })?;
 
         if scaffold_integrity {
             let integrity_zome_name = if scaffold_coordinator {
@@ -77,81 +95,29 @@ impl Zome {
             } else {
                 name.clone()
             };
-            let ScaffoldedTemplate {
-                file_tree,
-                next_instructions,
-            } = scaffold_integrity_zome(
-                dna_file_tree,
-                &template_file_tree,
-                &integrity_zome_name,
-                &self.integrity,
-            )?;
-
-            zome_next_instructions.0 = next_instructions;
-
-            println!(
+            output.println(format!(
                 "Integrity zome {} scaffolded!\n",
                 integrity_zome_name.italic(),
-            );
-
-            dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+            ));
         }
-
         if scaffold_coordinator {
-            let dependencies = {
-                if scaffold_integrity {
-                    Some(vec![integrity_zome_name(&name)])
-                } else {
-                    let integrity_zomes = select_integrity_zomes(&dna_file_tree.dna_manifest, Some(
-                      "Select integrity zome(s) this coordinator zome depends on (SPACE to select/unselect, ENTER to continue):"
-                    ))?;
-                    Some(integrity_zomes)
-                }
-            };
-            let ScaffoldedTemplate {
-                file_tree,
-                next_instructions,
-            } = scaffold_coordinator_zome(
-                dna_file_tree,
-                &template_file_tree,
-                &name,
-                dependencies.as_ref(),
-                &self.coordinator,
-            )?;
-            zome_next_instructions.1 = next_instructions;
-
-            println!("Coordinator zome {} scaffolded!\n", name.italic());
-
-            dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+            output.println(format!("Coordinator zome {} scaffolded!\n", name.italic()));
         }
 
-        // TODO: implement scaffold_zome_template
-        let file_tree =
-            MergeableFileSystemTree::<OsString, String>::from(dna_file_tree.file_tree());
-
         // FIXME: avoid cloning
         let f = file_tree.clone();
-        file_tree.build(&PathBuf::from("."))?;
-
-        if let Err(e) = run_cargo_fmt_if_available() {
-            println!(
-                "{}: {}",
-                "rustfmt exec failed: ".yellow(),
-                e.to_string().yellow()
-            );
+        let built = scaffolder.write(file_tree, build_mode)?;
+        output.record_build(scaffolder.project_dir(), build_mode, &built);
+        if !built.written {
+            return Ok(());
         }
 
         // Execute cargo metadata to set up the cargo workspace in case this zome is the first crate
-        exec_metadata(&f)?;
+        exec_metadata(project_dir, &f)?;
 
-        match zome_next_instructions {
-            (Some(integrity), Some(coordinator)) => {
-                println!("\n{integrity}");
-                println!("\n{coordinator}");
-            }
-            (None, Some(coordinator)) => println!("\n{coordinator}"),
-            (Some(integrity), None) => println!("\n{integrity}"),
-            _ => println!(
+        match next_instructions {
+            Some(i) => output.print_next_instructions(&format!("\n{i}")),
+            None => output.print_next_instructions(
                 r#"
 Add new entry definitions to your zome with:
 
@@ -1,47 +1,61 @@
-use std::{io, path::PathBuf};
+use std::{fmt::Display, io, path::PathBuf};
 
+use serde::{Serialize, Serializer};
 use thiserror;
 
-#[derive(Debug, thiserror::Error)]
+/// Serializes as `{ "kind": <VARIANT>, "fields": <FIELDS> }`, with the foreign errors serialized as their message
+#[derive(Debug, thiserror::Error, Serialize)]
+#[serde(tag = "kind", content = "fields")]
 pub enum ScaffoldError {
     /// std::io::Error
     #[error("IO error: {0}")]
+    #[serde(serialize_with = "serialize_display")]
     StdIoError(#[from] std::io::Error),
 
     /// MrBundleError
     #[error(transparent)]
+    #[serde(serialize_with = "serialize_display")]
     MrBundleError(#[from] mr_bundle::error::MrBundleError),
 
     /// MrBundleError
     #[error(transparent)]
+    #[serde(serialize_with = "serialize_display")]
     CargoMetadataError(#[from] cargo_metadata::Error),
 
     /// serde_yaml::Error
     #[error("YAML serialization error: {0}")]
+    #[serde(serialize_with = "serialize_display")]
     SerdeYamlError(#[from] serde_yaml::Error),
 
     #[error("JSON serialization error: {0}")]
+    #[serde(serialize_with = "serialize_display")]
     SerdeJsonError(#[from] serde_json::Error),
 
     #[error("TOML deserialization error: {0}")]
+    #[serde(serialize_with = "serialize_display")]
     TomlDeError(#[from] toml::de::Error),
 
     #[error("TOML serialization error: {0}")]
+    #[serde(serialize_with = "serialize_display")]
     TomlSerError(#[from] toml::ser::Error),
 
     #[error(transparent)]
+    #[serde(serialize_with = "serialize_display")]
     SynError(#[from] syn::Error),
 
     #[error("Error setting up the git repository")]
     GitInitError,
 
     #[error(transparent)]
+    #[serde(serialize_with = "serialize_display")]
     IgnoreError(#[from] ignore::Error),
 
     #[error(transparent)]
+    #[serde(serialize_with = "serialize_display")]
     HandlebarsRenderError(#[from] handlebars::RenderError),
 
     #[error(transparent)]
+    #[serde(serialize_with = "serialize_display")]
     HandlebarsTemplateError(#[from] Box<handlebars::TemplateError>), // Boxed to address TemplateError being too large
 
     #[error("Path was not found: {0}")]
@@ -77,6 +91,15 @@ pub enum ScaffoldError {
     #[error("Malformed file {0}: {1}")]
     MalformedFile(PathBuf, String),
 
+    #[error("Operation {0} was not found in the scaffolding history, which holds {1} operations")]
+    OperationNotFound(usize, usize),
+
+    #[error("These files were changed since the operation was run, so it can't be undone: {0}")]
+    FilesChangedSinceOperation(String),
+
+    #[error("{0} problems were found in the project")]
+    ProblemsFound(usize),
+
     #[error("Malformed template: {0}")]
     MalformedTemplate(String),
 
@@ -86,6 +109,9 @@ pub enum ScaffoldError {
     #[error("Zome \"{0}\" already exists in dna \"{1}\"")]
     ZomeAlreadyExists(String, String),
 
+    #[error("Zome \"{0}\" was not found in dna \"{1}\"")]
+    ZomeNotFound(String, String),
+
     #[error("Integrity zome \"{0}\" was not found in dna \"{1}\"")]
     IntegrityZomeNotFound(String, String),
 
@@ -107,6 +133,12 @@ pub enum ScaffoldError {
     #[error("Invalid UI framework \"{0}\", here are all valid UI frameworks: \"{1}\"")]
     InvalidUiFramework(String, String),
 
+    #[error("These constructs of the schema can't be mapped onto entry type fields: {0}")]
+    UnsupportedSchemaConstructs(String),
+
+    #[error("Type \"{0}\" of {1} can't be mapped to a TypeScript type, as it's neither a type known to the scaffolding tool nor a struct or an enum with unit variants defined in the zome")]
+    UnsupportedRustType(String, String),
+
     #[error("Invalid string format: \"{0}\"")]
     InvalidStringFormat(String),
 
@@ -128,19 +160,52 @@ pub enum ScaffoldError {
     #[error("Entry type \"{0}\" was not found in dna \"{1}\" for the integrity zome \"{2}\"")]
     EntryTypeNotFound(String, String, String),
 
+    #[error("Entry type \"{0}\" is private, so it can't be {1} without revealing its entries to other agents")]
+    PrivateEntryType(String, String),
+
+    #[error("Field \"{0}\" already exists in entry type \"{1}\"")]
+    FieldAlreadyExists(String, String),
+
+    #[error("Field \"{0}\" was not found in entry type \"{1}\"")]
+    FieldNotFound(String, String),
+
+    #[error("The removed fields are still used by the code generated for entry type \"{0}\", edit these lines by hand and run the command again:\n{1}")]
+    FieldsStillUsed(String, String),
+
     #[error("Link type \"{0}\" already exists in dna \"{1}\" for the integrity zome \"{2}\"")]
     LinkTypeAlreadyExists(String, String, String),
 
+    #[error("Link type \"{0}\" was not found in dna \"{1}\" for the integrity zome \"{2}\"")]
+    LinkTypeNotFound(String, String, String),
+
+    #[error("Collection \"{0}\" was not found in dna \"{1}\" for the integrity zome \"{2}\"")]
+    CollectionNotFound(String, String, String),
+
+    #[error("Missing required argument {0}, which can't be prompted for in non-interactive mode")]
+    MissingArgument(String),
+
+    #[error("Can't prompt \"{0}\" in non-interactive mode, pass the value as an argument instead")]
+    PromptInNonInteractiveMode(String),
+
     #[error("Invalid arguments: \"{0}\"")]
     InvalidArguments(String),
 
     #[error("Failed to build file tree: {0}")]
+    #[serde(serialize_with = "serialize_display")]
     FsBuildError(#[from] build_fs_tree::BuildError<PathBuf, io::Error>),
 
     /// anything else
     #[error("Unexpected error: {0}")]
+    #[serde(serialize_with = "serialize_display")]
     MiscError(#[from] anyhow::Error),
 }
 
+fn serialize_display<T: Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
 /// HcBundle Result type.
 pub type ScaffoldResult<T> = Result<T, ScaffoldError>;
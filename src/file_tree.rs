@@ -9,7 +9,13 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::error::{ScaffoldError, ScaffoldResult};
-use crate::utils::unparse_pretty;
+use crate::utils::{input_yes_or_no, unparse_pretty, Interactivity};
+
+pub mod diff;
+pub mod history;
+pub mod pristine;
+
+use diff::FileTreeDiff;
 
 pub type FileTree = FileSystemTree<OsString, String>;
 
@@ -17,7 +23,12 @@ pub type FileTree = FileSystemTree<OsString, String>;
 pub fn load_directory_into_memory(path: &Path) -> ScaffoldResult<FileTree> {
     let mut file_tree: FileTree = dir! {};
 
-    for result in WalkBuilder::new(path).hidden(false).build() {
+    // The scaffolding history is not part of the app
+    for result in WalkBuilder::new(path)
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != ".hc-scaffold")
+        .build()
+    {
         let dir_entry = result?
             .path()
             .iter()
@@ -31,6 +42,15 @@ pub fn load_directory_into_memory(path: &Path) -> ScaffoldResult<FileTree> {
         }
     }
 
+    // Unlike the history, the pristine store is loaded as the templates are merged with it
+    let pristine_store_path = Path::new(pristine::PRISTINE_STORE_PATH);
+    if let Ok(contents) = fs::read_to_string(path.join(pristine_store_path)) {
+        if let Some(parent) = pristine_store_path.parent() {
+            create_dir_all(&mut file_tree, parent)?;
+        }
+        insert_file(&mut file_tree, pristine_store_path, &contents)?;
+    }
+
     Ok(file_tree)
 }
 
@@ -92,6 +112,23 @@ pub fn insert_file(
     )
 }
 
+/// Removes the file in the given path from the file tree, returning its contents
+pub fn remove_file(file_tree: &mut FileTree, file_path: &Path) -> ScaffoldResult<String> {
+    let mut folder_path = file_path.to_path_buf();
+    folder_path.pop();
+
+    let v: Vec<OsString> = folder_path.iter().map(|s| s.to_os_string()).collect();
+    let removed = file_tree
+        .path_mut(&mut v.iter())
+        .and_then(|folder| folder.dir_content_mut())
+        .and_then(|content| content.remove(file_path.file_name()?));
+
+    match removed {
+        Some(FileTree::File(contents)) => Ok(contents),
+        _ => Err(ScaffoldError::PathNotFound(file_path.to_path_buf())),
+    }
+}
+
 pub fn insert_file_tree_in_dir(
     file_tree: &mut FileTree,
     folder_path: &Path,
@@ -398,3 +435,121 @@ pub fn build_file_tree(file_tree: FileTree, path: impl Into<PathBuf>) -> Result<
     mergeable_tree.build(&path.into())?;
     Ok(())
 }
+
+/// How a scaffolded file tree should be applied to the file system
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BuildMode {
+    /// Write the file tree to disk straight away
+    #[default]
+    Write,
+    /// Print the differences with what is on disk and write nothing
+    DryRun,
+    /// Print the differences with what is on disk and ask for confirmation before writing
+    Preview,
+}
+
+/// Result of building a file tree on disk
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuiltFileTree {
+    /// Whether the file tree was written to disk, which is not the case with [`BuildMode::DryRun`]
+    /// or when the changes are not confirmed with [`BuildMode::Preview`]
+    pub written: bool,
+    /// The differences between the file tree and what was on disk before it was built
+    pub diff: FileTreeDiff,
+    /// Content on disk of the written and removed files before the file tree was built,
+    /// by their path relative to the folder it was built in, `None` for the files that didn't exist
+    pub files_before: BTreeMap<PathBuf, Option<String>>,
+}
+
+/// Builds the file tree in the given path according to the given build mode
+pub fn build_file_tree_with_mode(
+    file_tree: FileTree,
+    path: impl Into<PathBuf>,
+    build_mode: BuildMode,
+) -> ScaffoldResult<BuiltFileTree> {
+    build_file_tree_with_removals(file_tree, &[], path, build_mode)
+}
+
+/// Same as [`build_file_tree_with_mode`], but also deletes the given files, relative to `path`, from disk
+pub fn build_file_tree_with_removals(
+    file_tree: FileTree,
+    removed_files: &[PathBuf],
+    path: impl Into<PathBuf>,
+    build_mode: BuildMode,
+) -> ScaffoldResult<BuiltFileTree> {
+    let path: PathBuf = path.into();
+
+    // Only the files that are written or removed are read from disk
+    let files_before: BTreeMap<PathBuf, Option<String>> = flatten_file_tree(&file_tree)
+        .into_iter()
+        .filter_map(|(file, content)| content.map(|_| file))
+        .chain(removed_files.iter().cloned())
+        .map(|file| {
+            let content = fs::read_to_string(path.join(&file)).ok();
+            (file, content)
+        })
+        .collect();
+    let existing_files = files_before
+        .iter()
+        .filter(|(_, content)| content.is_some())
+        .map(|(file, content)| (file.clone(), content.clone()))
+        .collect();
+    let existing_file_tree = unflatten_file_tree(&existing_files)?;
+    let diff = FileTreeDiff::new(&existing_file_tree, &file_tree)
+        .with_removed_files(&existing_file_tree, removed_files);
+
+    let confirmed = match build_mode {
+        BuildMode::Write => true,
+        BuildMode::DryRun => false,
+        BuildMode::Preview => {
+            println!(
+                "{}",
+                diff.render(colored::control::SHOULD_COLORIZE.should_colorize())
+            );
+
+            // The command line rejects --preview when it can't prompt
+            !diff.has_changes()
+                || input_yes_or_no(
+                    "Write these changes to disk?",
+                    None,
+                    Interactivity::Interactive,
+                )?
+        }
+    };
+
+    if confirmed {
+        write_file_tree(file_tree, removed_files, &path)?;
+    }
+
+    Ok(BuiltFileTree {
+        written: confirmed,
+        diff,
+        files_before,
+    })
+}
+
+fn write_file_tree(
+    file_tree: FileTree,
+    removed_files: &[PathBuf],
+    path: &Path,
+) -> ScaffoldResult<()> {
+    build_file_tree(file_tree, path)?;
+
+    for removed_file in removed_files {
+        let removed_path = path.join(removed_file);
+        if removed_path.is_file() {
+            fs::remove_file(&removed_path)?;
+        }
+
+        // Clean up the folders left empty, eg. after moving all the files of a zome
+        let mut parent = removed_path.parent();
+        while let Some(dir) = parent {
+            if dir == path || fs::remove_dir(dir).is_err() {
+                break;
+            }
+            parent = dir.parent();
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,274 @@
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use diffy::{DiffOptions, PatchFormatter};
+
+use super::{flatten_file_tree, FileTree};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    Added,
+    Modified,
+    Removed,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    pub path: PathBuf,
+    pub change: FileChange,
+    pub old_content: Option<String>,
+    pub new_content: String,
+}
+
+impl FileDiff {
+    /// Unified diff of this file, with paths prefixed by `a/` and `b/` like `git diff`
+    pub fn unified_diff(&self, with_color: bool) -> String {
+        let path = self.path.to_string_lossy();
+        let original = match self.change {
+            FileChange::Added => String::from("/dev/null"),
+            _ => format!("a/{path}"),
+        };
+
+        let modified = match self.change {
+            FileChange::Removed => String::from("/dev/null"),
+            _ => format!("b/{path}"),
+        };
+
+        patch_formatter(with_color)
+            .fmt_patch(
+                &DiffOptions::new()
+                    .set_original_filename(original)
+                    .set_modified_filename(modified)
+                    .create_patch(
+                        self.old_content.as_deref().unwrap_or_default(),
+                        &self.new_content,
+                    ),
+            )
+            .to_string()
+    }
+}
+
+fn patch_formatter(with_color: bool) -> PatchFormatter {
+    if with_color {
+        PatchFormatter::new().with_color()
+    } else {
+        PatchFormatter::new()
+    }
+}
+
+/// The per-file differences between what is currently on disk and a scaffolded file tree
+///
+/// Only the files present in the scaffolded tree are compared: building a file tree never
+/// removes files, so anything that exists only on disk is left untouched unless it is
+/// explicitly marked as removed with [`FileTreeDiff::with_removed_files`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileTreeDiff {
+    pub files: Vec<FileDiff>,
+}
+
+impl FileTreeDiff {
+    pub fn new(existing: &FileTree, scaffolded: &FileTree) -> Self {
+        let existing_files = flatten_file_tree(existing);
+
+        // The files kept by the scaffolding tool in .hc-scaffold are not shown to the user
+        let files = flatten_file_tree(scaffolded)
+            .into_iter()
+            .filter(|(path, _)| !path.starts_with(".hc-scaffold"))
+            .filter_map(|(path, content)| content.map(|c| (path, c)))
+            .map(|(path, new_content)| {
+                let old_content = existing_files.get(&path).cloned().flatten();
+                let change = match &old_content {
+                    None => FileChange::Added,
+                    Some(old) if *old == new_content => FileChange::Unchanged,
+                    Some(_) => FileChange::Modified,
+                };
+                FileDiff {
+                    path,
+                    change,
+                    old_content,
+                    new_content,
+                }
+            })
+            .collect();
+
+        FileTreeDiff { files }
+    }
+
+    /// Adds the given files, which exist in the existing tree, as removed
+    pub fn with_removed_files(mut self, existing: &FileTree, removed_files: &[PathBuf]) -> Self {
+        let existing_files = flatten_file_tree(existing);
+
+        for path in removed_files {
+            if let Some(Some(old_content)) = existing_files.get(path) {
+                self.files.push(FileDiff {
+                    path: path.clone(),
+                    change: FileChange::Removed,
+                    old_content: Some(old_content.clone()),
+                    new_content: String::new(),
+                });
+            }
+        }
+
+        self
+    }
+
+    pub fn paths_with_change(&self, change: FileChange) -> impl Iterator<Item = &Path> {
+        self.files
+            .iter()
+            .filter(move |f| f.change == change)
+            .map(|f| f.path.as_path())
+    }
+
+    /// Whether building the scaffolded tree would add or modify any file
+    pub fn has_changes(&self) -> bool {
+        self.files.iter().any(|f| f.change != FileChange::Unchanged)
+    }
+
+    /// Renders a summary of the added, modified and unchanged files, followed by the unified diff of every changed file
+    pub fn render(&self, with_color: bool) -> String {
+        let added: Vec<&Path> = self.paths_with_change(FileChange::Added).collect();
+        let modified: Vec<&Path> = self.paths_with_change(FileChange::Modified).collect();
+        let removed: Vec<&Path> = self.paths_with_change(FileChange::Removed).collect();
+        let unchanged_count = self.paths_with_change(FileChange::Unchanged).count();
+
+        let mut output = String::new();
+
+        for (label, paths) in [
+            ("Added", &added),
+            ("Modified", &modified),
+            ("Removed", &removed),
+        ] {
+            if paths.is_empty() {
+                continue;
+            }
+            output.push_str(&format!("{label} files:\n"));
+            for path in paths {
+                let line = format!("  {}", path.display());
+                let line = match (with_color, label) {
+                    (true, "Added") => line.green().to_string(),
+                    (true, "Removed") => line.red().to_string(),
+                    (true, _) => line.yellow().to_string(),
+                    _ => line,
+                };
+                output.push_str(&line);
+                output.push('\n');
+            }
+            output.push('\n');
+        }
+
+        let removed_count = if removed.is_empty() {
+            String::new()
+        } else {
+            format!("{} removed, ", removed.len())
+        };
+        output.push_str(&format!(
+            "{} added, {} modified, {}{} unchanged\n",
+            added.len(),
+            modified.len(),
+            removed_count,
+            unchanged_count
+        ));
+
+        for file in self.files.iter() {
+            if file.change != FileChange::Unchanged {
+                output.push('\n');
+                output.push_str(&file.unified_diff(with_color));
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use build_fs_tree::{dir, file};
+
+    use super::*;
+
+    #[test]
+    fn classifies_added_modified_and_unchanged_files() {
+        let existing: FileTree = dir! {
+            "Cargo.toml" => file!("[workspace]\n"),
+            "src" => dir! {
+                "lib.rs" => file!("fn a() {}\n"),
+                "hand_written.rs" => file!("fn b() {}\n"),
+            }
+        };
+        let scaffolded: FileTree = dir! {
+            "Cargo.toml" => file!("[workspace]\n"),
+            "src" => dir! {
+                "lib.rs" => file!("fn a() {}\nfn c() {}\n"),
+                "hand_written.rs" => file!("fn b() {}\n"),
+                "new.rs" => file!("fn d() {}\n"),
+            }
+        };
+
+        let diff = FileTreeDiff::new(&existing, &scaffolded);
+
+        assert!(diff.has_changes());
+        assert_eq!(
+            diff.paths_with_change(FileChange::Added)
+                .collect::<Vec<_>>(),
+            vec![Path::new("src/new.rs")]
+        );
+        assert_eq!(
+            diff.paths_with_change(FileChange::Modified)
+                .collect::<Vec<_>>(),
+            vec![Path::new("src/lib.rs")]
+        );
+        assert_eq!(diff.paths_with_change(FileChange::Unchanged).count(), 2);
+    }
+
+    #[test]
+    fn renders_unified_diff_for_changed_files_only() {
+        let existing: FileTree = dir! {
+            "lib.rs" => file!("fn a() {}\n"),
+            "same.rs" => file!("fn b() {}\n"),
+        };
+        let scaffolded: FileTree = dir! {
+            "lib.rs" => file!("fn a() {}\nfn c() {}\n"),
+            "same.rs" => file!("fn b() {}\n"),
+        };
+
+        let rendered = FileTreeDiff::new(&existing, &scaffolded).render(false);
+
+        assert!(rendered.contains("0 added, 1 modified, 1 unchanged"));
+        assert!(rendered.contains("--- a/lib.rs\n+++ b/lib.rs\n"));
+        assert!(rendered.contains("+fn c() {}\n"));
+        assert!(!rendered.contains("same.rs\n+++"));
+    }
+
+    #[test]
+    fn lists_removed_files_with_their_diff() {
+        let existing: FileTree = dir! {
+            "lib.rs" => file!("pub mod post;\n"),
+            "post.rs" => file!("fn post() {}\n"),
+        };
+        let scaffolded: FileTree = dir! {
+            "lib.rs" => file!(""),
+        };
+
+        let diff = FileTreeDiff::new(&existing, &scaffolded)
+            .with_removed_files(&existing, &[PathBuf::from("post.rs")]);
+
+        assert_eq!(
+            diff.paths_with_change(FileChange::Removed)
+                .collect::<Vec<_>>(),
+            vec![Path::new("post.rs")]
+        );
+        let rendered = diff.render(false);
+        assert!(rendered.contains("0 added, 1 modified, 1 removed, 0 unchanged"));
+        assert!(rendered.contains("--- a/post.rs\n+++ /dev/null\n"));
+    }
+
+    #[test]
+    fn identical_trees_have_no_changes() {
+        let tree: FileTree = dir! {
+            "lib.rs" => file!("fn a() {}\n"),
+        };
+
+        assert!(!FileTreeDiff::new(&tree, &tree).has_changes());
+    }
+}
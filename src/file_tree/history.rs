@@ -0,0 +1,246 @@
+//! Journal of the files written by each scaffolding command, so that `hc-scaffold undo` can revert them
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ScaffoldError, ScaffoldResult};
+
+/// Folder, relative to the root of the project, in which the journal entries are stored
+pub const HISTORY_DIR: &str = ".hc-scaffold/history";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Operation {
+    /// The command line that ran the operation
+    pub command: String,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    pub files: Vec<FileOperation>,
+}
+
+/// Content of a file before and after an operation, `None` if the file didn't exist
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FileOperation {
+    pub path: PathBuf,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Saves the journal entry of an operation on the given project, from the content of the files it wrote
+/// as it was before the operation, by their path relative to the project, and their current content on disk
+///
+/// The files whose content on disk is still the same are left out
+///
+/// Returns the path of the new journal entry, or `None` if no file was changed
+pub fn save_operation(
+    project_dir: &Path,
+    command: &str,
+    files_before: BTreeMap<PathBuf, Option<String>>,
+) -> ScaffoldResult<Option<PathBuf>> {
+    let files: Vec<FileOperation> = files_before
+        .into_iter()
+        .filter_map(|(path, before)| {
+            let after = read_file(&project_dir.join(&path));
+            (before != after).then_some(FileOperation {
+                path,
+                before,
+                after,
+            })
+        })
+        .collect();
+
+    if files.is_empty() {
+        return Ok(None);
+    }
+
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let operation = Operation {
+        command: command.to_owned(),
+        timestamp: since_epoch.as_secs(),
+        files,
+    };
+
+    let history_dir = project_dir.join(HISTORY_DIR);
+    fs::create_dir_all(&history_dir)?;
+
+    // Entries are named by the millisecond they were saved in, or by the next free one
+    // if another entry was already saved in it
+    let mut id = since_epoch.as_millis();
+    let (entry_path, mut entry_file) = loop {
+        let entry_path = history_dir.join(format!("{id}.json"));
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&entry_path)
+        {
+            Ok(entry_file) => break (entry_path, entry_file),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => id += 1,
+            Err(e) => return Err(e.into()),
+        }
+    };
+    entry_file.write_all(serde_json::to_string_pretty(&operation)?.as_bytes())?;
+
+    Ok(Some(entry_path))
+}
+
+/// Returns the recorded operations of the given project with the path of their journal entry, most recent first
+pub fn list_operations(project_dir: &Path) -> ScaffoldResult<Vec<(PathBuf, Operation)>> {
+    let history_dir = project_dir.join(HISTORY_DIR);
+    if !history_dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut entries: Vec<(u128, PathBuf)> = fs::read_dir(&history_dir)?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let id = path.file_stem()?.to_str()?.parse::<u128>().ok()?;
+            Some((id, path))
+        })
+        .collect();
+    entries.sort_by_key(|(id, _)| std::cmp::Reverse(*id));
+
+    entries
+        .into_iter()
+        .map(|(_, path)| {
+            let operation: Operation = serde_json::from_str(&fs::read_to_string(&path)?)
+                .map_err(|e| ScaffoldError::MalformedFile(path.clone(), e.to_string()))?;
+            Ok((path, operation))
+        })
+        .collect()
+}
+
+impl Operation {
+    /// Files of the given project whose content on disk is no longer the one the operation left them with
+    pub fn files_changed_since(&self, project_dir: &Path) -> Vec<PathBuf> {
+        self.files
+            .iter()
+            .filter(|f| read_file(&project_dir.join(&f.path)) != f.after)
+            .map(|f| f.path.clone())
+            .collect()
+    }
+}
+
+fn read_file(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use build_fs_tree::{dir, file};
+
+    use crate::file_tree::{build_file_tree_with_mode, BuildMode};
+
+    use super::*;
+
+    /// A new empty folder in the temporary directory for the given test
+    pub fn test_project_dir(test_name: &str) -> PathBuf {
+        let project_dir =
+            std::env::temp_dir().join(format!("hc-scaffold-{test_name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&project_dir);
+        fs::create_dir_all(&project_dir).unwrap();
+        project_dir
+    }
+
+    /// Writes the given files as an operation of the project, and saves its journal entry
+    pub fn run_operation(project_dir: &Path, files: &[(&str, Option<&str>)]) {
+        let mut files_before = BTreeMap::new();
+        for (path, content) in files {
+            files_before.insert(PathBuf::from(path), read_file(&project_dir.join(path)));
+            match content {
+                Some(content) => fs::write(project_dir.join(path), content).unwrap(),
+                None => fs::remove_file(project_dir.join(path)).unwrap(),
+            }
+        }
+        save_operation(project_dir, "hc-scaffold test", files_before).unwrap();
+    }
+
+    #[test]
+    fn saves_only_the_files_changed_by_the_operation() {
+        let project_dir = test_project_dir("history-test");
+        fs::write(project_dir.join("changed.txt"), "before").unwrap();
+        fs::write(project_dir.join("unchanged.txt"), "same").unwrap();
+
+        run_operation(
+            &project_dir,
+            &[
+                ("changed.txt", Some("after")),
+                ("unchanged.txt", Some("same")),
+                ("created.txt", Some("new")),
+            ],
+        );
+
+        let operations = list_operations(&project_dir).unwrap();
+        assert_eq!(operations.len(), 1);
+        assert_eq!(
+            operations[0].1.files,
+            vec![
+                FileOperation {
+                    path: PathBuf::from("changed.txt"),
+                    before: Some(String::from("before")),
+                    after: Some(String::from("after")),
+                },
+                FileOperation {
+                    path: PathBuf::from("created.txt"),
+                    before: None,
+                    after: Some(String::from("new")),
+                },
+            ]
+        );
+
+        fs::remove_dir_all(&project_dir).unwrap();
+    }
+
+    #[test]
+    fn saves_the_snapshot_returned_by_the_build_of_a_file_tree() {
+        let project_dir = test_project_dir("history-build-test");
+        fs::write(project_dir.join("changed.txt"), "before").unwrap();
+
+        let built = build_file_tree_with_mode(
+            dir! {
+                "changed.txt" => file!("after"),
+                "created.txt" => file!("new"),
+            },
+            &project_dir,
+            BuildMode::Write,
+        )
+        .unwrap();
+        assert_eq!(
+            built.files_before,
+            BTreeMap::from([
+                (PathBuf::from("changed.txt"), Some(String::from("before"))),
+                (PathBuf::from("created.txt"), None),
+            ])
+        );
+
+        save_operation(&project_dir, "hc-scaffold test", built.files_before).unwrap();
+
+        let operations = list_operations(&project_dir).unwrap();
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].1.files.len(), 2);
+
+        fs::remove_dir_all(&project_dir).unwrap();
+    }
+
+    #[test]
+    fn saves_operations_of_the_same_millisecond_in_separate_entries() {
+        let project_dir = test_project_dir("history-same-millisecond-test");
+
+        run_operation(&project_dir, &[("first.txt", Some("first"))]);
+        run_operation(&project_dir, &[("second.txt", Some("second"))]);
+
+        let operations = list_operations(&project_dir).unwrap();
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].1.files[0].path, PathBuf::from("second.txt"));
+        assert_eq!(operations[1].1.files[0].path, PathBuf::from("first.txt"));
+
+        fs::remove_dir_all(&project_dir).unwrap();
+    }
+}
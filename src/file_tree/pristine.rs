@@ -0,0 +1,44 @@
+//! Store of the content of each file as it was last generated by a template, so that rendering the
+//! template again can be merged with the changes made to the file since then
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ScaffoldError, ScaffoldResult};
+
+use super::{create_dir_all, file_content, insert_file, FileTree};
+
+/// Path of the store, relative to the root of the project
+pub const PRISTINE_STORE_PATH: &str = ".hc-scaffold/pristine.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PristineStore {
+    /// Generated content of each file, indexed by its path relative to the root of the project
+    pub files: BTreeMap<PathBuf, String>,
+}
+
+impl PristineStore {
+    /// Reads the store of the given project, which is empty if nothing was generated in it yet
+    pub fn load(app_file_tree: &FileTree) -> ScaffoldResult<Self> {
+        let path = Path::new(PRISTINE_STORE_PATH);
+        let Ok(content) = file_content(app_file_tree, path) else {
+            return Ok(PristineStore::default());
+        };
+
+        serde_json::from_str(&content)
+            .map_err(|e| ScaffoldError::MalformedFile(path.to_path_buf(), e.to_string()))
+    }
+
+    pub fn save(&self, app_file_tree: &mut FileTree) -> ScaffoldResult<()> {
+        let path = Path::new(PRISTINE_STORE_PATH);
+        if let Some(parent) = path.parent() {
+            create_dir_all(app_file_tree, parent)?;
+        }
+
+        insert_file(app_file_tree, path, &serde_json::to_string_pretty(self)?)
+    }
+}
@@ -34,6 +34,28 @@
 //! hc-scaffold --help
 //! ```
 //!
+//! # Using the scaffolding library
+//!
+//! The same scaffolding is available to other programs through [`Scaffolder`], which works on the project
+//! at the given path and never changes the working directory of the process:
+//!
+//! ```no_run
+//! use holochain_scaffolding_cli::{
+//!     file_tree::BuildMode, scaffold::web_app::template_type::TemplateType,
+//!     scaffolder::DnaAnswers, Scaffolder,
+//! };
+//!
+//! # fn main() -> holochain_scaffolding_cli::error::ScaffoldResult<()> {
+//! let scaffolder = Scaffolder::with_template_type("./todos", &TemplateType::Svelte)?;
+//! let scaffolded = scaffolder.dna(DnaAnswers {
+//!     app: None,
+//!     name: String::from("todos"),
+//! })?;
+//! scaffolder.write(scaffolded.file_tree, BuildMode::Write)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! # Custom Templates
 //!
 //! The scaffolding tool comes with 6 built-in templates:
@@ -139,7 +161,12 @@
 //! 6. If found, it copies the directory structure within that folder, selecting files with the `.hbs` extension.
 //! 7. It renders the contents of each file using appropriate data from the command.
 //!    - For instance, in `hc scaffold web-app`, one context field is `app_name`, representing the user-input app name.
-//! 8. Finally, it merges the resulting directory structure with the existing repository. If a file already exists, its contents are overwritten.
+//! 8. Finally, it merges the resulting directory structure with the existing repository.
+//!    - The content generated for each file is remembered in `.hc-scaffold/pristine.json`, with Rust files formatted as `cargo fmt` formats them.
+//!    - If a file already exists and was changed since it was generated, the changes are merged with the newly generated content (a three-way merge of the remembered, current and new contents).
+//!    - When the changes conflict with the template, the user chooses whether to keep their version, use the template version, or get both with conflict markers to resolve by hand. With `--non-interactive`, conflict markers are always used.
+//!    - Files that were generated before the pristine store existed are overwritten.
+//!    - Templates that use the `merge` helper on `previous_file_content` already render on top of the current content of their file, so their output is written as is and is not remembered.
 //!
 //! You can take a look at [Writing templates](#writing-templates) to learn how to write your own templates.
 //!
@@ -216,6 +243,8 @@
 //!
 //! This will get replaced by the contents of the file `field-types/String/TextArea/detail/render.hbs`.
 //!
+//! The fields of a `Struct` field are in `field_type.fields`, with the same shape as the fields of the entry type, so the widgets of `Struct` can render them by calling the partials of their field types and widgets, which can themselves be structs.
+//!
 //! ### Instructions
 //!
 //! Additionally to the folders, you can override the built-in instructions that get shown to the user after each command. The scaffolding tool will look for a file named `<COMMAND>.instructions.hbs` in the folder for the custom template, and if it exists, render its contents and display them to the user. The name of the `COMMAND` for the file matches the names for the folders where the templates for each command exist.
@@ -281,8 +310,12 @@
 pub mod cli;
 pub mod error;
 pub mod file_tree;
+pub mod output;
 pub mod reserved_words;
 pub mod scaffold;
+pub mod scaffolder;
 pub mod templates;
 pub mod utils;
 pub mod versions;
+
+pub use scaffolder::Scaffolder;
@@ -0,0 +1,224 @@
+//! Machine-readable report of a run of the scaffolding tool, printed with `--output json`
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use colored::Colorize;
+use path_clean::PathClean;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    error::ScaffoldError,
+    file_tree::{diff::FileChange, BuildMode, BuiltFileTree},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Coloured prose, meant to be read by humans
+    #[default]
+    Text,
+    /// A single JSON document describing the run, meant to be read by other tools
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = ScaffoldError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(ScaffoldError::InvalidArguments(format!(
+                "Invalid output format \"{s}\", here are all valid output formats: text, json"
+            ))),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct RunReport {
+    pub command: String,
+    /// The folder the command ran in, to which the paths of the files are relative
+    pub project_dir: PathBuf,
+    pub success: bool,
+    /// The template the command was run with, if it needed one
+    pub template: Option<String>,
+    /// The apps, DNAs, zomes and entry types that the command acted on, whether they were passed
+    /// as arguments or resolved from the project
+    pub choices: BTreeMap<String, String>,
+    /// Whether the files were written to disk, which is not the case with `--dry-run`
+    pub written: bool,
+    pub created_files: BTreeSet<PathBuf>,
+    pub modified_files: BTreeSet<PathBuf>,
+    pub removed_files: BTreeSet<PathBuf>,
+    pub next_instructions: Option<String>,
+    pub error: Option<ErrorReport>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ErrorReport {
+    /// The name of the `ScaffoldError` variant, or "Other" for errors that are not a `ScaffoldError`
+    pub kind: String,
+    /// The fields of the `ScaffoldError` variant
+    pub fields: Value,
+    pub message: String,
+}
+
+impl From<&anyhow::Error> for ErrorReport {
+    fn from(error: &anyhow::Error) -> Self {
+        let scaffold_error = error
+            .downcast_ref::<ScaffoldError>()
+            .and_then(|e| serde_json::to_value(e).ok());
+
+        let (kind, fields) = match scaffold_error {
+            Some(Value::Object(mut variant)) => (
+                variant
+                    .remove("kind")
+                    .and_then(|k| k.as_str().map(String::from)),
+                variant.remove("fields").unwrap_or(Value::Null),
+            ),
+            _ => (None, Value::Null),
+        };
+
+        ErrorReport {
+            kind: kind.unwrap_or_else(|| String::from("Other")),
+            fields,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Where a command prints its messages, and the report of its run that is printed with `--output json`
+///
+/// In JSON output mode the messages go to stderr, so that the report is the only output on stdout
+#[derive(Debug)]
+pub struct Output {
+    format: OutputFormat,
+    report: RunReport,
+    /// Content of the files written by the command as it was before the command,
+    /// by their path relative to the project folder
+    files_before: BTreeMap<PathBuf, Option<String>>,
+}
+
+impl Output {
+    pub fn new(format: OutputFormat, command: &str) -> Self {
+        if format == OutputFormat::Json {
+            colored::control::set_override(false);
+        }
+
+        Output {
+            format,
+            report: RunReport {
+                command: command.to_owned(),
+                ..Default::default()
+            },
+            files_before: BTreeMap::new(),
+        }
+    }
+
+    /// Prints the given message for the user
+    pub fn println(&self, message: impl Display) {
+        match self.format {
+            OutputFormat::Text => println!("{message}"),
+            OutputFormat::Json => eprintln!("{message}"),
+        }
+    }
+
+    pub fn record_template(&mut self, template: &str) {
+        self.report.template = Some(template.to_owned());
+    }
+
+    pub fn record_project_dir(&mut self, project_dir: &Path) {
+        self.report.project_dir = project_dir.to_path_buf();
+    }
+
+    /// Records the name of the app, DNA, zome... of the given kind that the command acts on
+    pub fn record_choice(&mut self, kind: &str, name: &str) {
+        self.report.choices.insert(kind.to_owned(), name.to_owned());
+    }
+
+    /// Records the files changed by building a file tree in the given root folder, with their content before
+    /// the first build that wrote them, and prints the changes that were not written because of `--dry-run`
+    pub fn record_build(&mut self, root: &Path, build_mode: BuildMode, built: &BuiltFileTree) {
+        if build_mode == BuildMode::DryRun {
+            self.println(
+                built
+                    .diff
+                    .render(colored::control::SHOULD_COLORIZE.should_colorize()),
+            );
+        }
+        if !built.written {
+            self.println("No files were written.".yellow());
+        }
+        self.report.written |= built.written;
+
+        let root = root.strip_prefix(&self.report.project_dir).unwrap_or(root);
+        for file in &built.diff.files {
+            let files = match file.change {
+                FileChange::Added => &mut self.report.created_files,
+                FileChange::Modified => &mut self.report.modified_files,
+                FileChange::Removed => &mut self.report.removed_files,
+                FileChange::Unchanged => continue,
+            };
+            files.insert(root.join(&file.path).clean());
+        }
+
+        if built.written {
+            for (path, before) in &built.files_before {
+                self.files_before
+                    .entry(root.join(path).clean())
+                    .or_insert_with(|| before.clone());
+            }
+        }
+    }
+
+    /// Takes the content of the files written by the command as it was before the command,
+    /// by their path relative to the project folder
+    pub fn take_files_before(&mut self) -> BTreeMap<PathBuf, Option<String>> {
+        std::mem::take(&mut self.files_before)
+    }
+
+    /// Prints the instructions shown to the user after the command, and records them in the report
+    pub fn print_next_instructions(&mut self, instructions: &str) {
+        self.println(instructions);
+
+        let all_instructions = match self.report.next_instructions.take() {
+            Some(previous) => format!("{previous}\n\n{}", instructions.trim()),
+            None => instructions.trim().to_owned(),
+        };
+        self.report.next_instructions = Some(all_instructions);
+    }
+
+    /// Records the result of the command, and returns the report of its run
+    pub fn finish(self, result: &anyhow::Result<()>) -> RunReport {
+        RunReport {
+            success: result.is_ok(),
+            error: result.as_ref().err().map(ErrorReport::from),
+            ..self.report
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_scaffold_errors_with_their_fields() {
+        let error = anyhow::Error::from(ScaffoldError::ZomeNotFound(
+            String::from("posts"),
+            String::from("forum"),
+        ));
+
+        let report = ErrorReport::from(&error);
+
+        assert_eq!(report.kind, "ZomeNotFound");
+        assert_eq!(report.fields, serde_json::json!(["posts", "forum"]));
+        assert_eq!(report.message, error.to_string());
+    }
+}
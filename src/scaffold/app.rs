@@ -7,6 +7,7 @@ use mr_bundle::Manifest;
 use crate::{
     error::{ScaffoldError, ScaffoldResult},
     file_tree::{find_files_by_name, FileTree},
+    utils::Interactivity,
 };
 
 pub mod cargo;
@@ -33,6 +34,7 @@ impl AppFileTree {
     pub fn get_or_choose(
         file_tree: FileTree,
         app_name: Option<&str>,
+        interactivity: Interactivity,
     ) -> ScaffoldResult<AppFileTree> {
         let app_manifests = find_app_manifests(&file_tree)?;
 
@@ -42,7 +44,7 @@ impl AppFileTree {
                 .into_iter()
                 .last()
                 .ok_or(ScaffoldError::AppManifestNotFound),
-            (_, None) => choose_app(app_manifests),
+            (_, None) => choose_app(app_manifests, interactivity),
             (_, Some(name)) => app_manifests
                 .into_iter()
                 .find(|(_, m)| m.app_name().to_string().eq(name))
@@ -59,7 +61,10 @@ impl AppFileTree {
 
 pub fn choose_app(
     app_manifests: BTreeMap<PathBuf, AppManifest>,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<(PathBuf, AppManifest)> {
+    interactivity.ensure_interactive("--app")?;
+
     let manifest_vec: Vec<(PathBuf, AppManifest)> = app_manifests.into_iter().collect();
     let app_names: Vec<String> = manifest_vec
         .iter()
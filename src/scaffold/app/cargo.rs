@@ -1,10 +1,13 @@
 use std::{
+    collections::BTreeMap,
+    fs,
     path::{Path, PathBuf},
     process::Stdio,
     str::from_utf8,
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
-use crate::file_tree::{file_content, insert_file, FileTree};
+use crate::file_tree::{file_content, flatten_file_tree, insert_file, map_file, FileTree};
 use cargo_metadata::{Metadata, MetadataCommand};
 
 use crate::error::{ScaffoldError, ScaffoldResult};
@@ -58,30 +61,54 @@ pub fn add_workspace_path_dependency(
     add_workspace_dependency(app_file_tree, crate_name, &toml::Value::Table(table))
 }
 
+/// Adds the given external crate to the dependencies of the crate at the given path, taking its version
+/// from the workspace dependencies, where it's added with the given version if it isn't there yet
+pub fn add_crate_external_dependency(
+    mut app_file_tree: FileTree,
+    crate_path: &Path,
+    crate_name: &str,
+    crate_version: &str,
+) -> ScaffoldResult<FileTree> {
+    let workspace_cargo_toml = get_workspace_cargo_toml(&app_file_tree)?;
+    let in_workspace = workspace_cargo_toml
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(|d| d.get(crate_name))
+        .is_some();
+    if !in_workspace {
+        app_file_tree =
+            add_workspace_external_dependency(app_file_tree, crate_name, crate_version)?;
+    }
+
+    let cargo_toml_path = crate_path.join("Cargo.toml");
+    let cargo_toml: toml::Value = toml::from_str(&file_content(&app_file_tree, &cargo_toml_path)?)?;
+    if cargo_toml
+        .get("dependencies")
+        .and_then(|d| d.get(crate_name))
+        .is_some()
+    {
+        return Ok(app_file_tree);
+    }
+
+    map_file(&mut app_file_tree, &cargo_toml_path, |contents| {
+        let dependency = format!("{crate_name} = {{ workspace = true }}");
+        Ok(match contents.find("[dependencies]\n") {
+            Some(i) => {
+                let i = i + "[dependencies]\n".len();
+                format!("{}{dependency}\n{}", &contents[..i], &contents[i..])
+            }
+            None => format!("{}\n[dependencies]\n{dependency}\n", contents.trim_end()),
+        })
+    })?;
+
+    Ok(app_file_tree)
+}
+
 pub fn get_workspace_packages_locations(
     app_file_tree: &FileTree,
 ) -> ScaffoldResult<Option<Vec<PathBuf>>> {
-    let current_dir = std::env::current_dir()?;
-
-    let path = current_dir
-        .join(workspace_cargo_toml_path(app_file_tree))
-        .canonicalize()?;
-    let command_result = MetadataCommand::new().manifest_path(path).exec();
-
-    match command_result {
-        Ok(metadata) => {
-            let packages_paths: Vec<PathBuf> = metadata
-                .workspace_packages()
-                .into_iter()
-                .map(|p| {
-                    PathBuf::from(p.manifest_path.as_std_path())
-                        .iter()
-                        .skip(current_dir.components().count())
-                        .collect()
-                })
-                .collect();
-            Ok(Some(packages_paths))
-        }
+    match get_workspace_packages(app_file_tree) {
+        Ok(packages) => Ok(Some(packages.into_values().collect())),
         Err(_) => Ok(None),
     }
 }
@@ -90,24 +117,73 @@ pub fn workspace_package_path(
     app_file_tree: &FileTree,
     crate_name: &str,
 ) -> ScaffoldResult<Option<PathBuf>> {
-    let current_dir = std::env::current_dir()?;
+    Ok(get_workspace_packages(app_file_tree)?.remove(crate_name))
+}
 
-    let path = current_dir
-        .join(workspace_cargo_toml_path(app_file_tree))
-        .canonicalize()?;
-    let metadata = MetadataCommand::new().manifest_path(path).exec()?;
-
-    let package_path: Option<PathBuf> = metadata
-        .workspace_packages()
-        .into_iter()
-        .find(|p| p.name.eq(crate_name))
-        .map(|p| {
-            PathBuf::from(p.manifest_path.as_std_path())
-                .iter()
-                .skip(current_dir.components().count())
-                .collect()
-        });
-    Ok(package_path)
+/// Returns the path to the Cargo.toml of every package in the workspace, indexed by package name
+///
+/// `cargo metadata` runs on a copy of the manifests and the crate roots of the given file tree, so that
+/// crates which have only been scaffolded in memory are found as well
+pub fn get_workspace_packages(
+    app_file_tree: &FileTree,
+) -> ScaffoldResult<BTreeMap<String, PathBuf>> {
+    let workspace_dir = std::env::temp_dir().join(format!(
+        "hc-scaffold-workspace-{}-{}",
+        std::process::id(),
+        WORKSPACE_COPIES.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let packages = copy_manifests_and_crate_roots(app_file_tree, &workspace_dir).and_then(|()| {
+        let workspace_dir = workspace_dir.canonicalize()?;
+        let metadata = MetadataCommand::new()
+            .manifest_path(workspace_dir.join(workspace_cargo_toml_path(app_file_tree)))
+            .no_deps()
+            .exec()?;
+
+        Ok(metadata
+            .workspace_packages()
+            .into_iter()
+            .filter_map(|p| {
+                let path = p
+                    .manifest_path
+                    .as_std_path()
+                    .strip_prefix(&workspace_dir)
+                    .ok()?;
+                Some((p.name.clone(), path.to_path_buf()))
+            })
+            .collect())
+    });
+    let _ = fs::remove_dir_all(&workspace_dir);
+
+    packages
+}
+
+/// Number of copies of workspaces made by this process, to name their folders
+static WORKSPACE_COPIES: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes the Cargo.toml files of the given file tree in the given folder, with an empty
+/// `src/lib.rs` or `src/main.rs` for the crates that have them, which is all `cargo metadata --no-deps` reads
+fn copy_manifests_and_crate_roots(file_tree: &FileTree, dir: &Path) -> ScaffoldResult<()> {
+    for (path, content) in flatten_file_tree(file_tree) {
+        let Some(content) = content else {
+            continue;
+        };
+        let is_crate_root = path.parent().is_some_and(|p| p.ends_with("src"))
+            && (path.ends_with("lib.rs") || path.ends_with("main.rs"));
+        let content = match path.file_name().and_then(|f| f.to_str()) {
+            Some("Cargo.toml") => content,
+            _ if is_crate_root => String::new(),
+            _ => continue,
+        };
+
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+
+    Ok(())
 }
 
 pub fn get_workspace_members(app_file_tree: &FileTree) -> ScaffoldResult<Vec<String>> {
@@ -156,9 +232,12 @@ pub fn get_workspace_cargo_toml(app_file_tree: &FileTree) -> ScaffoldResult<toml
     Ok(v)
 }
 
-pub fn exec_metadata(app_file_tree: &FileTree) -> Result<Metadata, cargo_metadata::Error> {
-    let current_dir = std::env::current_dir()?;
-    let path = current_dir
+/// Runs `cargo metadata` on the workspace of the app, whose files are in the given folder
+pub fn exec_metadata(
+    app_dir: &Path,
+    app_file_tree: &FileTree,
+) -> Result<Metadata, cargo_metadata::Error> {
+    let path = app_dir
         .join(workspace_cargo_toml_path(app_file_tree))
         .canonicalize()?;
     let output = MetadataCommand::new()
@@ -227,7 +306,110 @@ fn add_workspace_dependency(
     Ok(app_file_tree)
 }
 
+/// Renames a path dependency of the workspace, pointing it to the new location of the crate
+pub fn rename_workspace_path_dependency(
+    mut app_file_tree: FileTree,
+    old_crate_name: &str,
+    new_crate_name: &str,
+    new_path_from_workspace_root: &Path,
+) -> ScaffoldResult<FileTree> {
+    let path = workspace_cargo_toml_path(&app_file_tree);
+    let mut workspace_cargo_toml = get_workspace_cargo_toml(&app_file_tree)?;
+
+    let Some(workspace_table) = workspace_cargo_toml
+        .get_mut("workspace")
+        .and_then(|w| w.as_table_mut())
+    else {
+        return Err(ScaffoldError::MalformedFile(
+            path,
+            String::from("no workspace table found in workspace root"),
+        ));
+    };
+
+    let Some(dependencies) = workspace_table
+        .get_mut("dependencies")
+        .and_then(|d| d.as_table_mut())
+    else {
+        return Ok(app_file_tree);
+    };
+
+    let Some(mut crate_location) = dependencies.remove(old_crate_name) else {
+        return Ok(app_file_tree);
+    };
+    if let Some(table) = crate_location.as_table_mut() {
+        if table.contains_key("path") {
+            table.insert(
+                String::from("path"),
+                toml::Value::String(new_path_from_workspace_root.to_string_lossy().to_string()),
+            );
+        }
+    }
+    dependencies.insert(new_crate_name.to_owned(), crate_location);
+
+    let cargo_toml_str = toml::to_string(&workspace_cargo_toml)?;
+
+    insert_file(&mut app_file_tree, &path, &cargo_toml_str)?;
+
+    Ok(app_file_tree)
+}
+
 #[inline]
 fn workspace_cargo_toml_path(_app_file_tree: &FileTree) -> PathBuf {
     PathBuf::new().join("Cargo.toml")
 }
+
+#[cfg(test)]
+mod tests {
+    use build_fs_tree::{dir, file};
+
+    use super::*;
+
+    fn crate_dir(name: &str) -> FileTree {
+        dir! {
+            "Cargo.toml" => file!(format!("[package]\nname = \"{name}\"\nversion = \"0.0.1\"\nedition = \"2021\"\n")),
+            "src" => dir! {
+                "lib.rs" => file!("pub fn f() {}\n")
+            }
+        }
+    }
+
+    #[test]
+    fn resolves_the_workspace_members_with_cargo() {
+        let file_tree: FileTree = dir! {
+            "Cargo.toml" => file!(r#"[workspace]
+members = ["dnas/**/zomes/*/*"]
+exclude = ["dnas/forum/zomes/coordinator/old"]
+resolver = "2"
+"#),
+            "dnas" => dir! {
+                "forum" => dir! {
+                    "zomes" => dir! {
+                        "integrity" => dir! {
+                            "posts" => crate_dir("posts_integrity")
+                        },
+                        "coordinator" => dir! {
+                            "posts" => crate_dir("posts"),
+                            "old" => crate_dir("old")
+                        }
+                    }
+                }
+            }
+        };
+
+        let packages = get_workspace_packages(&file_tree).unwrap();
+
+        assert_eq!(
+            packages,
+            BTreeMap::from([
+                (
+                    "posts".to_string(),
+                    PathBuf::from("dnas/forum/zomes/coordinator/posts/Cargo.toml")
+                ),
+                (
+                    "posts_integrity".to_string(),
+                    PathBuf::from("dnas/forum/zomes/integrity/posts/Cargo.toml")
+                ),
+            ])
+        );
+    }
+}
@@ -13,7 +13,7 @@ pub fn setup_git_environment<P: AsRef<Path>>(path: P) -> ScaffoldResult<()> {
         index.write()?;
         Ok::<_, git2::Error>(())
     })() {
-        println!(
+        eprintln!(
             "{}{}",
             "Warning: Failed to set up git repository: ".yellow(),
             e.to_string().yellow()
@@ -78,12 +78,13 @@ pub fn setup_nix_developer_environment(dir: &Path) -> ScaffoldResult<()> {
         return Err(ScaffoldError::NixSetupError("- detected that Scaffolding is running inside an existing Git repository, please choose a different location to scaffold".to_string()));
     }
 
-    println!("Setting up nix development environment...");
+    eprintln!("Setting up nix development environment...");
 
     add_extra_experimental_features()?;
 
     let output = Command::new("nix")
-        .stdout(Stdio::inherit())
+        // Its output goes to stderr, which is where progress is reported with `--output json`
+        .stdout(Stdio::from(std::io::stderr()))
         .stderr(Stdio::inherit())
         .current_dir(dir)
         .args(["flake", "update"])
@@ -121,7 +122,7 @@ pub fn add_extra_experimental_features() -> ScaffoldResult<()> {
     {
         file.write_all(EXTRA_EXPERIMENTAL_FEATURES_LINE.as_bytes())?;
     } else {
-        println!("Warning: could not write extra-experimental-features to nix.conf");
+        eprintln!("Warning: could not write extra-experimental-features to nix.conf");
     }
     Ok(())
 }
@@ -11,10 +11,12 @@ use std::path::{Path, PathBuf};
 use crate::error::{ScaffoldError, ScaffoldResult};
 
 use super::{choose_app, find_app_manifests};
+use crate::utils::Interactivity;
 
 pub fn get_or_choose_app_manifest_path_for_dna_manifest(
     app_file_tree: &FileTree,
     dna_manifest_path: &Path,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<PathBuf> {
     let dna_manifest = read_dna_manifest(app_file_tree, dna_manifest_path)?;
 
@@ -37,7 +39,7 @@ pub fn get_or_choose_app_manifest_path_for_dna_manifest(
             .into_iter()
             .last()
             .ok_or(ScaffoldError::NoAppsFoundForDna(dna_manifest.name())),
-        _ => choose_app(apps_for_dna),
+        _ => choose_app(apps_for_dna, interactivity),
     }?;
 
     Ok(path)
@@ -0,0 +1,160 @@
+//! Code generated from the Rust source of the zomes of a project, as run by `hc-scaffold codegen`
+
+use std::path::PathBuf;
+
+use holochain_types::prelude::DnaManifest;
+use quote::ToTokens;
+
+use crate::{
+    error::ScaffoldResult,
+    file_tree::{find_map_rust_files, FileTree},
+};
+
+use super::{
+    dna::{find_dna_manifests, DnaFileTree},
+    inspect::crate_src_file_tree,
+    zome::ZomeFileTree,
+};
+
+pub mod client;
+pub mod rust_client;
+pub mod types;
+
+/// Callbacks that holochain calls itself, and that are not meant to be called by clients
+const CALLBACKS: &[&str] = &[
+    "init",
+    "post_commit",
+    "recv_remote_signal",
+    "validate",
+    "genesis_self_check",
+];
+
+/// A coordinator zome of the project, with the integrity zomes it depends on
+pub struct CoordinatorZome {
+    pub dna_name: String,
+    pub coordinator: ZomeFileTree,
+    pub integrity_zomes: Vec<ZomeFileTree>,
+}
+
+/// Finds all the coordinator zomes of all the DNAs of the project that are built from a crate in the workspace
+pub fn find_coordinator_zomes(file_tree: &FileTree) -> ScaffoldResult<Vec<CoordinatorZome>> {
+    let mut coordinator_zomes = vec![];
+
+    for dna_manifest_path in find_dna_manifests(file_tree)?.into_keys() {
+        let dna_file_tree =
+            DnaFileTree::from_dna_manifest_path(file_tree.clone(), &dna_manifest_path)?;
+        let DnaManifest::V1(v1) = dna_file_tree.dna_manifest.clone();
+
+        for zome_manifest in v1.coordinator.zomes {
+            let dependencies: Vec<String> = zome_manifest
+                .dependencies
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|d| d.name.to_string())
+                .collect();
+            let Ok(coordinator) =
+                ZomeFileTree::from_zome_manifest(dna_file_tree.clone(), zome_manifest)
+            else {
+                continue;
+            };
+            let integrity_zomes = v1
+                .integrity
+                .zomes
+                .iter()
+                .filter(|z| dependencies.contains(&z.name.to_string()))
+                .filter_map(|z| {
+                    ZomeFileTree::from_zome_manifest(dna_file_tree.clone(), z.clone()).ok()
+                })
+                .collect();
+
+            coordinator_zomes.push(CoordinatorZome {
+                dna_name: dna_file_tree.dna_manifest.name(),
+                coordinator,
+                integrity_zomes,
+            });
+        }
+    }
+
+    Ok(coordinator_zomes)
+}
+
+/// Whether the given `#[hdk_extern]` function is a zome function that clients can call
+pub fn is_callable_by_clients(function: &syn::ItemFn) -> bool {
+    let infallible = function.attrs.iter().any(|a| {
+        a.path().is_ident("hdk_extern") && a.to_token_stream().to_string().contains("infallible")
+    });
+
+    !infallible && !CALLBACKS.contains(&function.sig.ident.to_string().as_str())
+}
+
+/// The structs and enums defined at the top level of the source of a zome,
+/// with the path of the file they are defined in
+#[derive(Default)]
+pub struct ZomeItems {
+    pub structs: Vec<(PathBuf, syn::ItemStruct)>,
+    pub enums: Vec<(PathBuf, syn::ItemEnum)>,
+}
+
+impl ZomeItems {
+    pub fn extend(&mut self, other: ZomeItems) {
+        self.structs.extend(other.structs);
+        self.enums.extend(other.enums);
+    }
+
+    pub fn find_enum(&self, name: &str) -> Option<&(PathBuf, syn::ItemEnum)> {
+        self.enums.iter().find(|(_, e)| e.ident == name)
+    }
+
+    /// The structs annotated with `#[hdk_entry_helper]`
+    pub fn entry_structs(&self) -> impl Iterator<Item = &(PathBuf, syn::ItemStruct)> {
+        self.structs.iter().filter(|(_, s)| {
+            s.attrs
+                .iter()
+                .any(|a| a.path().is_ident("hdk_entry_helper"))
+        })
+    }
+
+    /// The variants of the `#[hdk_entry_types]` enums, with the name of the struct they hold
+    pub fn entry_types(&self) -> Vec<(String, String)> {
+        self.enums
+            .iter()
+            .filter(|(_, e)| e.attrs.iter().any(|a| a.path().is_ident("hdk_entry_types")))
+            .flat_map(|(_, e)| e.variants.iter())
+            .map(|variant| {
+                let struct_name = match &variant.fields {
+                    syn::Fields::Unnamed(fields) => fields
+                        .unnamed
+                        .first()
+                        .map(|f| f.ty.to_token_stream().to_string()),
+                    _ => None,
+                };
+                let variant_name = variant.ident.to_string();
+                (variant_name.clone(), struct_name.unwrap_or(variant_name))
+            })
+            .collect()
+    }
+}
+
+/// Parses the structs and enums of the given zome
+pub fn zome_items(zome_file_tree: &ZomeFileTree) -> ScaffoldResult<ZomeItems> {
+    let (crate_src_path, src_file_tree) = crate_src_file_tree(zome_file_tree)?;
+    let mut zome_items = ZomeItems::default();
+
+    for (path, items) in
+        find_map_rust_files(&src_file_tree, &|_path, file| Some(file.items.clone()))
+    {
+        let path = crate_src_path.join(path);
+        for item in items {
+            match item {
+                syn::Item::Struct(item_struct) => {
+                    zome_items.structs.push((path.clone(), item_struct))
+                }
+                syn::Item::Enum(item_enum) => zome_items.enums.push((path.clone(), item_enum)),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(zome_items)
+}
@@ -0,0 +1,427 @@
+//! Typed TypeScript client for the zome functions of each coordinator zome, generated from
+//! the signatures of its `#[hdk_extern]` functions
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use convert_case::{Case, Casing};
+use quote::ToTokens;
+
+use crate::{
+    error::{ScaffoldError, ScaffoldResult},
+    file_tree::{create_dir_all, file_content, insert_file, FileTree},
+    scaffold::{
+        entry_type::definitions::FieldType, zome::coordinator::find_all_extern_functions_by_file,
+    },
+    utils::format_code,
+};
+
+use super::{
+    find_coordinator_zomes, is_callable_by_clients, zome_items, CoordinatorZome, ZomeItems,
+};
+
+/// Types exported by @holochain/client that zome functions commonly take or return
+const HOLOCHAIN_CLIENT_TYPES: &[&str] = &[
+    "Action",
+    "ActionHash",
+    "AgentPubKey",
+    "AnyDhtHash",
+    "Create",
+    "CreateLink",
+    "Delete",
+    "DeleteLink",
+    "DnaHash",
+    "EntryHash",
+    "ExternalHash",
+    "Link",
+    "Record",
+    "SignedActionHashed",
+    "Update",
+];
+
+pub fn client_class_name(coordinator_zome_name: &str) -> String {
+    format!("{}ZomeClient", coordinator_zome_name.to_case(Case::Pascal))
+}
+
+/// Path of the client of the given coordinator zome, next to its types module
+pub fn client_ts_path(dna_name: &str, coordinator_zome_name: &str) -> PathBuf {
+    PathBuf::from("ui/src")
+        .join(dna_name)
+        .join(coordinator_zome_name)
+        .join(format!("{}.ts", client_class_name(coordinator_zome_name)))
+}
+
+/// Regenerates the client of every coordinator zome of the project,
+/// returning the paths of the clients whose content changed
+pub fn codegen_client(file_tree: &mut FileTree) -> ScaffoldResult<Vec<PathBuf>> {
+    let mut changed = vec![];
+
+    for coordinator_zome in find_coordinator_zomes(file_tree)? {
+        let path = client_ts_path(
+            &coordinator_zome.dna_name,
+            &coordinator_zome.coordinator.zome_manifest.name.to_string(),
+        );
+        let client_ts = generate_client_ts(&coordinator_zome)?;
+
+        if file_content(file_tree, &path).ok().as_ref() != Some(&client_ts) {
+            if let Some(parent) = path.parent() {
+                create_dir_all(file_tree, parent)?;
+            }
+            insert_file(file_tree, &path, &client_ts)?;
+            changed.push(path);
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Generates the client class of the given coordinator zome, with one method per zome function
+pub fn generate_client_ts(coordinator_zome: &CoordinatorZome) -> ScaffoldResult<String> {
+    let mut integrity_items = ZomeItems::default();
+    for integrity_zome in &coordinator_zome.integrity_zomes {
+        integrity_items.extend(zome_items(integrity_zome)?);
+    }
+    let coordinator_items = zome_items(&coordinator_zome.coordinator)?;
+    let mut ts_types = TsTypes::new(&integrity_items, &coordinator_items);
+
+    let zome_name = coordinator_zome.coordinator.zome_manifest.name.to_string();
+    let mut methods = vec![];
+    for (path, functions) in find_all_extern_functions_by_file(&coordinator_zome.coordinator)? {
+        for function in functions {
+            if let Some(method) = client_method(&path, &function, &mut ts_types)? {
+                methods.push(method);
+            }
+        }
+    }
+
+    let mut holochain_imports = ts_types.holochain_imports.clone();
+    holochain_imports.insert(String::from("AppClient"));
+    let mut sections = vec![format!(
+        "import type {{ {} }} from \"@holochain/client\";",
+        holochain_imports.into_iter().collect::<Vec<_>>().join(", ")
+    )];
+    if !ts_types.types_imports.is_empty() {
+        sections.push(format!(
+            "import type {{ {} }} from \"./types\";",
+            ts_types
+                .types_imports
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    sections.extend(ts_types.definitions);
+    sections.push(format!(
+        r#"export class {} {{
+  constructor(
+    public client: AppClient,
+    public roleName = "{}",
+    public zomeName = "{zome_name}",
+  ) {{}}
+
+{}
+}}"#,
+        client_class_name(&zome_name),
+        coordinator_zome.dna_name,
+        methods.join("\n\n")
+    ));
+
+    format_code(&format!("{}\n", sections.join("\n\n")), "client.ts")
+}
+
+/// The method calling the given zome function, if it can be called by clients
+fn client_method(
+    path: &Path,
+    function: &syn::ItemFn,
+    ts_types: &mut TsTypes,
+) -> ScaffoldResult<Option<String>> {
+    if !is_callable_by_clients(function) {
+        return Ok(None);
+    }
+    let fn_name = function.sig.ident.to_string();
+
+    let location = || format!("zome function \"{fn_name}\" in {}", path.display());
+    let (parameter, payload) = match function.sig.inputs.first() {
+        Some(syn::FnArg::Typed(pat_type)) => {
+            let name = match pat_type.pat.as_ref() {
+                syn::Pat::Ident(pat_ident) => pat_ident
+                    .ident
+                    .to_string()
+                    .trim_start_matches('_')
+                    .to_case(Case::Camel),
+                _ => String::from("input"),
+            };
+            let ts_type = ts_types.ts_type(&pat_type.ty, &location)?;
+            (format!("{name}: {ts_type}"), name)
+        }
+        _ => (String::new(), String::from("null")),
+    };
+    let output = match &function.sig.output {
+        syn::ReturnType::Default => String::from("void"),
+        syn::ReturnType::Type(_, ty) => ts_types.ts_type(ty, &location)?,
+    };
+
+    Ok(Some(format!(
+        r#"  /** Calls the "{fn_name}" zome function */
+  {}({parameter}): Promise<{output}> {{
+    return this.client.callZome({{
+      role_name: this.roleName,
+      zome_name: this.zomeName,
+      fn_name: "{fn_name}",
+      payload: {payload},
+    }});
+  }}"#,
+        fn_name.to_case(Case::Camel)
+    )))
+}
+
+/// Maps Rust types onto TypeScript types, collecting the imports and the definitions they need
+struct TsTypes<'a> {
+    integrity_items: &'a ZomeItems,
+    coordinator_items: &'a ZomeItems,
+    holochain_imports: BTreeSet<String>,
+    /// The types defined in the integrity zomes, which are imported from the types module
+    types_imports: BTreeSet<String>,
+    /// The interfaces and enums of the types defined in the coordinator zome
+    definitions: Vec<String>,
+    defined: BTreeSet<String>,
+}
+
+impl<'a> TsTypes<'a> {
+    fn new(integrity_items: &'a ZomeItems, coordinator_items: &'a ZomeItems) -> Self {
+        TsTypes {
+            integrity_items,
+            coordinator_items,
+            holochain_imports: BTreeSet::new(),
+            types_imports: BTreeSet::new(),
+            definitions: vec![],
+            defined: BTreeSet::new(),
+        }
+    }
+
+    fn ts_type(&mut self, ty: &syn::Type, location: &dyn Fn() -> String) -> ScaffoldResult<String> {
+        let unsupported = || {
+            ScaffoldError::UnsupportedRustType(
+                ty.to_token_stream().to_string().replace(' ', ""),
+                location(),
+            )
+        };
+
+        let type_path = match ty {
+            syn::Type::Tuple(tuple) if tuple.elems.is_empty() => return Ok(String::from("void")),
+            syn::Type::Tuple(tuple) => {
+                let elements = tuple
+                    .elems
+                    .iter()
+                    .map(|t| self.ts_type(t, location))
+                    .collect::<ScaffoldResult<Vec<String>>>()?;
+                return Ok(format!("[{}]", elements.join(", ")));
+            }
+            syn::Type::Path(type_path) if type_path.qself.is_none() => type_path,
+            _ => return Err(unsupported()),
+        };
+        let segment = type_path.path.segments.last().ok_or_else(unsupported)?;
+        let name = segment.ident.to_string();
+
+        if let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments {
+            let inner = match arguments.args.first() {
+                Some(syn::GenericArgument::Type(inner)) if arguments.args.len() == 1 => inner,
+                _ => return Err(unsupported()),
+            };
+            return match name.as_str() {
+                "ExternResult" => self.ts_type(inner, location),
+                "Option" => Ok(format!("{} | undefined", self.ts_type(inner, location)?)),
+                "Vec" if inner.to_token_stream().to_string() == "u8" => {
+                    Ok(String::from("Uint8Array"))
+                }
+                "Vec" => Ok(format!("Array<{}>", self.ts_type(inner, location)?)),
+                _ => Err(unsupported()),
+            };
+        }
+        if !matches!(segment.arguments, syn::PathArguments::None) {
+            return Err(unsupported());
+        }
+
+        if HOLOCHAIN_CLIENT_TYPES.contains(&name.as_str()) {
+            self.holochain_imports.insert(name.clone());
+            return Ok(name);
+        }
+        if let Ok(field_type) = FieldType::from_str(&name) {
+            if !matches!(
+                field_type,
+                FieldType::Enum { .. } | FieldType::Struct { .. }
+            ) {
+                return Ok(field_type.ts_type().to_string());
+            }
+        }
+        let defined_in_integrity = self
+            .integrity_items
+            .structs
+            .iter()
+            .any(|(_, s)| s.ident == name)
+            || self.integrity_items.find_enum(&name).is_some();
+        if defined_in_integrity {
+            self.types_imports.insert(name.clone());
+            return Ok(name);
+        }
+        if self.define_coordinator_type(&name, location)? {
+            return Ok(name);
+        }
+
+        Err(unsupported())
+    }
+
+    /// Defines the interface or the enum with the given name if it's defined in the coordinator zome,
+    /// returning whether it is
+    fn define_coordinator_type(
+        &mut self,
+        name: &str,
+        location: &dyn Fn() -> String,
+    ) -> ScaffoldResult<bool> {
+        if self.defined.contains(name) {
+            return Ok(true);
+        }
+
+        if let Some((path, item_struct)) = self
+            .coordinator_items
+            .structs
+            .iter()
+            .find(|(_, s)| s.ident == name)
+        {
+            self.defined.insert(name.to_string());
+            let mut members = vec![];
+            for field in &item_struct.fields {
+                let Some(field_name) = &field.ident else {
+                    return Err(ScaffoldError::UnsupportedRustType(
+                        name.to_string(),
+                        format!(
+                            "{} (only structs with named fields are supported)",
+                            path.display()
+                        ),
+                    ));
+                };
+                let ts_type = self.ts_type(&field.ty, &|| {
+                    format!("field \"{field_name}\" of {name} in {}", path.display())
+                })?;
+                members.push(format!("  {field_name}: {ts_type};"));
+            }
+            self.definitions.push(format!(
+                "export interface {name} {{\n{}\n}}",
+                members.join("\n")
+            ));
+            return Ok(true);
+        }
+
+        if let Some((_, item_enum)) = self.coordinator_items.find_enum(name) {
+            let variants = item_enum
+                .variants
+                .iter()
+                .map(|v| matches!(v.fields, syn::Fields::Unit).then(|| v.ident.to_string()))
+                .collect::<Option<Vec<String>>>();
+            let Some(variants) = variants else {
+                return Err(ScaffoldError::UnsupportedRustType(
+                    name.to_string(),
+                    location(),
+                ));
+            };
+            self.defined.insert(name.to_string());
+            self.definitions.extend(
+                FieldType::Enum {
+                    label: name.to_string(),
+                    variants,
+                    variant_fields: BTreeMap::new(),
+                }
+                .ts_type_definition(),
+            );
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_a_method_calling_the_zome_function() {
+        let integrity_items = ZomeItems {
+            structs: vec![(
+                PathBuf::from("src/post.rs"),
+                syn::parse_quote! { pub struct Post {} },
+            )],
+            enums: vec![],
+        };
+        let coordinator_items = ZomeItems {
+            structs: vec![(
+                PathBuf::from("src/post.rs"),
+                syn::parse_quote! {
+                    pub struct UpdatePostInput {
+                        pub original_post_hash: ActionHash,
+                        pub updated_post: Post,
+                    }
+                },
+            )],
+            enums: vec![],
+        };
+        let mut ts_types = TsTypes::new(&integrity_items, &coordinator_items);
+        let function: syn::ItemFn = syn::parse_quote! {
+            #[hdk_extern]
+            pub fn update_post(input: UpdatePostInput) -> ExternResult<Option<Vec<Record>>> {
+                todo!()
+            }
+        };
+
+        let method = client_method(Path::new("src/post.rs"), &function, &mut ts_types)
+            .unwrap()
+            .unwrap();
+
+        assert!(method
+            .contains("updatePost(input: UpdatePostInput): Promise<Array<Record> | undefined>"));
+        assert!(method.contains("fn_name: \"update_post\""));
+        assert_eq!(
+            ts_types.definitions,
+            vec!["export interface UpdatePostInput {\n  original_post_hash: ActionHash;\n  updated_post: Post;\n}"]
+        );
+        assert!(ts_types.types_imports.contains("Post"));
+        assert!(ts_types.holochain_imports.contains("Record"));
+    }
+
+    #[test]
+    fn maps_the_cursor_and_the_limit_of_paginated_getters() {
+        let coordinator_items = ZomeItems {
+            structs: vec![(
+                PathBuf::from("src/all_posts.rs"),
+                syn::parse_quote! {
+                    pub struct GetAllPostsInput {
+                        pub after: Option<(Timestamp, ActionHash)>,
+                        pub limit: u32,
+                    }
+                },
+            )],
+            enums: vec![],
+        };
+        let integrity_items = ZomeItems::default();
+        let mut ts_types = TsTypes::new(&integrity_items, &coordinator_items);
+        let function: syn::ItemFn = syn::parse_quote! {
+            #[hdk_extern]
+            pub fn get_all_posts(input: GetAllPostsInput) -> ExternResult<Vec<Link>> {
+                todo!()
+            }
+        };
+
+        client_method(Path::new("src/all_posts.rs"), &function, &mut ts_types)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            ts_types.definitions,
+            vec!["export interface GetAllPostsInput {\n  after: [number, ActionHash] | undefined;\n  limit: number;\n}"]
+        );
+    }
+}
@@ -0,0 +1,548 @@
+//! Rust crate calling the zome functions of the coordinator zomes of a DNA, generated from
+//! the signatures of their `#[hdk_extern]` functions, for native tools and services
+//!
+//! The crate doesn't depend on a particular conductor client: the calls go through the
+//! `ZomeCaller` trait, which is implemented for the connection the tool uses
+
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use convert_case::{Case, Casing};
+use path_clean::PathClean;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+
+use crate::{
+    error::{ScaffoldError, ScaffoldResult},
+    file_tree::{create_dir_all, file_content, insert_file, FileTree},
+    scaffold::{
+        app::cargo::get_workspace_cargo_toml, zome::coordinator::find_all_extern_functions,
+        zome::ZomeFileTree,
+    },
+};
+
+use super::{find_coordinator_zomes, is_callable_by_clients, zome_items, CoordinatorZome};
+
+/// Folder the client crates are generated in by default, relative to the root of the project
+pub const DEFAULT_CLIENTS_PATH: &str = "clients";
+
+pub fn client_crate_name(dna_name: &str) -> String {
+    format!("{}_client", dna_name.to_case(Case::Snake))
+}
+
+/// Generates a client crate for each DNA of the project in the given folder,
+/// returning the paths of the files whose content changed
+pub fn codegen_rust_client(
+    file_tree: &mut FileTree,
+    clients_path: &Path,
+) -> ScaffoldResult<Vec<PathBuf>> {
+    let mut coordinator_zomes_by_dna: Vec<(String, Vec<CoordinatorZome>)> = vec![];
+    for coordinator_zome in find_coordinator_zomes(file_tree)? {
+        match coordinator_zomes_by_dna
+            .iter_mut()
+            .find(|(dna_name, _)| *dna_name == coordinator_zome.dna_name)
+        {
+            Some((_, zomes)) => zomes.push(coordinator_zome),
+            None => coordinator_zomes_by_dna
+                .push((coordinator_zome.dna_name.clone(), vec![coordinator_zome])),
+        }
+    }
+
+    let hdk_version = workspace_hdk_version(file_tree)?;
+
+    let mut changed = vec![];
+    for (dna_name, coordinator_zomes) in coordinator_zomes_by_dna {
+        let crate_path = clients_path.join(client_crate_name(&dna_name)).clean();
+        let (cargo_toml, lib_rs) =
+            generate_rust_client(&dna_name, &coordinator_zomes, &crate_path, &hdk_version)?;
+
+        for (path, content) in [
+            (crate_path.join("Cargo.toml"), cargo_toml),
+            (crate_path.join("src").join("lib.rs"), lib_rs),
+        ] {
+            if file_content(file_tree, &path).ok().as_ref() != Some(&content) {
+                if let Some(parent) = path.parent() {
+                    create_dir_all(file_tree, parent)?;
+                }
+                insert_file(file_tree, &path, &content)?;
+                changed.push(path);
+            }
+        }
+    }
+
+    Ok(changed)
+}
+
+/// The version requirement of the hdk dependency of the workspace, so that the clients use the same hdk as the zomes
+fn workspace_hdk_version(file_tree: &FileTree) -> ScaffoldResult<String> {
+    let cargo_toml = get_workspace_cargo_toml(file_tree)?;
+    let hdk = cargo_toml
+        .get("workspace")
+        .and_then(|workspace| workspace.get("dependencies"))
+        .and_then(|dependencies| dependencies.get("hdk"));
+    let version = match hdk {
+        Some(toml::Value::Table(table)) => table.get("version").and_then(|v| v.as_str()),
+        Some(hdk) => hdk.as_str(),
+        None => None,
+    };
+
+    version.map(String::from).ok_or_else(|| {
+        ScaffoldError::MalformedFile(
+            PathBuf::from("Cargo.toml"),
+            String::from("should have the version of the hdk in its workspace.dependencies table"),
+        )
+    })
+}
+
+/// Generates the Cargo.toml and the lib.rs of the client crate of the given DNA,
+/// to be written at the given path relative to the root of the project
+pub fn generate_rust_client(
+    dna_name: &str,
+    coordinator_zomes: &[CoordinatorZome],
+    crate_path: &Path,
+    hdk_version: &str,
+) -> ScaffoldResult<(String, String)> {
+    // From the crate back to the root of the project
+    let to_root: PathBuf = crate_path.components().map(|_| "..").collect();
+
+    let mut integrity_dependencies: Vec<(String, PathBuf)> = vec![];
+    let mut modules = vec![];
+    for coordinator_zome in coordinator_zomes {
+        let mut integrity_crates = vec![];
+        for integrity_zome in &coordinator_zome.integrity_zomes {
+            let crate_name = zome_crate_name(integrity_zome)?;
+            if !integrity_dependencies
+                .iter()
+                .any(|(name, _)| *name == crate_name)
+            {
+                integrity_dependencies.push((
+                    crate_name.clone(),
+                    to_root.join(&integrity_zome.zome_crate_path),
+                ));
+            }
+            integrity_crates.push((crate_name, integrity_zome));
+        }
+        modules.push(zome_module(coordinator_zome, &integrity_crates)?);
+    }
+
+    let lib_rs = lib_rs(dna_name, modules)?;
+
+    let dependencies: Vec<String> = integrity_dependencies
+        .iter()
+        .map(|(name, path)| format!("{name} = {{ path = \"{}\" }}", path.display()))
+        .collect();
+    let cargo_toml = format!(
+        r#"[package]
+name = "{}"
+version = "0.0.1"
+edition = "2021"
+
+# The zomes are built for WebAssembly, so this crate is kept out of their workspace
+[workspace]
+
+[dependencies]
+hdk = "{hdk_version}"
+serde = "1"
+{}
+"#,
+        client_crate_name(dna_name),
+        dependencies.join("\n")
+    );
+
+    Ok((cargo_toml, lib_rs))
+}
+
+fn lib_rs(dna_name: &str, modules: Vec<TokenStream>) -> ScaffoldResult<String> {
+    let crate_doc = format!(
+        " Client for the zome functions of the \"{dna_name}\" DNA, generated by `hc-scaffold codegen rust-client`"
+    );
+
+    let lib_rs = quote! {
+        #![doc = #crate_doc]
+
+        use hdk::prelude::*;
+        use serde::de::DeserializeOwned;
+
+        /// Calls a zome function in a cell of the app, for example through the app websocket of holochain_client
+        #[allow(async_fn_in_trait)]
+        pub trait ZomeCaller {
+            type Error;
+
+            async fn call_zome(
+                &self,
+                role_name: &str,
+                zome_name: &str,
+                fn_name: &str,
+                payload: ExternIO,
+            ) -> Result<ExternIO, Self::Error>;
+        }
+
+        #[derive(Debug)]
+        pub enum ClientError<E> {
+            /// The zome call failed
+            Call(E),
+            /// The input or the output of the zome call couldn't be serialized
+            Serialization(SerializedBytesError),
+        }
+
+        /// Calls the zome functions of a zome, serializing their input and deserializing their output
+        pub struct ZomeClient<'a, C> {
+            caller: &'a C,
+            role_name: String,
+            zome_name: String,
+        }
+
+        impl<'a, C: ZomeCaller> ZomeClient<'a, C> {
+            pub fn new(caller: &'a C, role_name: &str, zome_name: &str) -> Self {
+                ZomeClient {
+                    caller,
+                    role_name: role_name.to_string(),
+                    zome_name: zome_name.to_string(),
+                }
+            }
+
+            pub async fn call<I, O>(&self, fn_name: &str, input: I) -> Result<O, ClientError<C::Error>>
+            where
+                I: Serialize + std::fmt::Debug,
+                O: DeserializeOwned + std::fmt::Debug,
+            {
+                let payload = ExternIO::encode(input).map_err(ClientError::Serialization)?;
+                let output = self
+                    .caller
+                    .call_zome(&self.role_name, &self.zome_name, fn_name, payload)
+                    .await
+                    .map_err(ClientError::Call)?;
+                output.decode().map_err(ClientError::Serialization)
+            }
+        }
+
+        #(#modules)*
+    };
+
+    let file: syn::File = syn::parse2(lib_rs)?;
+    Ok(prettyplease::unparse(&file))
+}
+
+/// The module of the given coordinator zome, with a client struct that has a method per zome function
+fn zome_module(
+    coordinator_zome: &CoordinatorZome,
+    integrity_crates: &[(String, &ZomeFileTree)],
+) -> ScaffoldResult<TokenStream> {
+    let zome_name = coordinator_zome.coordinator.zome_manifest.name.to_string();
+    let module_name = format_ident!("{}", zome_name.to_case(Case::Snake));
+    let client_name = format_ident!("{}ZomeClient", zome_name.to_case(Case::Pascal));
+    let module_doc =
+        format!(" Client for the zome functions of the \"{zome_name}\" coordinator zome");
+
+    // The structs and the enums of the integrity zomes are re-exported instead of duplicated
+    let mut re_exports = vec![];
+    for (crate_name, integrity_zome) in integrity_crates {
+        let items = zome_items(integrity_zome)?;
+        let crate_ident = format_ident!("{}", crate_name);
+        let names: Vec<syn::Ident> = items
+            .structs
+            .iter()
+            .filter(|(_, s)| matches!(s.vis, syn::Visibility::Public(_)))
+            .map(|(_, s)| s.ident.clone())
+            .chain(
+                items
+                    .enums
+                    .iter()
+                    .filter(|(_, e)| matches!(e.vis, syn::Visibility::Public(_)))
+                    .filter(|(_, e)| {
+                        !e.attrs.iter().any(|a| {
+                            a.path().is_ident("hdk_entry_types")
+                                || a.path().is_ident("hdk_link_types")
+                        })
+                    })
+                    .map(|(_, e)| e.ident.clone()),
+            )
+            .collect();
+        if !names.is_empty() {
+            re_exports.push(quote! { pub use #crate_ident::{#(#names),*}; });
+        }
+    }
+
+    let functions: Vec<syn::ItemFn> = find_all_extern_functions(&coordinator_zome.coordinator)?
+        .into_iter()
+        .filter(is_callable_by_clients)
+        .collect();
+
+    let mut methods = vec![];
+    let mut used_idents = BTreeSet::new();
+    for function in &functions {
+        let fn_name = function.sig.ident.to_string();
+        let method_name = &function.sig.ident;
+        let method_doc = format!(" Calls the \"{fn_name}\" zome function");
+
+        let (parameter, input) = match function.sig.inputs.first() {
+            Some(syn::FnArg::Typed(pat_type)) => {
+                let name = match pat_type.pat.as_ref() {
+                    syn::Pat::Ident(pat_ident) => pat_ident
+                        .ident
+                        .to_string()
+                        .trim_start_matches('_')
+                        .to_string(),
+                    _ => String::new(),
+                };
+                let name = format_ident!("{}", if name.is_empty() { "input" } else { &name });
+                let ty = &pat_type.ty;
+                collect_idents(ty.to_token_stream(), &mut used_idents);
+                (quote! { , #name: #ty }, quote! { #name })
+            }
+            _ => (quote! {}, quote! { () }),
+        };
+        let output = match &function.sig.output {
+            syn::ReturnType::Default => quote! { () },
+            syn::ReturnType::Type(_, ty) => {
+                collect_idents(ty.to_token_stream(), &mut used_idents);
+                extern_result_inner(ty)
+            }
+        };
+
+        methods.push(quote! {
+            #[doc = #method_doc]
+            pub async fn #method_name(&self #parameter) -> Result<#output, ClientError<C::Error>> {
+                self.zome_client.call(#fn_name, #input).await
+            }
+        });
+    }
+
+    let definitions = coordinator_definitions(coordinator_zome, used_idents)?;
+
+    Ok(quote! {
+        #[doc = #module_doc]
+        pub mod #module_name {
+            use hdk::prelude::*;
+
+            use crate::{ClientError, ZomeCaller, ZomeClient};
+
+            #(#re_exports)*
+
+            #(#definitions)*
+
+            pub struct #client_name<'a, C> {
+                zome_client: ZomeClient<'a, C>,
+            }
+
+            impl<'a, C: ZomeCaller> #client_name<'a, C> {
+                pub fn new(caller: &'a C, role_name: &str) -> Self {
+                    #client_name {
+                        zome_client: ZomeClient::new(caller, role_name, #zome_name),
+                    }
+                }
+
+                #(#methods)*
+            }
+        }
+    })
+}
+
+/// The output of a zome function, without the `ExternResult` it's wrapped in
+fn extern_result_inner(ty: &syn::Type) -> TokenStream {
+    if let syn::Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            if let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                if segment.ident == "ExternResult" {
+                    if let Some(syn::GenericArgument::Type(inner)) = arguments.args.first() {
+                        return inner.to_token_stream();
+                    }
+                }
+            }
+        }
+    }
+    ty.to_token_stream()
+}
+
+fn collect_idents(tokens: TokenStream, idents: &mut BTreeSet<String>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                idents.insert(ident.to_string());
+            }
+            TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
+
+/// Copies of the structs and enums defined in the coordinator zome that the zome functions
+/// take or return, and of the ones they use in turn
+fn coordinator_definitions(
+    coordinator_zome: &CoordinatorZome,
+    mut used_idents: BTreeSet<String>,
+) -> ScaffoldResult<Vec<TokenStream>> {
+    let items = zome_items(&coordinator_zome.coordinator)?;
+    let mut definitions = vec![];
+    let mut defined = BTreeSet::new();
+
+    loop {
+        let mut new_definitions = vec![];
+        for (_, item_struct) in &items.structs {
+            let name = item_struct.ident.to_string();
+            if used_idents.contains(&name) && defined.insert(name) {
+                let mut item_struct = item_struct.clone();
+                item_struct.attrs.retain(|a| a.path().is_ident("serde"));
+                item_struct.vis = syn::parse_quote! { pub };
+                for field in item_struct.fields.iter_mut() {
+                    field.vis = syn::parse_quote! { pub };
+                }
+                new_definitions.push(quote! {
+                    #[derive(Serialize, Deserialize, Debug, Clone)]
+                    #item_struct
+                });
+            }
+        }
+        for (_, item_enum) in &items.enums {
+            let name = item_enum.ident.to_string();
+            if used_idents.contains(&name) && defined.insert(name) {
+                let mut item_enum = item_enum.clone();
+                item_enum.attrs.retain(|a| a.path().is_ident("serde"));
+                item_enum.vis = syn::parse_quote! { pub };
+                new_definitions.push(quote! {
+                    #[derive(Serialize, Deserialize, Debug, Clone)]
+                    #item_enum
+                });
+            }
+        }
+
+        if new_definitions.is_empty() {
+            break;
+        }
+        for definition in &new_definitions {
+            collect_idents(definition.clone(), &mut used_idents);
+        }
+        definitions.extend(new_definitions);
+    }
+
+    Ok(definitions)
+}
+
+/// Name of the library of the crate of the given zome
+fn zome_crate_name(zome_file_tree: &ZomeFileTree) -> ScaffoldResult<String> {
+    let cargo_toml_path = zome_file_tree.zome_crate_path.join("Cargo.toml");
+    let cargo_toml: toml::Value = toml::from_str(&file_content(
+        zome_file_tree.dna_file_tree.file_tree_ref(),
+        &cargo_toml_path,
+    )?)?;
+
+    let name = cargo_toml
+        .get("lib")
+        .and_then(|lib| lib.get("name"))
+        .or_else(|| cargo_toml.get("package").and_then(|p| p.get("name")))
+        .and_then(|name| name.as_str())
+        .ok_or_else(|| {
+            ScaffoldError::MalformedFile(cargo_toml_path.clone(), String::from("no package name"))
+        })?;
+
+    Ok(name.replace('-', "_"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scaffold::{
+        example::{example_file_tree, ExampleType},
+        web_app::{package_manager::PackageManager, template_type::TemplateType},
+    };
+    use crate::utils::Interactivity;
+
+    use super::*;
+
+    /// Compares the generated code with its snapshot, which is rewritten instead when UPDATE_SNAPSHOTS is set
+    fn assert_snapshot(name: &str, content: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/scaffold/codegen/snapshots")
+            .join(name);
+        if std::env::var("UPDATE_SNAPSHOTS").is_ok() {
+            std::fs::write(&path, content).unwrap();
+        }
+        let snapshot = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, snapshot, "{name} doesn't match its snapshot");
+    }
+
+    fn forum_example() -> FileTree {
+        let template_file_tree = TemplateType::Headless.file_tree().unwrap();
+        example_file_tree(
+            &ExampleType::Forum,
+            PackageManager::Npm,
+            &template_file_tree,
+            false,
+            Interactivity::NonInteractive,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn generates_the_rust_client_of_the_forum_example() {
+        let mut file_tree = forum_example();
+
+        let changed = codegen_rust_client(&mut file_tree, Path::new(DEFAULT_CLIENTS_PATH)).unwrap();
+
+        let cargo_toml_path = PathBuf::from("clients/forum_client/Cargo.toml");
+        let lib_rs_path = PathBuf::from("clients/forum_client/src/lib.rs");
+        assert_eq!(changed, vec![cargo_toml_path.clone(), lib_rs_path.clone()]);
+
+        let cargo_toml = file_content(&file_tree, &cargo_toml_path).unwrap();
+        let lib_rs = file_content(&file_tree, &lib_rs_path).unwrap();
+        syn::parse_file(&lib_rs).unwrap();
+        toml::from_str::<toml::Value>(&cargo_toml).unwrap();
+
+        assert_snapshot("forum_client_cargo.toml.snap", &cargo_toml);
+        assert_snapshot("forum_client_lib.rs.snap", &lib_rs);
+    }
+
+    #[test]
+    fn clients_depend_on_the_hdk_of_the_workspace() {
+        let workspace = |hdk: &str| -> FileTree {
+            build_fs_tree::dir! {
+                "Cargo.toml" => build_fs_tree::file!(format!("[workspace]\nmembers = []\n\n[workspace.dependencies]\n{hdk}"))
+            }
+        };
+
+        assert_eq!(
+            workspace_hdk_version(&workspace("hdk = \"0.5.1\"\n")).unwrap(),
+            "0.5.1"
+        );
+        assert_eq!(
+            workspace_hdk_version(&workspace(
+                "hdk = { version = \"=0.5.0\", default-features = false }\n"
+            ))
+            .unwrap(),
+            "=0.5.0"
+        );
+        assert!(workspace_hdk_version(&workspace("serde = \"1\"\n")).is_err());
+    }
+
+    /// Needs the network or a cargo registry with the holochain crates, and builds them
+    #[test]
+    #[ignore = "builds the holochain crates"]
+    fn the_rust_client_of_the_forum_example_compiles() {
+        let project_dir = std::env::temp_dir().join(format!(
+            "hc-scaffold-rust-client-test-{}",
+            std::process::id()
+        ));
+        let mut file_tree = forum_example();
+        codegen_rust_client(&mut file_tree, Path::new(DEFAULT_CLIENTS_PATH)).unwrap();
+        crate::file_tree::build_file_tree_with_mode(
+            file_tree,
+            &project_dir,
+            crate::file_tree::BuildMode::Write,
+        )
+        .unwrap();
+
+        let output = std::process::Command::new(env!("CARGO"))
+            .arg("check")
+            .current_dir(project_dir.join("clients/forum_client"))
+            .output()
+            .unwrap();
+        std::fs::remove_dir_all(&project_dir).unwrap();
+
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
@@ -0,0 +1,595 @@
+//! TypeScript types of the entry types and signals of each coordinator zome, generated from
+//! the Rust source of the zome and of the integrity zomes it depends on
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use convert_case::{Case, Casing};
+use quote::ToTokens;
+
+use crate::{
+    error::{ScaffoldError, ScaffoldResult},
+    file_tree::{file_content, insert_file, FileTree},
+    scaffold::entry_type::definitions::{Cardinality, FieldDefinition, FieldType, VariantFields},
+    utils::format_code,
+};
+
+use super::{find_coordinator_zomes, zome_items, CoordinatorZome, ZomeItems};
+
+const HOLOCHAIN_CLIENT_IMPORTS: &str = r#"import type {
+  ActionHash,
+  AgentPubKey,
+  Create,
+  CreateLink,
+  Delete,
+  DeleteLink,
+  DnaHash,
+  EntryHash,
+  ExternalHash,
+  Record,
+  SignedActionHashed,
+  Update,
+} from "@holochain/client";"#;
+
+/// Path of the types module of the given coordinator zome, where the UI templates scaffold it
+pub fn types_ts_path(dna_name: &str, coordinator_zome_name: &str) -> PathBuf {
+    PathBuf::from("ui/src")
+        .join(dna_name)
+        .join(coordinator_zome_name)
+        .join("types.ts")
+}
+
+/// Regenerates the types module of every coordinator zome of the project,
+/// returning the paths of the modules whose content changed
+pub fn codegen_types(file_tree: &mut FileTree) -> ScaffoldResult<Vec<PathBuf>> {
+    let mut changed = vec![];
+
+    for coordinator_zome in find_coordinator_zomes(file_tree)? {
+        let path = types_ts_path(
+            &coordinator_zome.dna_name,
+            &coordinator_zome.coordinator.zome_manifest.name.to_string(),
+        );
+        let types_ts = generate_types_ts(&coordinator_zome)?;
+
+        if file_content(file_tree, &path).ok().as_ref() != Some(&types_ts) {
+            if let Some(parent) = path.parent() {
+                crate::file_tree::create_dir_all(file_tree, parent)?;
+            }
+            insert_file(file_tree, &path, &types_ts)?;
+            changed.push(path);
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Generates the types module of the given coordinator zome
+pub fn generate_types_ts(coordinator_zome: &CoordinatorZome) -> ScaffoldResult<String> {
+    let mut integrity_items = ZomeItems::default();
+    for integrity_zome in &coordinator_zome.integrity_zomes {
+        integrity_items.extend(zome_items(integrity_zome)?);
+    }
+    let coordinator_items = zome_items(&coordinator_zome.coordinator)?;
+    let type_mapper = TypeMapper::new(&integrity_items);
+
+    let mut sections = vec![HOLOCHAIN_CLIENT_IMPORTS.to_string()];
+
+    if let Some((path, signal_enum)) = coordinator_items.find_enum("Signal") {
+        let zome_name = coordinator_zome.coordinator.zome_manifest.name.to_string();
+        sections.push(signal_ts_type(&zome_name, path, signal_enum, &type_mapper)?);
+    }
+
+    let entry_types = integrity_items.entry_types();
+    sections.push(entry_types_ts_type(&entry_types));
+
+    // The entry structs in the order of the entry types, followed by the ones that are not an entry type
+    let mut entry_structs: Vec<&(PathBuf, syn::ItemStruct)> = entry_types
+        .iter()
+        .filter_map(|(_, struct_name)| {
+            integrity_items
+                .entry_structs()
+                .find(|(_, s)| s.ident == struct_name)
+        })
+        .collect();
+    for entry_struct in integrity_items.entry_structs() {
+        if !entry_structs
+            .iter()
+            .any(|(_, s)| s.ident == entry_struct.1.ident)
+        {
+            entry_structs.push(entry_struct);
+        }
+    }
+
+    let mut defined_types: Vec<String> = vec![];
+    for (path, entry_struct) in entry_structs {
+        sections.push(interface_ts_type(
+            path,
+            entry_struct,
+            &type_mapper,
+            &mut defined_types,
+        )?);
+    }
+
+    format_code(&format!("{}\n", sections.join("\n\n")), "types.ts")
+}
+
+/// Maps the Rust types of the fields onto the field types of the scaffolding tool,
+/// and through them onto TypeScript types
+struct TypeMapper<'a> {
+    structs: BTreeMap<String, &'a (PathBuf, syn::ItemStruct)>,
+    enums: BTreeMap<String, &'a (PathBuf, syn::ItemEnum)>,
+}
+
+impl<'a> TypeMapper<'a> {
+    fn new(items: &'a ZomeItems) -> Self {
+        TypeMapper {
+            structs: items
+                .structs
+                .iter()
+                .map(|s| (s.1.ident.to_string(), s))
+                .collect(),
+            enums: items
+                .enums
+                .iter()
+                .map(|e| (e.1.ident.to_string(), e))
+                .collect(),
+        }
+    }
+
+    fn field_definition(
+        &self,
+        field_name: &str,
+        ty: &syn::Type,
+        location: &dyn Fn() -> String,
+    ) -> ScaffoldResult<FieldDefinition> {
+        self.nested_field_definition(field_name, ty, location, &[])
+    }
+
+    /// Maps the type of a field of the given structs or enums, which can't be used again in it
+    fn nested_field_definition(
+        &self,
+        field_name: &str,
+        ty: &syn::Type,
+        location: &dyn Fn() -> String,
+        parent_types: &[String],
+    ) -> ScaffoldResult<FieldDefinition> {
+        let unsupported = || {
+            ScaffoldError::UnsupportedRustType(
+                quote::ToTokens::to_token_stream(ty)
+                    .to_string()
+                    .replace(' ', ""),
+                location(),
+            )
+        };
+
+        let (type_name, cardinality) = match type_path_ident(ty) {
+            Some((ident, Some(inner))) if ident == "Option" || ident == "Vec" => {
+                let Some((inner_ident, None)) = type_path_ident(inner) else {
+                    return Err(unsupported());
+                };
+                let cardinality = match ident.as_str() {
+                    "Option" => Cardinality::Option,
+                    _ => Cardinality::Vector,
+                };
+                (inner_ident, cardinality)
+            }
+            Some((ident, None)) => (ident, Cardinality::Single),
+            _ => return Err(unsupported()),
+        };
+
+        let field_type = match FieldType::from_str(&type_name) {
+            Ok(FieldType::Enum { .. } | FieldType::Struct { .. }) | Err(_) => {
+                match self.enum_type(&type_name, parent_types)? {
+                    Some(field_type) => field_type,
+                    None => self
+                        .struct_type(&type_name, parent_types)?
+                        .ok_or_else(unsupported)?,
+                }
+            }
+            Ok(field_type) => field_type,
+        };
+
+        Ok(FieldDefinition {
+            field_name: field_name.to_string(),
+            field_type,
+            widget: None,
+            cardinality,
+            linked_from: None,
+            constraints: None,
+            immutable: false,
+        })
+    }
+
+    /// The enum with the given name, if it's defined in the zome and all its variants are units,
+    /// or if it's tagged like the enums scaffolded with variants that carry data
+    fn enum_type(&self, name: &str, parent_types: &[String]) -> ScaffoldResult<Option<FieldType>> {
+        let Some((path, item_enum)) = self.enums.get(name) else {
+            return Ok(None);
+        };
+        if parent_types.iter().any(|p| p == name) {
+            return Ok(None);
+        }
+        let parent_types = [parent_types, &[name.to_string()]].concat();
+        let adjacently_tagged = item_enum.attrs.iter().any(|a| {
+            a.path().is_ident("serde")
+                && a.to_token_stream()
+                    .to_string()
+                    .replace(' ', "")
+                    .contains("content=\"content\"")
+        });
+
+        let mut variants = vec![];
+        let mut variant_fields = BTreeMap::new();
+        for variant in &item_enum.variants {
+            let variant_name = variant.ident.to_string();
+            let location = || format!("variant {name}::{variant_name} in {}", path.display());
+            let fields = match &variant.fields {
+                syn::Fields::Unit => None,
+                _ if !adjacently_tagged => return Ok(None),
+                syn::Fields::Named(named) => Some(VariantFields {
+                    fields: named
+                        .named
+                        .iter()
+                        .map(|f| {
+                            let field_name = f.ident.as_ref().map(|i| i.to_string());
+                            self.nested_field_definition(
+                                &field_name.unwrap_or_default(),
+                                &f.ty,
+                                &location,
+                                &parent_types,
+                            )
+                        })
+                        .collect::<ScaffoldResult<Vec<_>>>()?,
+                    newtype: false,
+                }),
+                syn::Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+                    Some(VariantFields {
+                        fields: vec![self.nested_field_definition(
+                            &variant_name.to_case(Case::Snake),
+                            &unnamed.unnamed[0].ty,
+                            &location,
+                            &parent_types,
+                        )?],
+                        newtype: true,
+                    })
+                }
+                syn::Fields::Unnamed(_) => return Ok(None),
+            };
+            if let Some(fields) = fields {
+                variant_fields.insert(variant_name.clone(), fields);
+            }
+            variants.push(variant_name);
+        }
+
+        Ok(Some(FieldType::Enum {
+            label: name.to_string(),
+            variants,
+            variant_fields,
+        }))
+    }
+
+    /// The struct with the given name, if it's defined in the zome and it has named fields,
+    /// with the field types of its fields
+    fn struct_type(
+        &self,
+        name: &str,
+        parent_types: &[String],
+    ) -> ScaffoldResult<Option<FieldType>> {
+        let Some((path, item_struct)) = self.structs.get(name) else {
+            return Ok(None);
+        };
+        // A struct that contains itself has no TypeScript interface that the scaffolding tool can generate
+        if parent_types.iter().any(|p| p == name) {
+            return Ok(None);
+        }
+        let parent_types = [parent_types, &[name.to_string()]].concat();
+
+        let mut fields = vec![];
+        for field in &item_struct.fields {
+            let Some(field_name) = &field.ident else {
+                return Ok(None);
+            };
+            fields.push(self.nested_field_definition(
+                &field_name.to_string(),
+                &field.ty,
+                &|| format!("field \"{field_name}\" of {name} in {}", path.display()),
+                &parent_types,
+            )?);
+        }
+
+        Ok(Some(FieldType::Struct {
+            label: name.to_string(),
+            fields,
+        }))
+    }
+}
+
+/// The identifier of the given type, with its generic argument if it has exactly one
+fn type_path_ident(ty: &syn::Type) -> Option<(String, Option<&syn::Type>)> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    if type_path.qself.is_some() {
+        return None;
+    }
+    let segment = type_path.path.segments.last()?;
+
+    match &segment.arguments {
+        syn::PathArguments::None => Some((segment.ident.to_string(), None)),
+        syn::PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+            match arguments.args.first() {
+                Some(syn::GenericArgument::Type(inner)) => {
+                    Some((segment.ident.to_string(), Some(inner)))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn signal_ts_type(
+    zome_name: &str,
+    path: &Path,
+    signal_enum: &syn::ItemEnum,
+    type_mapper: &TypeMapper,
+) -> ScaffoldResult<String> {
+    let variants = signal_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            let mut members = vec![format!("  type: '{}';", variant.ident)];
+
+            for field in &variant.fields {
+                let Some(field_name) = &field.ident else {
+                    return Err(ScaffoldError::UnsupportedRustType(
+                        format!("Signal::{}", variant.ident),
+                        format!(
+                            "{} (only variants with named fields are supported)",
+                            path.display()
+                        ),
+                    ));
+                };
+                let ts_type = match type_path_ident(&field.ty) {
+                    Some((ident, None)) if ident == "SignedActionHashed" => {
+                        match signed_action_type(
+                            &variant.ident.to_string(),
+                            &field_name.to_string(),
+                        ) {
+                            Some(action_type) => format!("SignedActionHashed<{action_type}>"),
+                            None => String::from("SignedActionHashed"),
+                        }
+                    }
+                    Some((ident, None)) if ident == "LinkTypes" => String::from("string"),
+                    Some((ident, None)) if ident == "EntryTypes" => String::from("EntryTypes"),
+                    _ => {
+                        let field = type_mapper.field_definition(
+                            &field_name.to_string(),
+                            &field.ty,
+                            &|| {
+                                format!(
+                                    "field \"{field_name}\" of Signal::{} in {}",
+                                    variant.ident,
+                                    path.display()
+                                )
+                            },
+                        )?;
+                        let member = field.ts_field_codegen();
+                        members.push(member);
+                        continue;
+                    }
+                };
+                members.push(format!("  {field_name}: {ts_type};"));
+            }
+
+            Ok(format!("{{\n{}\n}}", members.join("\n")))
+        })
+        .collect::<ScaffoldResult<Vec<String>>>()?;
+
+    Ok(format!(
+        "export type {}Signal = {};",
+        zome_name.to_case(Case::Pascal),
+        variants.join(" | ")
+    ))
+}
+
+/// The action that the signals emitted by the scaffolded `signal_action` function carry
+fn signed_action_type(variant: &str, field_name: &str) -> Option<&'static str> {
+    match (variant, field_name) {
+        (_, "create_link_action") => Some("CreateLink"),
+        ("EntryCreated", _) => Some("Create"),
+        ("EntryUpdated", _) => Some("Update"),
+        ("EntryDeleted", _) => Some("Delete"),
+        ("LinkCreated", _) => Some("CreateLink"),
+        ("LinkDeleted", _) => Some("DeleteLink"),
+        _ => None,
+    }
+}
+
+/// The union of the entry types, in the format that the entry type templates add to
+fn entry_types_ts_type(entry_types: &[(String, String)]) -> String {
+    if entry_types.is_empty() {
+        return String::from("export type EntryTypes = {};");
+    }
+
+    let variants: Vec<String> = entry_types
+        .iter()
+        .map(|(variant, struct_name)| format!(" | ({{ type: '{variant}'; }} & {struct_name})"))
+        .collect();
+
+    format!(
+        "/* dprint-ignore-start */\nexport type EntryTypes =\n{};\n/* dprint-ignore-end */",
+        variants.join("\n")
+    )
+}
+
+fn interface_ts_type(
+    path: &Path,
+    entry_struct: &syn::ItemStruct,
+    type_mapper: &TypeMapper,
+    defined_types: &mut Vec<String>,
+) -> ScaffoldResult<String> {
+    let mut enum_definitions = vec![];
+    let mut members = vec![];
+
+    for field in &entry_struct.fields {
+        let Some(field_name) = &field.ident else {
+            return Err(ScaffoldError::UnsupportedRustType(
+                entry_struct.ident.to_string(),
+                format!(
+                    "{} (only structs with named fields are supported)",
+                    path.display()
+                ),
+            ));
+        };
+        let field = type_mapper.field_definition(&field_name.to_string(), &field.ty, &|| {
+            format!(
+                "field \"{field_name}\" of {} in {}",
+                entry_struct.ident,
+                path.display()
+            )
+        })?;
+
+        if let FieldType::Enum { label, .. } | FieldType::Struct { label, .. } = &field.field_type {
+            if !defined_types.contains(label) {
+                defined_types.push(label.clone());
+                enum_definitions.extend(field.field_type.ts_type_definition());
+            }
+        }
+        members.push(field.ts_field_codegen());
+    }
+
+    let interface = format!(
+        "export interface {} {{\n{}\n}}",
+        entry_struct.ident,
+        members.join("\n")
+    );
+
+    Ok(match enum_definitions.is_empty() {
+        true => interface,
+        false => format!("{}\n\n{interface}", enum_definitions.join("\n")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_rust_field_types_to_typescript() {
+        let items = ZomeItems {
+            structs: vec![],
+            enums: vec![(
+                PathBuf::from("src/post.rs"),
+                syn::parse_quote! {
+                    pub enum Status { Draft, Published }
+                },
+            )],
+        };
+        let type_mapper = TypeMapper::new(&items);
+        let entry_struct: syn::ItemStruct = syn::parse_quote! {
+            pub struct Post {
+                pub title: String,
+                pub tags: Vec<String>,
+                pub author: Option<AgentPubKey>,
+                pub status: Status,
+            }
+        };
+
+        let ts_type = interface_ts_type(
+            Path::new("src/post.rs"),
+            &entry_struct,
+            &type_mapper,
+            &mut vec![],
+        )
+        .unwrap();
+
+        assert_eq!(
+            ts_type,
+            r#"export type Status = {type: 'Draft'} | {type: 'Published'};
+
+export interface Post {
+  title: string;
+  tags: Array<string>;
+  author: AgentPubKey | undefined;
+  status: Status;
+}"#
+        );
+
+        let unknown: syn::ItemStruct = syn::parse_quote! {
+            pub struct Post { pub count: u64 }
+        };
+        let error = interface_ts_type(
+            Path::new("src/post.rs"),
+            &unknown,
+            &type_mapper,
+            &mut vec![],
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("\"u64\""));
+    }
+
+    #[test]
+    fn maps_structs_defined_in_the_zome_to_interfaces() {
+        let items = ZomeItems {
+            structs: vec![
+                (
+                    PathBuf::from("src/place.rs"),
+                    syn::parse_quote! {
+                        pub struct Address { pub street: String, pub previous: Option<Address> }
+                    },
+                ),
+                (
+                    PathBuf::from("src/place.rs"),
+                    syn::parse_quote! {
+                        pub struct Location { pub lat: f32, pub lng: f32 }
+                    },
+                ),
+            ],
+            enums: vec![],
+        };
+        let type_mapper = TypeMapper::new(&items);
+        let entry_struct: syn::ItemStruct = syn::parse_quote! {
+            pub struct Place {
+                pub location: Location,
+                pub locations: Vec<Location>,
+            }
+        };
+
+        let ts_type = interface_ts_type(
+            Path::new("src/place.rs"),
+            &entry_struct,
+            &type_mapper,
+            &mut vec![],
+        )
+        .unwrap();
+
+        assert_eq!(
+            ts_type,
+            r#"export interface Location {
+  lat: number;
+  lng: number;
+}
+
+export interface Place {
+  location: Location;
+  locations: Array<Location>;
+}"#
+        );
+
+        // Structs that contain themselves can't be mapped
+        let recursive: syn::ItemStruct = syn::parse_quote! {
+            pub struct Place { pub address: Address }
+        };
+        assert!(interface_ts_type(
+            Path::new("src/place.rs"),
+            &recursive,
+            &type_mapper,
+            &mut vec![],
+        )
+        .is_err());
+    }
+}
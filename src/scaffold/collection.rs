@@ -9,6 +9,7 @@ use crate::{
     file_tree::FileTree,
     reserved_words::check_for_reserved_keywords,
     templates::{collection::scaffold_collection_templates, ScaffoldedTemplate},
+    utils::Interactivity,
 };
 
 use self::coordinator::add_collection_to_coordinators;
@@ -17,10 +18,14 @@ use super::{
     app::AppFileTree,
     entry_type::{
         definitions::{EntryTypeReference, Referenceable},
-        integrity::get_all_entry_types,
+        integrity::{get_all_entry_types, is_private_entry_type},
         utils::choose_entry_type_reference,
     },
-    link_type::integrity::add_link_type_to_integrity_zome,
+    link_type::{
+        integrity::{add_link_type_to_integrity_zome, get_all_link_types},
+        link_type_reference_patterns, remove_link_types,
+    },
+    remove::{any_case_reference, find_leftover_references, Removal},
     zome::ZomeFileTree,
 };
 
@@ -31,33 +36,121 @@ pub mod coordinator;
 pub enum CollectionType {
     Global,
     ByAuthor,
+    /// The entries are linked from a path for the time bucket they were created in,
+    /// eg. "all_posts.2026.10.18.14" if the granularity is an hour
+    ByTime {
+        granularity: TimeGranularity,
+    },
 }
 
 impl FromStr for CollectionType {
     type Err = ScaffoldError;
 
     fn from_str(s: &str) -> ScaffoldResult<Self> {
-        match s {
-            "global" => Ok(CollectionType::Global),
-            "by-author" => Ok(CollectionType::ByAuthor),
-            _ => Err(ScaffoldError::InvalidCollectionType(
+        let invalid = || {
+            ScaffoldError::InvalidCollectionType(
                 s.to_string(),
-                "global, by-author".to_string(),
-            )),
+                "global, by-author, by-time:<year|month|day|hour>".to_string(),
+            )
+        };
+        match s.split_once(':') {
+            None if s == "global" => Ok(CollectionType::Global),
+            None if s == "by-author" => Ok(CollectionType::ByAuthor),
+            None if s == "by-time" => Ok(CollectionType::ByTime {
+                granularity: TimeGranularity::default(),
+            }),
+            Some(("by-time", granularity)) => Ok(CollectionType::ByTime {
+                granularity: TimeGranularity::from_str(granularity).map_err(|_| invalid())?,
+            }),
+            _ => Err(invalid()),
         }
     }
 }
 
-pub fn choose_collection_type() -> ScaffoldResult<CollectionType> {
+/// The span of time of the buckets of a by-time collection
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeGranularity {
+    Year,
+    Month,
+    Day,
+    #[default]
+    Hour,
+}
+
+impl TimeGranularity {
+    pub const ALL: [TimeGranularity; 4] = [
+        TimeGranularity::Year,
+        TimeGranularity::Month,
+        TimeGranularity::Day,
+        TimeGranularity::Hour,
+    ];
+
+    /// The number of components of the path of a time bucket after the name of the collection
+    pub fn depth(&self) -> usize {
+        match self {
+            TimeGranularity::Year => 1,
+            TimeGranularity::Month => 2,
+            TimeGranularity::Day => 3,
+            TimeGranularity::Hour => 4,
+        }
+    }
+
+    pub fn from_depth(depth: usize) -> Option<TimeGranularity> {
+        Self::ALL.into_iter().find(|g| g.depth() == depth)
+    }
+}
+
+impl FromStr for TimeGranularity {
+    type Err = ScaffoldError;
+
+    fn from_str(s: &str) -> ScaffoldResult<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|g| g.to_string() == s)
+            .ok_or_else(|| ScaffoldError::InvalidStringFormat(s.to_string()))
+    }
+}
+
+impl std::fmt::Display for TimeGranularity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let granularity = match self {
+            TimeGranularity::Year => "year",
+            TimeGranularity::Month => "month",
+            TimeGranularity::Day => "day",
+            TimeGranularity::Hour => "hour",
+        };
+        write!(f, "{granularity}")
+    }
+}
+
+/// The link type of the path tree of the time buckets of a by-time collection
+pub fn time_path_link_type_name(link_type_name: &str) -> String {
+    format!("{link_type_name}TimePath")
+}
+
+pub fn choose_collection_type(interactivity: Interactivity) -> ScaffoldResult<CollectionType> {
+    interactivity.ensure_interactive("<collection-type>")?;
+
     let selection = Select::with_theme(&ColorfulTheme::default())
         .with_prompt("Which type of collection should be scaffolded?")
         .default(0)
         .item("Global (get all entries of the selected entry types)")
         .item("By author (get entries of the selected entry types that a given author has created)")
+        .item("By time (get entries of the selected entry types created in a given time range, spread across paths for each span of time)")
         .interact()?;
     match selection {
         0 => Ok(CollectionType::Global),
         1 => Ok(CollectionType::ByAuthor),
+        2 => {
+            let granularity = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Which span of time should each path of the collection cover?")
+                .default(TimeGranularity::ALL.len() - 1)
+                .items(&TimeGranularity::ALL)
+                .interact()?;
+            Ok(CollectionType::ByTime {
+                granularity: TimeGranularity::ALL[granularity],
+            })
+        }
         _ => Err(ScaffoldError::InvalidCollectionType(
             selection.to_string(),
             "".into(),
@@ -65,14 +158,18 @@ pub fn choose_collection_type() -> ScaffoldResult<CollectionType> {
     }
 }
 
+// TODO: group some params into a new-type or prefer builder pattern
+#[allow(clippy::too_many_arguments)]
 pub fn scaffold_collection(
     integrity_zome_file_tree: ZomeFileTree,
     template_file_tree: &FileTree,
     collection_name: &str,
     maybe_collection_type: Option<CollectionType>,
     maybe_entry_type: Option<EntryTypeReference>,
+    paginated: bool,
     no_ui: bool,
     no_spec: bool,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     check_for_reserved_keywords(collection_name)?;
 
@@ -85,7 +182,7 @@ pub fn scaffold_collection(
 
     let collection_type = match maybe_collection_type {
         Some(t) => Ok(t),
-        None => choose_collection_type(),
+        None => choose_collection_type(interactivity),
     }?;
 
     let all_entries_names: Vec<String> = all_entries
@@ -105,12 +202,26 @@ pub fn scaffold_collection(
                 ))
             }
         }
-        None => choose_entry_type_reference(&all_entries, "Which entry type should be collected?"),
+        None => {
+            interactivity.ensure_interactive("<entry-type>")?;
+            choose_entry_type_reference(
+                &all_entries,
+                "Which entry type should be collected?",
+                interactivity,
+            )
+        }
     }?;
 
+    if is_private_entry_type(&integrity_zome_file_tree, &entry_type.entry_type)? {
+        return Err(ScaffoldError::PrivateEntryType(
+            entry_type.entry_type.to_case(Case::Pascal),
+            "collected".to_string(),
+        ));
+    }
+
     let link_type_name = collection_name.to_case(Case::Pascal);
 
-    let zome_file_tree = add_link_type_to_integrity_zome(
+    let mut zome_file_tree = add_link_type_to_integrity_zome(
         integrity_zome_file_tree,
         &link_type_name,
         &None,
@@ -119,17 +230,30 @@ pub fn scaffold_collection(
         &PathBuf::from(format!("{}.rs", entry_type.entry_type.to_case(Case::Snake))),
     )?;
 
+    if let CollectionType::ByTime { .. } = collection_type {
+        zome_file_tree = add_link_type_to_integrity_zome(
+            zome_file_tree,
+            &time_path_link_type_name(&link_type_name),
+            &None,
+            &None,
+            false,
+            &PathBuf::from(format!("{}.rs", entry_type.entry_type.to_case(Case::Snake))),
+        )?;
+    }
+
     let (dna_file_tree, coordinator_zome, deletable) = add_collection_to_coordinators(
         zome_file_tree,
         collection_name,
         &link_type_name,
         &collection_type,
         &entry_type,
+        paginated,
+        interactivity,
     )?;
 
     let dna_name = dna_file_tree.dna_manifest.name();
 
-    let app_file_tree = AppFileTree::get_or_choose(dna_file_tree.file_tree(), None)?;
+    let app_file_tree = AppFileTree::get_or_choose(dna_file_tree.file_tree(), None, interactivity)?;
 
     let app_name = app_file_tree.app_manifest.app_name().to_string();
 
@@ -143,7 +267,68 @@ pub fn scaffold_collection(
         collection_name,
         &entry_type,
         deletable,
+        paginated,
         no_ui,
         no_spec,
+        interactivity,
     )
 }
+
+/// Removes the given collection, with the link type that indexes it, its zome functions, its UI and tests
+pub fn remove_collection(
+    zome_file_tree: ZomeFileTree,
+    collection_name: &str,
+) -> ScaffoldResult<Removal> {
+    let link_type = collection_name.to_case(Case::Pascal);
+    let all_link_types = get_all_link_types(&zome_file_tree)?;
+
+    if !all_link_types.contains(&link_type) {
+        return Err(ScaffoldError::CollectionNotFound(
+            collection_name.to_owned(),
+            zome_file_tree.dna_file_tree.dna_manifest.name(),
+            zome_file_tree.zome_manifest.name.to_string(),
+        ));
+    }
+
+    let mut link_types = vec![link_type.clone()];
+    let time_path_link_type = time_path_link_type_name(&link_type);
+    if all_link_types.contains(&time_path_link_type) {
+        link_types.push(time_path_link_type);
+    }
+
+    let (zome_file_tree, removed_files) = remove_link_types(zome_file_tree, &link_types)?;
+    let file_tree = zome_file_tree.dna_file_tree.file_tree();
+
+    let mut patterns = link_type_reference_patterns(&link_type);
+    patterns.push(any_case_reference(collection_name));
+    let leftover_references = find_leftover_references(&file_tree, &patterns);
+
+    Ok(Removal {
+        file_tree,
+        removed_files,
+        leftover_references,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_by_time_collection_types() {
+        assert!(matches!(
+            CollectionType::from_str("by-time"),
+            Ok(CollectionType::ByTime {
+                granularity: TimeGranularity::Hour
+            })
+        ));
+        assert!(matches!(
+            CollectionType::from_str("by-time:day"),
+            Ok(CollectionType::ByTime {
+                granularity: TimeGranularity::Day
+            })
+        ));
+        assert!(CollectionType::from_str("by-time:week").is_err());
+        assert!(CollectionType::from_str("global:day").is_err());
+    }
+}
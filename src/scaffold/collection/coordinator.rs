@@ -19,10 +19,10 @@ use crate::{
             ZomeFileTree,
         },
     },
-    utils::unparse_pretty,
+    utils::{unparse_pretty, Interactivity},
 };
 
-use super::CollectionType;
+use super::{time_path_link_type_name, CollectionType, TimeGranularity};
 
 pub fn add_collection_to_coordinators(
     integrity_zome_file_tree: ZomeFileTree,
@@ -30,6 +30,8 @@ pub fn add_collection_to_coordinators(
     link_type_name: &str,
     collection_type: &CollectionType,
     entry_type: &EntryTypeReference,
+    paginated: bool,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<(DnaFileTree, ZomeManifest, bool)> {
     let integrity_zome_name = integrity_zome_file_tree.zome_manifest.name.0.to_string();
     let dna_manifest_path = integrity_zome_file_tree
@@ -49,14 +51,16 @@ pub fn add_collection_to_coordinators(
         )),
         1 => Ok(coordinator_zomes_for_integrity[0].clone()),
         _ => {
+            let prompt =
+                "Which coordinator zome should the collection getter functions be scaffolded in?";
+            interactivity.ensure_can_prompt(prompt)?;
+
             let names: Vec<String> = coordinator_zomes_for_integrity
                 .iter()
                 .map(|z| z.name.0.to_string())
                 .collect();
             let selection = Select::with_theme(&ColorfulTheme::default())
-                .with_prompt(
-                    "Which coordinator zome should the collection getter functions be scaffolded in?",
-                )
+                .with_prompt(prompt)
                 .default(0)
                 .items(&names[..])
                 .interact()?;
@@ -74,12 +78,25 @@ pub fn add_collection_to_coordinators(
     let snake_link_type_name = collection_name.to_case(Case::Snake);
 
     let getter = match collection_type {
-        CollectionType::Global => {
-            global_collection_getter(&integrity_zome_name, collection_name, link_type_name)
-        }
-        CollectionType::ByAuthor => {
-            by_author_collection_getter(&integrity_zome_name, collection_name, link_type_name)
-        }
+        CollectionType::Global => global_collection_getter(
+            &integrity_zome_name,
+            collection_name,
+            link_type_name,
+            paginated,
+        ),
+        CollectionType::ByAuthor => by_author_collection_getter(
+            &integrity_zome_name,
+            collection_name,
+            link_type_name,
+            paginated,
+        ),
+        CollectionType::ByTime { granularity } => by_time_collection_getter(
+            &integrity_zome_name,
+            collection_name,
+            link_type_name,
+            granularity,
+            paginated,
+        ),
     };
 
     let mut file_tree = zome_file_tree.dna_file_tree.file_tree();
@@ -110,6 +127,7 @@ pub fn add_collection_to_coordinators(
         link_type_name,
         collection_type,
         entry_type,
+        interactivity,
     )?;
 
     let (dna_file_tree, deletable) = add_delete_link_in_delete_function(
@@ -124,10 +142,42 @@ pub fn add_collection_to_coordinators(
     Ok((dna_file_tree, coordinator_zome, deletable))
 }
 
+/// The input of a paginated collection getter, with the optional extra fields that select the collection
+fn paginated_getter_input(input_name: &syn::Ident, extra_fields: TokenStream) -> TokenStream {
+    quote! {
+        #[derive(Serialize, Deserialize, Debug)]
+        pub struct #input_name {
+            #extra_fields
+            /// The timestamp and the create link hash of the last link of the previous page
+            pub after: Option<(Timestamp, ActionHash)>,
+            pub limit: u32,
+        }
+    }
+}
+
+/// Sorts the links by timestamp, and takes the page of at most `input.limit` links after the `input.after` cursor
+///
+/// Links created at the same time are sorted by their hash, so none of them is skipped at the boundary of a page
+fn paginate_links() -> TokenStream {
+    quote! {
+        links.sort_by(|a, b| (a.timestamp, &a.create_link_hash).cmp(&(b.timestamp, &b.create_link_hash)));
+        Ok(links
+            .into_iter()
+            .skip_while(|link| {
+                input.after.as_ref().is_some_and(|(timestamp, hash)| {
+                    (link.timestamp, &link.create_link_hash) <= (*timestamp, hash)
+                })
+            })
+            .take(input.limit as usize)
+            .collect())
+    }
+}
+
 fn global_collection_getter(
     integrity_zome_name: &str,
     collection_name: &str,
     link_type_name: &str,
+    paginated: bool,
 ) -> TokenStream {
     let get_collection_function_name =
         format_ident!("get_{}", collection_name.to_case(Case::Snake));
@@ -135,6 +185,25 @@ fn global_collection_getter(
     let integrity_zome_name = format_ident!("{integrity_zome_name}");
     let snake_collection_name = collection_name.to_case(Case::Snake);
 
+    if paginated {
+        let input_name = format_ident!("Get{}Input", collection_name.to_case(Case::Pascal));
+        let input = paginated_getter_input(&input_name, quote! {});
+        let paginate = paginate_links();
+        return quote! {
+            use hdk::prelude::*;
+            use #integrity_zome_name::*;
+
+            #input
+
+            #[hdk_extern]
+            pub fn #get_collection_function_name(input: #input_name) -> ExternResult<Vec<Link>> {
+                let path = Path::from(#snake_collection_name);
+                let mut links = get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::#link_type_name)?.build())?;
+                #paginate
+            }
+        };
+    }
+
     quote! {
         use hdk::prelude::*;
         use #integrity_zome_name::*;
@@ -151,12 +220,31 @@ fn by_author_collection_getter(
     integrity_zome_name: &str,
     collection_name: &str,
     link_type_name: &str,
+    paginated: bool,
 ) -> TokenStream {
     let get_collection_function_name =
         format_ident!("get_{}", collection_name.to_case(Case::Snake));
     let link_type_name = format_ident!("{link_type_name}");
     let integrity_zome_name = format_ident!("{integrity_zome_name}");
 
+    if paginated {
+        let input_name = format_ident!("Get{}Input", collection_name.to_case(Case::Pascal));
+        let input = paginated_getter_input(&input_name, quote! { pub author: AgentPubKey, });
+        let paginate = paginate_links();
+        return quote! {
+            use hdk::prelude::*;
+            use #integrity_zome_name::*;
+
+            #input
+
+            #[hdk_extern]
+            pub fn #get_collection_function_name(input: #input_name) -> ExternResult<Vec<Link>> {
+                let mut links = get_links(GetLinksInputBuilder::try_new(input.author.clone(), LinkTypes::#link_type_name)?.build())?;
+                #paginate
+            }
+        };
+    }
+
     quote! {
         use hdk::prelude::*;
         use #integrity_zome_name::*;
@@ -168,6 +256,159 @@ fn by_author_collection_getter(
     }
 }
 
+/// The name of the function of the collection module that returns the path of the time bucket of a timestamp
+fn time_bucket_path_function_name(collection_name: &str) -> syn::Ident {
+    format_ident!("{}_path", collection_name.to_case(Case::Snake))
+}
+
+fn by_time_collection_getter(
+    integrity_zome_name: &str,
+    collection_name: &str,
+    link_type_name: &str,
+    granularity: &TimeGranularity,
+    paginated: bool,
+) -> TokenStream {
+    let snake_collection_name = collection_name.to_case(Case::Snake);
+    let get_collection_function_name = format_ident!("get_{snake_collection_name}");
+    let path_function_name = time_bucket_path_function_name(collection_name);
+    let time_path_link_type_name = format_ident!("{}", time_path_link_type_name(link_type_name));
+    let link_type_name = format_ident!("{link_type_name}");
+    let integrity_zome_name = format_ident!("{integrity_zome_name}");
+    let input_name = format_ident!("Get{}Input", collection_name.to_case(Case::Pascal));
+    let bucket_depth = proc_macro2::Literal::usize_unsuffixed(granularity.depth());
+    let path_example = format!(
+        "{snake_collection_name}.{}",
+        ["2026", "10", "18", "14"][..granularity.depth()].join(".")
+    );
+    let path_doc = format!(
+        " The path of the time bucket of the timestamp, eg. \"{path_example}\" for {}",
+        match granularity {
+            TimeGranularity::Year => "2026",
+            TimeGranularity::Month => "October 2026",
+            TimeGranularity::Day => "the 18th of October 2026",
+            TimeGranularity::Hour => "14:00 to 15:00 UTC on the 18th of October 2026",
+        }
+    );
+
+    let (input, paginate) = if paginated {
+        (
+            paginated_getter_input(
+                &input_name,
+                quote! {
+                    pub from: Timestamp,
+                    pub to: Timestamp,
+                },
+            ),
+            paginate_links(),
+        )
+    } else {
+        (
+            quote! {
+                #[derive(Serialize, Deserialize, Debug)]
+                pub struct #input_name {
+                    pub from: Timestamp,
+                    pub to: Timestamp,
+                }
+            },
+            quote! {
+                links.sort_by_key(|link| link.timestamp);
+                Ok(links)
+            },
+        )
+    };
+
+    quote! {
+        use hdk::prelude::*;
+        use #integrity_zome_name::*;
+
+        /// The number of components of the path of a time bucket after the name of the collection: year, month, day and hour
+        const BUCKET_DEPTH: usize = #bucket_depth;
+
+        #[doc = #path_doc]
+        pub fn #path_function_name(timestamp: Timestamp) -> ExternResult<TypedPath> {
+            let components: Vec<String> = time_bucket(timestamp)
+                .iter()
+                .map(|component| format!("{component:02}"))
+                .collect();
+            Path::from(format!("{}.{}", #snake_collection_name, components.join("."))).typed(LinkTypes::#time_path_link_type_name)
+        }
+
+        /// The UTC year, month, day and hour of the timestamp, up to the depth of the buckets
+        fn time_bucket(timestamp: Timestamp) -> Vec<i64> {
+            let seconds = timestamp.as_micros().div_euclid(1_000_000);
+            let days = seconds.div_euclid(86_400);
+            let hour = seconds.rem_euclid(86_400) / 3_600;
+
+            // Civil date of the days since the unix epoch, with years starting in March
+            let days = days + 719_468;
+            let era = days.div_euclid(146_097);
+            let day_of_era = days.rem_euclid(146_097);
+            let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+            let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+            let month_from_march = (5 * day_of_year + 2) / 153;
+            let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+            let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+            let year = era * 400 + year_of_era + i64::from(month <= 2);
+
+            let mut bucket = vec![year, month, day, hour];
+            bucket.truncate(BUCKET_DEPTH);
+            bucket
+        }
+
+        /// Walks down the path tree to the time buckets between the `from` and `to` buckets, without creating any path
+        fn buckets_in_range(path: TypedPath, bucket: Vec<i64>, from: &[i64], to: &[i64]) -> ExternResult<Vec<TypedPath>> {
+            if bucket.len() == BUCKET_DEPTH {
+                return Ok(vec![path]);
+            }
+
+            let mut children = get_links(
+                GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::#time_path_link_type_name)?.build(),
+            )?;
+            // Agents creating the first entries of a bucket at the same time all link it to its parent
+            children.sort_unstable_by(|a, b| a.tag.cmp(&b.tag));
+            children.dedup_by(|a, b| a.tag == b.tag);
+
+            let mut buckets = Vec::new();
+            for child in children {
+                let component: Component = SerializedBytes::from(UnsafeBytes::from(child.tag.0))
+                    .try_into()
+                    .map_err(|e: SerializedBytesError| wasm_error!(e))?;
+                let Some(value) = String::try_from(&component).ok().and_then(|c| c.parse::<i64>().ok()) else {
+                    continue;
+                };
+                let mut child_bucket = bucket.clone();
+                child_bucket.push(value);
+                let depth = child_bucket.len();
+                if child_bucket[..] < from[..depth] || child_bucket[..] > to[..depth] {
+                    continue;
+                }
+                let mut child_path = path.path.clone();
+                child_path.append_component(component);
+                buckets.extend(buckets_in_range(child_path.into_typed(path.link_type), child_bucket, from, to)?);
+            }
+            Ok(buckets)
+        }
+
+        #input
+
+        /// Gets the links to the entries created between `input.from` and `input.to`, oldest first
+        #[hdk_extern]
+        pub fn #get_collection_function_name(input: #input_name) -> ExternResult<Vec<Link>> {
+            let root = Path::from(#snake_collection_name).typed(LinkTypes::#time_path_link_type_name)?;
+            let buckets = buckets_in_range(root, vec![], &time_bucket(input.from), &time_bucket(input.to))?;
+
+            let mut links = Vec::new();
+            for bucket in buckets {
+                links.extend(get_links(
+                    GetLinksInputBuilder::try_new(bucket.path_entry_hash()?, LinkTypes::#link_type_name)?.build(),
+                )?);
+            }
+            links.retain(|link| input.from <= link.timestamp && link.timestamp <= input.to);
+            #paginate
+        }
+    }
+}
+
 fn add_create_link_in_create_function(
     dna_file_tree: DnaFileTree,
     coordinator_zomes_for_integrity: &Vec<ZomeManifest>,
@@ -175,6 +416,7 @@ fn add_create_link_in_create_function(
     link_type_name: &str,
     collection_type: &CollectionType,
     entry_type_reference: &EntryTypeReference,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<DnaFileTree> {
     let dna_manifest_path = dna_file_tree.dna_manifest_path.clone();
 
@@ -189,6 +431,7 @@ fn add_create_link_in_create_function(
             "At the end of which function should the {} entries be collected?",
             entry_type_reference.entry_type.to_case(Case::Pascal)
         ),
+        interactivity,
     )?;
 
     let zome_file_tree = ZomeFileTree::from_zome_manifest(dna_file_tree, chosen_coordinator_zome)?;
@@ -227,6 +470,19 @@ fn add_create_link_in_create_function(
                 create_link(my_agent_pub_key, #link_to_variable.clone(), LinkTypes::#link_type_name, ())?;
             });
         }
+        CollectionType::ByTime { .. } => {
+            let collection_module = format_ident!("{}", collection_name.to_case(Case::Snake));
+            let path_function_name = time_bucket_path_function_name(collection_name);
+            create_link_stmts.push(parse_quote! {
+                let path = crate::#collection_module::#path_function_name(record.action().timestamp())?;
+            });
+            create_link_stmts.push(parse_quote! {
+                path.ensure()?;
+            });
+            create_link_stmts.push(parse_quote! {
+                create_link(path.path_entry_hash()?, #link_to_variable.clone(), LinkTypes::#link_type_name, ())?;
+            });
+        }
     };
 
     let crate_src_path = zome_file_tree.zome_crate_path.join("src");
@@ -345,11 +601,33 @@ fn add_delete_link_in_delete_function(
                 },
             ]
         }
-        CollectionType::ByAuthor => {
+        CollectionType::ByAuthor | CollectionType::ByTime { .. } => {
             let original_hash = format_ident!("original_{snake_case_entry_type}_hash");
             let error_message = format!("{pascal_entry_def_name} not found");
             let link_type_name = format_ident!("{link_type_name}");
-            vec![
+            let get_links_stmts: Vec<syn::Stmt> = match collection_type {
+                CollectionType::ByTime { .. } => {
+                    let collection_module =
+                        format_ident!("{}", collection_name.to_case(Case::Snake));
+                    let path_function_name = time_bucket_path_function_name(collection_name);
+                    vec![
+                        parse_quote! {
+                            let path = crate::#collection_module::#path_function_name(record.action().timestamp())?;
+                        },
+                        parse_quote! {
+                            let links = get_links(
+                                GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::#link_type_name)?.build()
+                            )?;
+                        },
+                    ]
+                }
+                _ => vec![parse_quote! {
+                    let links = get_links(
+                        GetLinksInputBuilder::try_new(record.action().author().clone(), LinkTypes::#link_type_name)?.build()
+                    )?;
+                }],
+            };
+            let mut stmts: Vec<syn::Stmt> = vec![
                 parse_quote! {
                     let details = get_details(#original_hash.clone(), GetOptions::default())?
                     .ok_or(
@@ -362,21 +640,18 @@ fn add_delete_link_in_delete_function(
                         _ => Err(wasm_error!(WasmErrorInner::Guest("Malformed get details response".to_string()))),
                     }?;
                 },
-                parse_quote! {
-                    let links = get_links(
-                        GetLinksInputBuilder::try_new(record.action().author().clone(), LinkTypes::#link_type_name)?.build()
-                    )?;
-                },
-                parse_quote! {
-                    for link in links {
-                        if let Some(hash) = link.target.#into_hash_fn() {
-                           if hash == #target_hash_variable {
-                                delete_link(link.create_link_hash)?;
-                            }
+            ];
+            stmts.extend(get_links_stmts);
+            stmts.push(parse_quote! {
+                for link in links {
+                    if let Some(hash) = link.target.#into_hash_fn() {
+                       if hash == #target_hash_variable {
+                            delete_link(link.create_link_hash)?;
                         }
                     }
-                },
-            ]
+                }
+            });
+            stmts
         }
     };
 
@@ -428,3 +703,333 @@ fn add_delete_link_in_delete_function(
 
     Ok((dna_file_tree, true))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use build_fs_tree::{dir, file};
+    use quote::ToTokens;
+
+    use holochain_types::prelude::DnaManifest;
+
+    use super::*;
+    use crate::file_tree::{file_content, FileTree};
+
+    fn find_fn<'a>(file: &'a syn::File, name: &str) -> &'a syn::ItemFn {
+        file.items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Fn(f) if f.sig.ident == name => Some(f),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn tokens_without_spaces(tokens: impl ToTokens) -> String {
+        tokens.to_token_stream().to_string().replace(' ', "")
+    }
+
+    fn by_time_getter(granularity: TimeGranularity) -> syn::File {
+        syn::parse2(by_time_collection_getter(
+            "posts_integrity",
+            "posts_by_time",
+            "PostsByTime",
+            &granularity,
+            false,
+        ))
+        .unwrap()
+    }
+
+    /// Compiles the generated `time_bucket` function with a stand-in for the `Timestamp` of the hdk,
+    /// and returns the buckets it computes for the given timestamps in microseconds
+    fn run_time_bucket(granularity: TimeGranularity, timestamps: &[i64]) -> Vec<String> {
+        let file = by_time_getter(granularity);
+        let bucket_depth = file
+            .items
+            .iter()
+            .find(|item| matches!(item, syn::Item::Const(c) if c.ident == "BUCKET_DEPTH"))
+            .unwrap();
+        let time_bucket = find_fn(&file, "time_bucket");
+        let program: syn::File = syn::parse_quote! {
+            struct Timestamp(i64);
+
+            impl Timestamp {
+                fn as_micros(&self) -> i64 {
+                    self.0
+                }
+            }
+
+            #bucket_depth
+
+            #time_bucket
+
+            fn main() {
+                for micros in std::env::args().skip(1) {
+                    println!("{:?}", time_bucket(Timestamp(micros.parse().unwrap())));
+                }
+            }
+        };
+
+        let dir = std::env::temp_dir().join(format!(
+            "hc-scaffold-time-bucket-{}-{}",
+            std::process::id(),
+            granularity.depth()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("main.rs"), unparse_pretty(&program)).unwrap();
+        let rustc = std::env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+        let compiled = std::process::Command::new(rustc)
+            .args(["--edition", "2021", "-D", "warnings", "-o"])
+            .arg(dir.join("time_bucket"))
+            .arg(dir.join("main.rs"))
+            .output()
+            .unwrap();
+        assert!(
+            compiled.status.success(),
+            "{}",
+            String::from_utf8_lossy(&compiled.stderr)
+        );
+        let output = std::process::Command::new(dir.join("time_bucket"))
+            .args(timestamps.iter().map(|t| t.to_string()))
+            .output()
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn time_buckets_are_the_utc_date_and_hour_of_the_timestamp() {
+        assert_eq!(
+            run_time_bucket(
+                TimeGranularity::Hour,
+                &[
+                    0,
+                    -1,
+                    1_709_209_800_000_000,
+                    978_307_199_000_000,
+                    1_792_297_352_899_577
+                ]
+            ),
+            vec![
+                "[1970, 1, 1, 0]",
+                "[1969, 12, 31, 23]",
+                "[2024, 2, 29, 12]",
+                "[2000, 12, 31, 23]",
+                "[2026, 10, 18, 4]",
+            ]
+        );
+        assert_eq!(
+            run_time_bucket(TimeGranularity::Day, &[1_792_297_352_899_577]),
+            vec!["[2026, 10, 18]"]
+        );
+    }
+
+    #[test]
+    fn by_time_getters_only_walk_the_buckets_in_the_range() {
+        let file = by_time_getter(TimeGranularity::Day);
+
+        let path = tokens_without_spaces(find_fn(&file, "posts_by_time_path"));
+        assert!(path.contains("format!(\"{component:02}\")"));
+        assert!(path.contains(
+            "Path::from(format!(\"{}.{}\",\"posts_by_time\",components.join(\".\"))).typed(LinkTypes::PostsByTimeTimePath)"
+        ));
+
+        let buckets_in_range = tokens_without_spaces(find_fn(&file, "buckets_in_range"));
+        assert!(buckets_in_range.contains("ifbucket.len()==BUCKET_DEPTH{returnOk(vec![path]);}"));
+        assert!(buckets_in_range.contains("children.dedup_by(|a,b|a.tag==b.tag);"));
+        assert!(buckets_in_range
+            .contains("ifchild_bucket[..]<from[..depth]||child_bucket[..]>to[..depth]{continue;}"));
+        // No path is created while reading the collection
+        assert!(!buckets_in_range.contains("ensure"));
+
+        let getter = tokens_without_spaces(find_fn(&file, "get_posts_by_time"));
+        assert!(getter.contains(
+            "buckets_in_range(root,vec![],&time_bucket(input.from),&time_bucket(input.to))?"
+        ));
+        assert!(getter
+            .contains("links.retain(|link|input.from<=link.timestamp&&link.timestamp<=input.to);"));
+    }
+
+    #[test]
+    fn by_time_collections_link_the_entries_from_their_time_bucket() {
+        let crate_dir = |name: &str, lib_rs: &str| -> FileTree {
+            dir! {
+                "Cargo.toml" => file!(format!("[package]\nname = \"{name}\"\nversion = \"0.0.1\"\nedition = \"2021\"\n")),
+                "src" => dir! {
+                    "lib.rs" => file!(lib_rs)
+                }
+            }
+        };
+        let dna_manifest = r#"manifest_version: '1'
+name: forum
+integrity:
+  network_seed: null
+  properties: null
+  origin_time: 1792297352899577
+  zomes:
+  - name: posts_integrity
+    hash: null
+    bundled: ../../../target/wasm32-unknown-unknown/release/posts_integrity.wasm
+    dependencies: null
+    dylib: null
+coordinator:
+  zomes:
+  - name: posts
+    hash: null
+    bundled: ../../../target/wasm32-unknown-unknown/release/posts.wasm
+    dependencies:
+    - name: posts_integrity
+    dylib: null
+lineage: []
+"#;
+        let coordinator_lib_rs = r#"use hdk::prelude::*;
+use posts_integrity::*;
+
+#[hdk_extern]
+pub fn create_post(post: Post) -> ExternResult<Record> {
+    let post_hash = create_entry(&EntryTypes::Post(post.clone()))?;
+    let record = get(post_hash.clone(), GetOptions::default())?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Could not find the newly created Post".to_string())))?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn delete_post(original_post_hash: ActionHash) -> ExternResult<ActionHash> {
+    delete_entry(original_post_hash)
+}
+"#;
+        let file_tree: FileTree = dir! {
+            "Cargo.toml" => file!("[workspace]\nmembers = [\"dnas/*/zomes/coordinator/*\", \"dnas/*/zomes/integrity/*\"]\nresolver = \"2\"\n"),
+            "dnas" => dir! {
+                "forum" => dir! {
+                    "workdir" => dir! {
+                        "dna.yaml" => file!(dna_manifest)
+                    },
+                    "zomes" => dir! {
+                        "integrity" => dir! {
+                            "posts" => crate_dir("posts_integrity", "use hdi::prelude::*;\n")
+                        },
+                        "coordinator" => dir! {
+                            "posts" => crate_dir("posts", coordinator_lib_rs)
+                        }
+                    }
+                }
+            }
+        };
+        let dna_file_tree = DnaFileTree::from_dna_manifest_path(
+            file_tree,
+            Path::new("dnas/forum/workdir/dna.yaml"),
+        )
+        .unwrap();
+        let integrity_zome = match dna_file_tree.dna_manifest.clone() {
+            DnaManifest::V1(m) => m.integrity.zomes[0].clone(),
+        };
+        let integrity_zome_file_tree =
+            ZomeFileTree::from_zome_manifest(dna_file_tree, integrity_zome).unwrap();
+
+        let (dna_file_tree, _, deletable) = add_collection_to_coordinators(
+            integrity_zome_file_tree,
+            "posts_by_time",
+            "PostsByTime",
+            &CollectionType::ByTime {
+                granularity: TimeGranularity::Hour,
+            },
+            &EntryTypeReference {
+                entry_type: String::from("post"),
+                reference_entry_hash: false,
+            },
+            false,
+            Interactivity::NonInteractive,
+        )
+        .unwrap();
+        assert!(deletable);
+
+        let coordinator_src = Path::new("dnas/forum/zomes/coordinator/posts/src");
+        let file_tree = dna_file_tree.file_tree();
+        assert!(file_content(&file_tree, &coordinator_src.join("posts_by_time.rs")).is_ok());
+        let lib_rs: syn::File =
+            syn::parse_str(&file_content(&file_tree, &coordinator_src.join("lib.rs")).unwrap())
+                .unwrap();
+
+        // The entry is linked from the bucket of the time it was created at, which is created if needed
+        let create_post = &find_fn(&lib_rs, "create_post").block.stmts;
+        let linking: Vec<String> = create_post[create_post.len() - 4..]
+            .iter()
+            .map(tokens_without_spaces)
+            .collect();
+        assert_eq!(
+            linking,
+            vec![
+                "letpath=crate::posts_by_time::posts_by_time_path(record.action().timestamp())?;",
+                "path.ensure()?;",
+                "create_link(path.path_entry_hash()?,post_hash.clone(),LinkTypes::PostsByTime,())?;",
+                "Ok(record)",
+            ]
+        );
+
+        // The links are deleted from the bucket of the time the entry was created at
+        let delete_post = &find_fn(&lib_rs, "delete_post").block.stmts;
+        let unlinking: Vec<String> = delete_post.iter().map(tokens_without_spaces).collect();
+        assert!(unlinking[2].starts_with(
+            "letpath=crate::posts_by_time::posts_by_time_path(record.action().timestamp())?;"
+        ));
+        assert!(unlinking[3].contains(
+            "GetLinksInputBuilder::try_new(path.path_entry_hash()?,LinkTypes::PostsByTime)?"
+        ));
+        assert!(unlinking[4]
+            .contains("ifhash==original_post_hash{delete_link(link.create_link_hash)?;}"));
+        assert_eq!(
+            unlinking.last().unwrap(),
+            "delete_entry(original_post_hash)"
+        );
+    }
+
+    #[test]
+    fn paginated_getters_take_a_link_cursor_and_a_limit() {
+        let file: syn::File = syn::parse2(global_collection_getter(
+            "posts_integrity",
+            "all_posts",
+            "AllPosts",
+            true,
+        ))
+        .unwrap();
+
+        let input = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Struct(s) if s.ident == "GetAllPostsInput" => Some(s),
+                _ => None,
+            })
+            .unwrap();
+        let field_types: Vec<String> = input
+            .fields
+            .iter()
+            .map(|f| f.ty.to_token_stream().to_string().replace(' ', ""))
+            .collect();
+        assert_eq!(field_types, vec!["Option<(Timestamp,ActionHash)>", "u32"]);
+
+        let getter = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Fn(f) if f.sig.ident == "get_all_posts" => Some(f),
+                _ => None,
+            })
+            .unwrap()
+            .to_token_stream()
+            .to_string()
+            .replace(' ', "");
+        // Links created at the same time are ordered by their hash, which is part of the cursor
+        assert!(getter
+            .contains("(a.timestamp,&a.create_link_hash).cmp(&(b.timestamp,&b.create_link_hash))"));
+        assert!(getter.contains("(link.timestamp,&link.create_link_hash)<=(*timestamp,hash)"));
+        assert!(getter.contains(".take(input.limitasusize)"));
+    }
+}
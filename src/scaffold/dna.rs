@@ -8,7 +8,7 @@ use crate::{
     },
     reserved_words::check_for_reserved_keywords,
     templates::{dna::scaffold_dna_templates, ScaffoldedTemplate},
-    utils::choose_directory_path,
+    utils::{choose_directory_path, Interactivity},
 };
 use build_fs_tree::{dir, file};
 use dialoguer::{theme::ColorfulTheme, Select};
@@ -39,6 +39,7 @@ impl DnaFileTree {
     pub fn get_or_choose(
         file_tree: FileTree,
         dna_name: Option<&str>,
+        interactivity: Interactivity,
     ) -> ScaffoldResult<DnaFileTree> {
         let dna_manifests = find_dna_manifests(&file_tree)?;
 
@@ -48,7 +49,7 @@ impl DnaFileTree {
                 .into_iter()
                 .last()
                 .ok_or(ScaffoldError::NoDnasFound),
-            (_, None) => choose_dna(dna_manifests.into_iter().collect()),
+            (_, None) => choose_dna(dna_manifests.into_iter().collect(), interactivity),
             (_, Some(name)) => dna_manifests
                 .into_iter()
                 .find(|(_, m)| m.name().to_string().eq(name))
@@ -88,7 +89,8 @@ fn default_dnas_dir_path() -> PathBuf {
     PathBuf::new().join("dnas")
 }
 
-fn zome_wasm_location(dna_file_tree: &DnaFileTree, zome_name: &str) -> Location {
+/// Location of the wasm of the given zome in the target folder of the cargo workspace
+pub fn zome_wasm_location(dna_file_tree: &DnaFileTree, zome_name: &str) -> Location {
     let mut zome_wasm_location = PathBuf::new();
 
     let mut dna_workdir_path = dna_file_tree.dna_manifest_path.clone();
@@ -127,7 +129,10 @@ pub fn find_dna_manifests(
 
 fn choose_dna(
     dna_manifests: Vec<(PathBuf, DnaManifest)>,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<(PathBuf, DnaManifest)> {
+    interactivity.ensure_interactive("--dna")?;
+
     let dna_names: Vec<String> = dna_manifests
         .iter()
         .map(|(_, m)| m.name().to_string())
@@ -151,7 +156,10 @@ pub fn read_dna_manifest(
     Ok(manifest)
 }
 
-pub fn get_or_choose_dnas_dir_path(app_file_tree: &FileTree) -> ScaffoldResult<PathBuf> {
+pub fn get_or_choose_dnas_dir_path(
+    app_file_tree: &FileTree,
+    interactivity: Interactivity,
+) -> ScaffoldResult<PathBuf> {
     let default_path = default_dnas_dir_path();
     if dir_exists(app_file_tree, &default_path) {
         Ok(default_path.clone())
@@ -159,6 +167,7 @@ pub fn get_or_choose_dnas_dir_path(app_file_tree: &FileTree) -> ScaffoldResult<P
         choose_directory_path(
             &String::from("Which directory should the DNA be scaffolded in?"),
             app_file_tree,
+            interactivity,
         )
     }
 }
@@ -167,6 +176,7 @@ pub fn scaffold_dna(
     app_file_tree: AppFileTree,
     template_file_tree: &FileTree,
     dna_name: &str,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     check_for_reserved_keywords(dna_name)?;
 
@@ -180,7 +190,7 @@ pub fn scaffold_dna(
         }
     };
 
-    let dnas_path = get_or_choose_dnas_dir_path(app_file_tree.file_tree_ref())?;
+    let dnas_path = get_or_choose_dnas_dir_path(app_file_tree.file_tree_ref(), interactivity)?;
 
     let dna_workdir_path = PathBuf::new()
         .join(&dnas_path)
@@ -254,5 +264,6 @@ pub fn scaffold_dna(
         template_file_tree,
         &app_name.to_string(),
         dna_name,
+        interactivity,
     )
 }
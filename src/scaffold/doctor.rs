@@ -0,0 +1,310 @@
+//! Health checks of a scaffolded project, as run by `hc-scaffold doctor`
+
+use std::path::{Path, PathBuf};
+
+use holochain_types::prelude::DnaManifest;
+use mr_bundle::Location;
+use path_clean::PathClean;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    error::ScaffoldResult,
+    file_tree::{file_content, FileTree},
+    versions,
+};
+
+use super::{
+    app::{cargo::get_workspace_cargo_toml, find_app_manifests},
+    config::ScaffoldConfig,
+    dna::{find_dna_manifests, zome_wasm_location, DnaFileTree},
+    web_app::template_type::TemplateType,
+};
+
+/// A problem found in the project, with how to fix it
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// The file in which the problem was found
+    pub path: PathBuf,
+    pub problem: String,
+    pub fix: String,
+}
+
+/// Checks the versions of the holochain dependencies, the app and DNA manifests
+/// and the template configuration of the project
+pub fn diagnose_project(file_tree: &FileTree) -> ScaffoldResult<Vec<Finding>> {
+    let mut findings = check_cargo_versions(file_tree);
+
+    findings.extend(check_npm_versions(
+        file_tree,
+        Path::new("ui/package.json"),
+        &[("@holochain/client", versions::HOLOCHAIN_CLIENT_VERSION)],
+    ));
+    findings.extend(check_npm_versions(
+        file_tree,
+        Path::new("tests/package.json"),
+        &[
+            ("@holochain/client", versions::HOLOCHAIN_CLIENT_VERSION),
+            ("@holochain/tryorama", versions::TRYORAMA_VERSION),
+        ],
+    ));
+    findings.extend(check_npm_versions(
+        file_tree,
+        Path::new("package.json"),
+        &[("@holochain/hc-spin", versions::HC_SPIN_VERSION)],
+    ));
+
+    findings.extend(check_manifests(file_tree)?);
+    findings.extend(check_template(file_tree));
+
+    Ok(findings)
+}
+
+fn check_cargo_versions(file_tree: &FileTree) -> Vec<Finding> {
+    let path = PathBuf::from("Cargo.toml");
+
+    let cargo_toml = match get_workspace_cargo_toml(file_tree) {
+        Ok(cargo_toml) => cargo_toml,
+        Err(e) => {
+            return vec![Finding {
+                path,
+                problem: format!("The workspace Cargo.toml could not be read: {e}"),
+                fix: "Run hc-scaffold from the root folder of the project".to_string(),
+            }]
+        }
+    };
+
+    [("hdk", versions::HDK_VERSION), ("hdi", versions::HDI_VERSION)]
+        .into_iter()
+        .filter_map(|(crate_name, expected)| {
+            let dependency = cargo_toml
+                .get("workspace")
+                .and_then(|w| w.get("dependencies"))
+                .and_then(|d| d.get(crate_name));
+            let version = match dependency {
+                Some(toml::Value::String(version)) => Some(version.as_str()),
+                Some(toml::Value::Table(table)) => table.get("version").and_then(|v| v.as_str()),
+                _ => None,
+            };
+            let expected = format!("={expected}");
+
+            match version {
+                Some(version) if version == expected => None,
+                Some(version) => Some(Finding {
+                    path: path.clone(),
+                    problem: format!(
+                        "{crate_name} is at version \"{version}\", but this version of the scaffolding tool generates code for \"{expected}\""
+                    ),
+                    fix: format!(
+                        "Set {crate_name} = \"{expected}\" in the [workspace.dependencies] of Cargo.toml, or run hc-scaffold upgrade"
+                    ),
+                }),
+                None => Some(Finding {
+                    path: path.clone(),
+                    problem: format!("{crate_name} is not a dependency of the workspace"),
+                    fix: format!(
+                        "Add {crate_name} = \"{expected}\" to the [workspace.dependencies] of Cargo.toml"
+                    ),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Checks the versions of the given dependencies in the given package.json, if it exists and has them
+fn check_npm_versions(
+    file_tree: &FileTree,
+    package_json_path: &Path,
+    dependencies: &[(&str, &str)],
+) -> Vec<Finding> {
+    let Ok(content) = file_content(file_tree, package_json_path) else {
+        return vec![];
+    };
+    let package_json: Value = match serde_json::from_str(&content) {
+        Ok(package_json) => package_json,
+        Err(e) => {
+            return vec![Finding {
+                path: package_json_path.to_path_buf(),
+                problem: format!("The file is not valid JSON: {e}"),
+                fix: "Fix the syntax of the file".to_string(),
+            }]
+        }
+    };
+
+    dependencies
+        .iter()
+        .filter_map(|(package, expected)| {
+            let version = ["dependencies", "devDependencies"]
+                .into_iter()
+                .find_map(|field| package_json.get(field)?.get(package)?.as_str())?;
+
+            (version != *expected).then(|| Finding {
+                path: package_json_path.to_path_buf(),
+                problem: format!(
+                    "{package} is at version \"{version}\", but this version of the scaffolding tool generates code for \"{expected}\""
+                ),
+                fix: format!(
+                    "Set \"{package}\": \"{expected}\" in {}, or run hc-scaffold upgrade",
+                    package_json_path.display()
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Checks that the DNAs of the apps resolve to a DNA manifest,
+/// and that the zomes of the DNAs point to the wasm built in the workspace target folder
+fn check_manifests(file_tree: &FileTree) -> ScaffoldResult<Vec<Finding>> {
+    let mut findings = vec![];
+
+    let dna_manifests = find_dna_manifests(file_tree)?;
+
+    for (app_manifest_path, app_manifest) in find_app_manifests(file_tree)? {
+        let mut workdir = app_manifest_path.clone();
+        workdir.pop();
+
+        for role in app_manifest.app_roles() {
+            let bundle_path = match role.dna.location {
+                Some(Location::Bundled(path)) | Some(Location::Path(path)) => {
+                    workdir.join(path).clean()
+                }
+                _ => continue,
+            };
+            let resolves = dna_manifests
+                .keys()
+                .any(|dna_manifest_path| dna_manifest_path.parent() == bundle_path.parent());
+
+            if !resolves {
+                findings.push(Finding {
+                    path: app_manifest_path.clone(),
+                    problem: format!(
+                        "The DNA of role \"{}\" is bundled from {}, but there is no dna.yaml in that folder",
+                        role.name,
+                        bundle_path.display()
+                    ),
+                    fix: "Point the bundled location of the role to the .dna file next to the dna.yaml of its DNA".to_string(),
+                });
+            }
+        }
+    }
+
+    for dna_manifest_path in dna_manifests.into_keys() {
+        let dna_file_tree =
+            DnaFileTree::from_dna_manifest_path(file_tree.clone(), &dna_manifest_path)?;
+        let DnaManifest::V1(v1) = dna_file_tree.dna_manifest.clone();
+
+        for zome in v1.integrity.zomes.iter().chain(v1.coordinator.zomes.iter()) {
+            let Location::Bundled(mut expected) = zome_wasm_location(&dna_file_tree, &zome.name.0)
+            else {
+                continue;
+            };
+
+            let matches = match &zome.location {
+                Location::Bundled(wasm_path) => {
+                    // The wasm is named after the crate of the zome, which may differ from the zome name
+                    if let Some(file_name) = wasm_path.file_name() {
+                        expected.set_file_name(file_name);
+                    }
+                    wasm_path.clean() == expected
+                }
+                _ => false,
+            };
+            if !matches {
+                findings.push(Finding {
+                    path: dna_manifest_path.clone(),
+                    problem: format!(
+                        "The wasm of zome \"{}\" is not bundled from the workspace target folder",
+                        zome.name
+                    ),
+                    fix: format!(
+                        "Set the bundled location of the zome to {}",
+                        expected.display()
+                    ),
+                });
+            }
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Checks that the template the app was scaffolded with, as configured in its package.json, can still be loaded
+fn check_template(file_tree: &FileTree) -> Option<Finding> {
+    let path = PathBuf::from("package.json");
+    let content = file_content(file_tree, &path).ok()?;
+    let package_json: Value = serde_json::from_str(&content).ok()?;
+
+    let Some(config) = package_json.get("hcScaffold") else {
+        return Some(Finding {
+            path,
+            problem: "The template the app was scaffolded with is not configured".to_string(),
+            fix: "Add \"hcScaffold\": { \"template\": \"<TEMPLATE>\" } to package.json".to_string(),
+        });
+    };
+
+    let template = match serde_json::from_value::<ScaffoldConfig>(config.clone()) {
+        Ok(config) => config.template,
+        Err(e) => {
+            return Some(Finding {
+                path,
+                problem: format!("The hcScaffold configuration is malformed: {e}"),
+                fix: "Set \"hcScaffold\": { \"template\": \"<TEMPLATE>\" } in package.json"
+                    .to_string(),
+            })
+        }
+    };
+
+    let TemplateType::Custom(template_path) = &template else {
+        return None;
+    };
+    if let Err(e) = template.check_valid_template() {
+        return Some(Finding {
+            path,
+            problem: format!(
+                "The custom template at {} could not be loaded: {e}",
+                template_path.display()
+            ),
+            fix: "Point hcScaffold.template in package.json to the folder of the template"
+                .to_string(),
+        });
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use build_fs_tree::{dir, file};
+
+    use super::*;
+
+    #[test]
+    fn reports_outdated_dependencies() {
+        let file_tree: FileTree = dir! {
+            "Cargo.toml" => file!(format!(
+                "[workspace.dependencies]\nhdi = \"={}\"\nhdk = \"=0.1.0\"\n",
+                versions::HDI_VERSION
+            )),
+            "tests" => dir! {
+                "package.json" => file!(format!(
+                    r#"{{ "dependencies": {{ "@holochain/client": "{}", "@holochain/tryorama": "^0.1.0" }} }}"#,
+                    versions::HOLOCHAIN_CLIENT_VERSION
+                ))
+            }
+        };
+
+        let outdated: Vec<PathBuf> = diagnose_project(&file_tree)
+            .unwrap()
+            .into_iter()
+            .map(|finding| finding.path)
+            .collect();
+
+        assert_eq!(
+            outdated,
+            vec![
+                PathBuf::from("Cargo.toml"),
+                PathBuf::from("tests/package.json")
+            ]
+        );
+    }
+}
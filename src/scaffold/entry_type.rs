@@ -1,37 +1,74 @@
-use std::{ffi::OsString, path::PathBuf};
+use std::{
+    collections::BTreeSet,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use crate::{
-    file_tree::FileTree,
+    file_tree::{file_content, file_exists, flatten_file_tree, insert_file, FileTree},
     reserved_words::check_for_reserved_keywords,
-    templates::{entry_type::scaffold_entry_type_templates, ScaffoldedTemplate},
+    templates::{
+        collection::scaffold_collection_templates,
+        entry_type::scaffold_entry_type_templates,
+        field::{scaffold_field_templates, ScaffoldedFields},
+        ScaffoldedTemplate,
+    },
+    utils::{format_code, Interactivity},
 };
 
 use build_fs_tree::dir;
 use colored::Colorize;
 use convert_case::{Case, Casing};
 use dialoguer::{theme::ColorfulTheme, Select};
+use holochain_types::prelude::{DnaManifest, ZomeManifest};
+use itertools::Itertools;
+use quote::ToTokens;
+use regex::Regex;
 
 use crate::error::{ScaffoldError, ScaffoldResult};
 
 use self::{
-    coordinator::{add_crud_functions_to_coordinator, updates_link_name},
+    coordinator::{add_crud_functions_to_coordinator, initial_crud_handlers, updates_link_name},
     crud::Crud,
-    definitions::{EntryDefinition, EntryTypeReference, FieldDefinition, Referenceable},
+    definitions::{
+        Cardinality, EntryDefinition, EntryTypeReference, FieldDefinition, FieldType, Permission,
+        Referenceable, Visibility,
+    },
     fields::choose_fields,
-    integrity::{add_entry_type_to_integrity_zome, get_all_entry_types},
+    integrity::{
+        add_entry_type_to_integrity_zome, add_fields_to_entry_type, get_all_entry_types,
+        is_private_entry_type, remove_entry_type_from_integrity_zome,
+        remove_fields_from_entry_type, render_entry_definition_file,
+    },
 };
 
 use super::{
     app::AppFileTree,
-    link_type::{integrity::add_link_type_to_integrity_zome, link_type_name},
+    collection::CollectionType,
+    dna::{find_dna_manifests, DnaFileTree},
+    link_type::{
+        integrity::{
+            add_link_type_to_integrity_zome, get_all_link_types, get_link_types_validated_in_file,
+        },
+        link_type_identifiers, link_type_name, link_type_reference_patterns, remove_link_types,
+    },
+    remove::{
+        any_case_reference, find_leftover_references, is_code_file, pascal_case_reference,
+        remove_generated_files, remove_module, remove_typescript_functions, ui_and_tests_folders,
+        LeftoverReference, Removal,
+    },
+    rename::{identifier_case, rename_file_name, NameReplacer, Renaming},
     zome::{utils::get_coordinator_zomes_for_integrity, ZomeFileTree},
 };
 
+pub mod constraints;
 pub mod coordinator;
 pub mod crud;
 pub mod definitions;
 pub mod fields;
 pub mod integrity;
+pub mod schema;
 pub mod utils;
 
 // TODO: group some params into a new-type or prefer builder pattern
@@ -43,9 +80,13 @@ pub fn scaffold_entry_type(
     maybe_crud: Option<Crud>,
     maybe_reference_entry_hash: Option<bool>,
     maybe_link_from_original_to_each_update: Option<bool>,
+    paginated_revisions: bool,
+    maybe_visibility: Option<Visibility>,
+    maybe_permission: Option<Permission>,
     maybe_fields: Option<&Vec<FieldDefinition>>,
     no_ui: bool,
     no_spec: bool,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     check_for_reserved_keywords(name)?;
 
@@ -55,7 +96,7 @@ WARNING: Opting out of UI generation for this entry-type but not for other entry
 may result in potential UI inconsistencies. Specifically, UI elements intended for associated entry-types, link-types or collections could
 inadvertently reference or expect elements from the skipped entry type."#
             .yellow();
-        println!("{warning_text}");
+        eprintln!("{warning_text}");
     }
 
     let fields = match maybe_fields {
@@ -73,20 +114,34 @@ inadvertently reference or expect elements from the skipped entry type."#
                 &zome_file_tree,
                 template_file_tree.path(&mut v.iter()).unwrap_or(&dir! {}),
                 no_ui,
+                interactivity,
             )?
         }
     };
 
+    let visibility = maybe_visibility.unwrap_or_default();
+    let fields = match visibility {
+        Visibility::Public => fields,
+        Visibility::Private => skip_links_to_private_entries(name, fields),
+    };
+
     let reference_entry_hash = maybe_reference_entry_hash.unwrap_or(false);
 
     let crud = match maybe_crud {
         Some(c) => c,
-        None => Crud::choose()?,
+        None => Crud::choose(interactivity)?,
     };
 
-    let link_from_original_to_each_update = if crud.update {
+    if let Some(permission) = &maybe_permission {
+        check_permission(name, permission, &fields, &crud)?;
+    }
+
+    // The links to the updates of a private entry would be public
+    let link_from_original_to_each_update = if crud.update && visibility == Visibility::Public {
         if let Some(l) = maybe_link_from_original_to_each_update {
             l
+        } else if !interactivity.is_interactive() {
+            true
         } else {
             let selection = Select::with_theme(&ColorfulTheme::default())
                 .with_prompt(
@@ -103,10 +158,19 @@ inadvertently reference or expect elements from the skipped entry type."#
         false
     };
 
+    if paginated_revisions && !link_from_original_to_each_update {
+        return Err(ScaffoldError::InvalidArguments(format!(
+            "the revisions of {} can only be paginated if it's public, can be updated and links the original entry to each update",
+            name.to_case(Case::Pascal)
+        )));
+    }
+
     let entry_def = EntryDefinition {
         name: name.to_owned(),
         fields,
         reference_entry_hash,
+        visibility,
+        permission: maybe_permission,
     };
     let entry_def_ts_types = entry_def.ts_type_codegen();
 
@@ -143,12 +207,15 @@ inadvertently reference or expect elements from the skipped entry type."#
         )),
         1 => Ok(coordinator_zomes_for_integrity[0].clone()),
         _ => {
+            let prompt = "Which coordinator zome should the CRUD functions be scaffolded in?";
+            interactivity.ensure_can_prompt(prompt)?;
+
             let names: Vec<String> = coordinator_zomes_for_integrity
                 .iter()
                 .map(|z| z.name.to_string())
                 .collect();
             let selection = Select::with_theme(&ColorfulTheme::default())
-                .with_prompt("Which coordinator zome should the CRUD functions be scaffolded in?")
+                .with_prompt(prompt)
                 .default(0)
                 .items(&names[..])
                 .interact()?;
@@ -177,11 +244,16 @@ inadvertently reference or expect elements from the skipped entry type."#
         &entry_def,
         &crud,
         link_from_original_to_each_update,
+        paginated_revisions,
     )?;
 
     let dna_manifest = zome_file_tree.dna_file_tree.dna_manifest.clone();
 
-    let app_file_tree = AppFileTree::get_or_choose(zome_file_tree.dna_file_tree.file_tree(), None)?;
+    let app_file_tree = AppFileTree::get_or_choose(
+        zome_file_tree.dna_file_tree.file_tree(),
+        None,
+        interactivity,
+    )?;
 
     let app_name = app_file_tree.app_manifest.app_name().to_string();
 
@@ -195,11 +267,74 @@ inadvertently reference or expect elements from the skipped entry type."#
         &entry_def_ts_types,
         &crud,
         link_from_original_to_each_update,
+        paginated_revisions,
         no_ui,
         no_spec,
+        interactivity,
     )
 }
 
+/// Leaves out the links from the fields of a private entry type, which would reveal its entries to other agents
+fn skip_links_to_private_entries(
+    entry_type_name: &str,
+    fields: Vec<FieldDefinition>,
+) -> Vec<FieldDefinition> {
+    let linked_fields: Vec<&str> = fields
+        .iter()
+        .filter(|f| f.linked_from.is_some())
+        .map(|f| f.field_name.as_str())
+        .collect();
+    if !linked_fields.is_empty() {
+        let warning_text = format!(
+            "\nWARNING: {} is a private entry type, so no links will be created from its fields {}",
+            entry_type_name.to_case(Case::Pascal),
+            linked_fields.join(", ")
+        )
+        .yellow();
+        eprintln!("{warning_text}");
+    }
+
+    fields
+        .into_iter()
+        .map(|f| FieldDefinition {
+            linked_from: None,
+            ..f
+        })
+        .collect()
+}
+
+/// Checks that the entries of the entry type can be modified, and that the field listing the
+/// editors is a vector of agents
+fn check_permission(
+    entry_type_name: &str,
+    permission: &Permission,
+    fields: &[FieldDefinition],
+    crud: &Crud,
+) -> ScaffoldResult<()> {
+    if !crud.update && !crud.delete {
+        return Err(ScaffoldError::InvalidArguments(format!(
+            "{} can't be updated nor deleted, so it needs no permission",
+            entry_type_name.to_case(Case::Pascal)
+        )));
+    }
+
+    if let Permission::Editors { field } = permission {
+        let is_agents_vector = fields.iter().any(|f| {
+            &f.field_name == field
+                && f.field_type == FieldType::AgentPubKey
+                && f.cardinality == Cardinality::Vector
+        });
+        if !is_agents_vector {
+            return Err(ScaffoldError::InvalidArguments(format!(
+                "the field {field} listing the editors of {} must be of type Vec<AgentPubKey>",
+                entry_type_name.to_case(Case::Pascal)
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 fn check_field_definitions(
     entry_type_name: &str,
     zome_file_tree: &ZomeFileTree,
@@ -237,3 +372,1004 @@ fn check_field_definitions(
         None => Ok(()),
     }
 }
+
+/// Removes the given entry type, with its validation, its CRUD functions, its UI and tests,
+/// and the link types that were scaffolded with it
+pub fn remove_entry_type(
+    zome_file_tree: ZomeFileTree,
+    entry_type_name: &str,
+) -> ScaffoldResult<Removal> {
+    let pascal_entry_def_name = entry_type_name.to_case(Case::Pascal);
+    let snake_entry_def_name = entry_type_name.to_case(Case::Snake);
+    let kebab_entry_def_name = entry_type_name.to_case(Case::Kebab);
+
+    let exists = get_all_entry_types(&zome_file_tree)?
+        .unwrap_or_default()
+        .iter()
+        .any(|e| e.entry_type.to_case(Case::Pascal) == pascal_entry_def_name);
+
+    if !exists {
+        return Err(ScaffoldError::EntryTypeNotFound(
+            entry_type_name.to_owned(),
+            zome_file_tree.dna_file_tree.dna_manifest.name(),
+            zome_file_tree.zome_manifest.name.to_string(),
+        ));
+    }
+
+    // The updates link, the links from the entry types it's linked from and its collections
+    let link_types = get_link_types_validated_in_file(
+        &zome_file_tree,
+        Path::new(&format!("{snake_entry_def_name}.rs")),
+    )?;
+    let (zome_file_tree, mut removed_files) = remove_link_types(zome_file_tree, &link_types)?;
+
+    let zome_file_tree = remove_entry_type_from_integrity_zome(zome_file_tree, entry_type_name)?;
+
+    let dna_manifest_path = zome_file_tree.dna_file_tree.dna_manifest_path.clone();
+    let dna_name = zome_file_tree.dna_file_tree.dna_manifest.name();
+    let coordinator_zomes = get_coordinator_zomes_for_integrity(
+        &zome_file_tree.dna_file_tree.dna_manifest,
+        zome_file_tree.zome_manifest.name.0.as_ref(),
+    );
+    let integrity_src_path = zome_file_tree.zome_crate_path.join("src");
+    let mut file_tree = zome_file_tree.dna_file_tree.file_tree();
+
+    removed_files.extend(remove_module(
+        &mut file_tree,
+        &integrity_src_path,
+        &snake_entry_def_name,
+    )?);
+
+    let generated_file_names = [
+        format!("Create{pascal_entry_def_name}"),
+        format!("Edit{pascal_entry_def_name}"),
+        format!("{pascal_entry_def_name}Detail"),
+        format!("create-{kebab_entry_def_name}"),
+        format!("edit-{kebab_entry_def_name}"),
+        format!("{kebab_entry_def_name}-detail"),
+        kebab_entry_def_name.clone(),
+    ];
+    let test_helpers = vec![
+        format!("sample{pascal_entry_def_name}"),
+        format!("create{pascal_entry_def_name}"),
+    ];
+
+    for coordinator_zome in coordinator_zomes {
+        let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+        let coordinator_file_tree =
+            ZomeFileTree::from_zome_manifest(dna_file_tree, coordinator_zome.clone())?;
+        let coordinator_src_path = coordinator_file_tree.zome_crate_path.join("src");
+        file_tree = coordinator_file_tree.dna_file_tree.file_tree();
+
+        removed_files.extend(remove_module(
+            &mut file_tree,
+            &coordinator_src_path,
+            &snake_entry_def_name,
+        )?);
+
+        let ui_and_tests = ui_and_tests_folders(&dna_name, &coordinator_zome.name.0);
+        let types_path = ui_and_tests[0].join("types.ts");
+        if file_exists(&file_tree, &types_path) {
+            let content = file_content(&file_tree, &types_path)?;
+            if let Some(new_content) = remove_ts_entry_type(&content, entry_type_name) {
+                insert_file(
+                    &mut file_tree,
+                    &types_path,
+                    &format_code(&new_content, &types_path)?,
+                )?;
+            }
+        }
+
+        for folder in ui_and_tests {
+            removed_files.extend(remove_generated_files(&mut file_tree, &folder, |stem| {
+                generated_file_names.iter().any(|n| n == stem)
+            })?);
+            remove_typescript_functions(&mut file_tree, &folder.join("common.ts"), &test_helpers)?;
+        }
+    }
+
+    let mut patterns = vec![
+        pascal_case_reference(&pascal_entry_def_name),
+        pascal_case_reference(&pluralizer::pluralize(&pascal_entry_def_name, 2, false)),
+        any_case_reference(&format!("{snake_entry_def_name}_hash")),
+    ];
+    patterns.extend(
+        link_types
+            .iter()
+            .flat_map(|l| link_type_reference_patterns(l)),
+    );
+    let leftover_references = find_leftover_references(&file_tree, &patterns);
+
+    Ok(Removal {
+        file_tree,
+        removed_files,
+        leftover_references,
+    })
+}
+
+/// Adds the given fields to an existing entry type, in its integrity zome and in the UI and tests
+/// that were generated for it
+pub fn add_fields(
+    zome_file_tree: ZomeFileTree,
+    template_file_tree: &FileTree,
+    entry_type_name: &str,
+    maybe_fields: Option<&Vec<FieldDefinition>>,
+    no_ui: bool,
+    interactivity: Interactivity,
+) -> ScaffoldResult<ScaffoldedFields> {
+    let entry_type = get_all_entry_types(&zome_file_tree)?
+        .unwrap_or_default()
+        .into_iter()
+        .find(|e| e.entry_type.to_case(Case::Pascal) == entry_type_name.to_case(Case::Pascal))
+        .ok_or(ScaffoldError::EntryTypeNotFound(
+            entry_type_name.to_owned(),
+            zome_file_tree.dna_file_tree.dna_manifest.name(),
+            zome_file_tree.zome_manifest.name.to_string(),
+        ))?;
+
+    let fields = match maybe_fields {
+        Some(f) => {
+            check_field_definitions(entry_type_name, &zome_file_tree, f)?;
+            f.clone()
+        }
+        None => {
+            let v: Vec<OsString> = PathBuf::from("field-types")
+                .iter()
+                .map(|s| s.to_os_string())
+                .collect();
+            choose_fields(
+                entry_type_name,
+                &zome_file_tree,
+                template_file_tree.path(&mut v.iter()).unwrap_or(&dir! {}),
+                no_ui,
+                interactivity,
+            )?
+        }
+    };
+
+    let visibility = if is_private_entry_type(&zome_file_tree, entry_type_name)? {
+        Visibility::Private
+    } else {
+        Visibility::Public
+    };
+    let fields = match visibility {
+        Visibility::Public => fields,
+        Visibility::Private => skip_links_to_private_entries(entry_type_name, fields),
+    };
+
+    let entry_def = EntryDefinition {
+        name: entry_type_name.to_owned(),
+        fields,
+        reference_entry_hash: entry_type.reference_entry_hash,
+        visibility,
+        permission: None,
+    };
+
+    let zome_file_tree =
+        add_fields_to_entry_type(zome_file_tree, entry_type_name, &entry_def.fields)?;
+
+    let dna_name = zome_file_tree.dna_file_tree.dna_manifest.name();
+    let coordinator_zomes = get_coordinator_zomes_for_integrity(
+        &zome_file_tree.dna_file_tree.dna_manifest,
+        zome_file_tree.zome_manifest.name.0.as_ref(),
+    );
+    let mut file_tree = zome_file_tree.dna_file_tree.file_tree();
+    let mut skipped_files = vec![];
+
+    for coordinator_zome in coordinator_zomes {
+        let types_path = PathBuf::from("ui/src")
+            .join(&dna_name)
+            .join(coordinator_zome.name.0.as_ref())
+            .join("types.ts");
+        if !no_ui && file_exists(&file_tree, &types_path) {
+            let content = file_content(&file_tree, &types_path)?;
+            match add_fields_to_ts_interface(&content, &entry_def) {
+                Some(new_content) => {
+                    insert_file(
+                        &mut file_tree,
+                        &types_path,
+                        &format_code(&new_content, &types_path)?,
+                    )?;
+                }
+                None => skipped_files.push((
+                    types_path,
+                    format!("interface {} not found", entry_def.pascal_case_name()),
+                )),
+            }
+        }
+
+        let scaffolded_fields = scaffold_field_templates(
+            file_tree,
+            template_file_tree,
+            &dna_name,
+            &coordinator_zome,
+            &entry_def,
+            no_ui,
+        )?;
+        file_tree = scaffolded_fields.file_tree;
+        skipped_files.extend(scaffolded_fields.skipped_files);
+    }
+
+    Ok(ScaffoldedFields {
+        file_tree,
+        skipped_files,
+    })
+}
+
+/// Removes the given fields from an existing entry type, with their validation and their typescript definitions
+///
+/// Refuses to remove them while the code generated for the entry type in the coordinator zomes, the UI
+/// and the tests still uses them, listing every line that must be edited by hand first. Other uses of the
+/// fields are left in place, and listed as leftover references
+pub fn remove_fields(
+    zome_file_tree: ZomeFileTree,
+    entry_type_name: &str,
+    field_names: &[String],
+) -> ScaffoldResult<Removal> {
+    let zome_file_tree =
+        remove_fields_from_entry_type(zome_file_tree, entry_type_name, field_names)?;
+
+    let dna_name = zome_file_tree.dna_file_tree.dna_manifest.name();
+    let dna_manifest_path = zome_file_tree.dna_file_tree.dna_manifest_path.clone();
+    let coordinator_zomes = get_coordinator_zomes_for_integrity(
+        &zome_file_tree.dna_file_tree.dna_manifest,
+        zome_file_tree.zome_manifest.name.0.as_ref(),
+    );
+    let mut folders = vec![zome_file_tree.zome_crate_path.clone()];
+    let mut file_tree = zome_file_tree.dna_file_tree.file_tree();
+
+    for coordinator_zome in coordinator_zomes {
+        let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+        let coordinator_file_tree =
+            ZomeFileTree::from_zome_manifest(dna_file_tree, coordinator_zome.clone())?;
+        folders.push(coordinator_file_tree.zome_crate_path.clone());
+        file_tree = coordinator_file_tree.dna_file_tree.file_tree();
+
+        let ui_and_tests = ui_and_tests_folders(&dna_name, &coordinator_zome.name.0);
+        let types_path = ui_and_tests[0].join("types.ts");
+        if file_exists(&file_tree, &types_path) {
+            let content = file_content(&file_tree, &types_path)?;
+            if let Some(new_content) =
+                remove_fields_from_ts_interface(&content, entry_type_name, field_names)
+            {
+                insert_file(
+                    &mut file_tree,
+                    &types_path,
+                    &format_code(&new_content, &types_path)?,
+                )?;
+            }
+        }
+        folders.extend(ui_and_tests);
+    }
+
+    let patterns: Vec<Regex> = field_names
+        .iter()
+        .flat_map(|f| {
+            [
+                format!(r"\b_?{}\b", regex::escape(&f.to_case(Case::Snake))),
+                format!(r"\b_?{}\b", regex::escape(&f.to_case(Case::Camel))),
+                format!(r"\bset{}\b", regex::escape(&f.to_case(Case::Pascal))),
+            ]
+        })
+        .unique()
+        .map(|p| Regex::new(&p).expect("Failed to create regex"))
+        .collect();
+    let (references_to_edit, leftover_references): (Vec<LeftoverReference>, _) =
+        find_leftover_references(&file_tree, &patterns)
+            .into_iter()
+            .filter(|r| folders.iter().any(|f| r.path.starts_with(f)))
+            .partition(|r| is_generated_for_entry_type(&file_tree, r, entry_type_name));
+
+    if !references_to_edit.is_empty() {
+        return Err(ScaffoldError::FieldsStillUsed(
+            entry_type_name.to_owned(),
+            references_to_edit
+                .iter()
+                .map(|r| format!("  {r}"))
+                .join("\n"),
+        ));
+    }
+
+    Ok(Removal {
+        file_tree,
+        removed_files: vec![],
+        leftover_references,
+    })
+}
+
+/// Whether the reference is in the coordinator module, the UI components, the tests or the sample
+/// data that were generated for the entry type
+fn is_generated_for_entry_type(
+    file_tree: &FileTree,
+    reference: &LeftoverReference,
+    entry_type_name: &str,
+) -> bool {
+    let Some(file_name) = reference.path.file_name().and_then(|f| f.to_str()) else {
+        return false;
+    };
+    let stem = file_name.split('.').next().unwrap_or_default();
+
+    if file_name == "common.ts" {
+        let sample_function = format!("function sample{}(", entry_type_name.to_case(Case::Pascal));
+        let Ok(content) = file_content(file_tree, &reference.path) else {
+            return false;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let Some(start) = lines.iter().position(|l| l.contains(&sample_function)) else {
+            return false;
+        };
+        let end = lines[start..]
+            .iter()
+            .position(|l| *l == "}")
+            .map_or(lines.len(), |i| start + i);
+        return (start + 1..=end + 1).contains(&reference.line_number);
+    }
+
+    match reference.path.extension().and_then(|e| e.to_str()) {
+        Some("rs") => stem == entry_type_name.to_case(Case::Snake),
+        _ => {
+            stem.contains(&entry_type_name.to_case(Case::Pascal))
+                || stem.contains(&entry_type_name.to_case(Case::Kebab))
+        }
+    }
+}
+
+fn ts_interface_range(content: &str, entry_type_name: &str) -> Option<(usize, usize)> {
+    let interface_opener = format!(
+        "export interface {} {{",
+        entry_type_name.to_case(Case::Pascal)
+    );
+    let start = content.find(&interface_opener)?;
+    let end = start + content[start..].find("\n}")?;
+    Some((start, end))
+}
+
+/// Adds the fields of the given entry definition to its typescript interface, together with
+/// the type definitions they need
+fn add_fields_to_ts_interface(content: &str, entry_def: &EntryDefinition) -> Option<String> {
+    let (start, end) = ts_interface_range(content, &entry_def.name)?;
+
+    let type_definitions: String = entry_def
+        .fields
+        .iter()
+        .filter(|f| match &f.field_type {
+            FieldType::Enum { label, .. } => !content.contains(&format!("export type {label} =")),
+            FieldType::Struct { label, .. } => {
+                !content.contains(&format!("export interface {label} {{"))
+            }
+            _ => false,
+        })
+        .filter_map(|f| f.field_type.ts_type_definition())
+        .unique()
+        .map(|d| format!("{d}\n\n"))
+        .collect();
+    let new_fields: String = entry_def
+        .fields
+        .iter()
+        .map(|f| format!("\n{}", f.ts_field_codegen()))
+        .collect();
+
+    Some(format!(
+        "{}{type_definitions}{}{new_fields}{}",
+        &content[..start],
+        &content[start..end],
+        &content[end..]
+    ))
+}
+
+/// Removes the given fields from the typescript interface of the entry type, together with
+/// the type definitions that were only used by them
+fn remove_fields_from_ts_interface(
+    content: &str,
+    entry_type_name: &str,
+    field_names: &[String],
+) -> Option<String> {
+    let (start, end) = ts_interface_range(content, entry_type_name)?;
+
+    let mut removed_types = vec![];
+    let mut interface_lines = vec![];
+    for line in content[start..end].lines() {
+        let removed_type = field_names.iter().find_map(|f| {
+            let f = f.to_case(Case::Snake);
+            line.trim_start()
+                .strip_prefix(&format!("{f}:"))
+                .or_else(|| line.trim_start().strip_prefix(&format!("{f}?:")))
+        });
+        match removed_type {
+            Some(t) => removed_types.push(t.to_owned()),
+            None => interface_lines.push(line),
+        }
+    }
+    let new_content = format!(
+        "{}{}{}",
+        &content[..start],
+        interface_lines.join("\n"),
+        &content[end..]
+    );
+
+    let type_identifier = Regex::new(r"[A-Z]\w*").expect("Failed to create regex");
+    let unused_types: Vec<String> = removed_types
+        .iter()
+        .flat_map(|t| type_identifier.find_iter(t).map(|m| m.as_str().to_owned()))
+        .filter(|t| {
+            let reference = Regex::new(&format!(r"\b{t}\b")).expect("Failed to create regex");
+            let type_definition = format!("export type {t} =");
+            new_content.contains(&type_definition)
+                && new_content
+                    .lines()
+                    .filter(|l| !l.starts_with(&type_definition))
+                    .all(|l| !reference.is_match(l))
+        })
+        .collect();
+
+    Some(
+        new_content
+            .lines()
+            .filter(|l| {
+                !unused_types
+                    .iter()
+                    .any(|t| l.starts_with(&format!("export type {t} =")))
+            })
+            .join("\n"),
+    )
+}
+
+/// Removes the typescript interface of the entry type and its member of the `EntryTypes` union,
+/// together with the type definitions that were only used by its fields
+fn remove_ts_entry_type(content: &str, entry_type_name: &str) -> Option<String> {
+    let pascal_entry_def_name = entry_type_name.to_case(Case::Pascal);
+
+    let (start, end) = ts_interface_range(content, entry_type_name)?;
+    let field_names: Vec<String> = content[start..end]
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .map(|(field_name, _)| field_name.trim().trim_end_matches('?').to_owned())
+        .collect();
+    let content = remove_fields_from_ts_interface(content, entry_type_name, &field_names)?;
+
+    let (start, end) = ts_interface_range(&content, entry_type_name)?;
+    let content = format!("{}{}", content[..start].trim_end(), &content[end + 2..]);
+
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    let member = format!("type: '{pascal_entry_def_name}'; }} & {pascal_entry_def_name})");
+    if let Some(i) = lines
+        .iter()
+        .position(|l| l.trim_start().starts_with("| ({") && l.contains(&member))
+    {
+        let was_last = lines.remove(i).ends_with(';');
+        let union_start = lines[..i]
+            .iter()
+            .rposition(|l| l.starts_with("export type EntryTypes ="));
+        let previous_member = lines[..i]
+            .iter()
+            .rposition(|l| l.trim_start().starts_with("| ({"))
+            .filter(|member| union_start.map_or(true, |start| start < *member));
+        match (was_last, previous_member, union_start) {
+            (true, Some(member), _) => lines[member].push(';'),
+            (true, None, Some(start)) => {
+                // The union was left empty, as it was before any entry type was scaffolded
+                lines.splice(start..i, [String::from("export type EntryTypes = {};")]);
+                let is_marker = |line: Option<&String>, marker: &str| {
+                    line.is_some_and(|l| l.trim() == format!("/* dprint-ignore-{marker} */"))
+                };
+                if is_marker(lines.get(start + 1), "end") {
+                    lines.remove(start + 1);
+                }
+                if start > 0 && is_marker(lines.get(start - 1), "start") {
+                    lines.remove(start - 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(format!("{}\n", lines.join("\n")))
+}
+
+/// Renames an entry type in its integrity and coordinator zomes, and in the UI and tests generated for it
+/// with the given templates
+pub fn rename_entry_type(
+    zome_file_tree: ZomeFileTree,
+    template_file_tree: &FileTree,
+    old_name: &str,
+    new_name: &str,
+) -> ScaffoldResult<Renaming> {
+    check_for_reserved_keywords(new_name)?;
+
+    let dna_name = zome_file_tree.dna_file_tree.dna_manifest.name();
+    let zome_name = zome_file_tree.zome_manifest.name.to_string();
+
+    let entry_type_references = get_all_entry_types(&zome_file_tree)?.unwrap_or_default();
+    let entry_types: Vec<String> = entry_type_references
+        .iter()
+        .map(|e| e.entry_type.clone())
+        .collect();
+    let link_types = get_all_link_types(&zome_file_tree)?;
+    let find_entry_type = |name: &str| {
+        entry_type_references
+            .iter()
+            .find(|e| e.entry_type.to_case(Case::Pascal) == name.to_case(Case::Pascal))
+    };
+
+    let Some(old_entry_type) = find_entry_type(old_name) else {
+        return Err(ScaffoldError::EntryTypeNotFound(
+            old_name.to_owned(),
+            dna_name,
+            zome_name,
+        ));
+    };
+    if find_entry_type(new_name).is_some() {
+        return Err(ScaffoldError::EntryTypeAlreadyExists(
+            new_name.to_owned(),
+            dna_name,
+            zome_name,
+        ));
+    }
+
+    // Only the name and the options matter to tell the identifiers generated for the entry type
+    let old_entry_def = EntryDefinition {
+        name: old_name.to_owned(),
+        fields: vec![],
+        reference_entry_hash: old_entry_type.reference_entry_hash,
+        visibility: match is_private_entry_type(&zome_file_tree, old_name)? {
+            true => Visibility::Private,
+            false => Visibility::Public,
+        },
+        permission: None,
+    };
+    let link_from_original_to_each_update = link_types.contains(&updates_link_name(old_name));
+
+    let coordinator_zomes =
+        get_coordinator_zomes_for_integrity(&zome_file_tree.dna_file_tree.dna_manifest, &zome_name);
+
+    let dna_manifest = zome_file_tree.dna_file_tree.dna_manifest.clone();
+    let dna_manifest_path = zome_file_tree.dna_file_tree.dna_manifest_path.clone();
+    let mut folders = vec![zome_file_tree.zome_crate_path.clone()];
+    let mut file_tree = zome_file_tree.dna_file_tree.file_tree();
+
+    for coordinator_zome in &coordinator_zomes {
+        let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+        let coordinator_file_tree =
+            ZomeFileTree::from_zome_manifest(dna_file_tree, coordinator_zome.clone())?;
+        folders.push(coordinator_file_tree.zome_crate_path.clone());
+        folders.extend(ui_and_tests_folders(&dna_name, &coordinator_zome.name.0));
+        file_tree = coordinator_file_tree.dna_file_tree.file_tree();
+    }
+
+    // The app level UI and tests, outside of the folders of every DNA
+    let dna_names: Vec<String> = find_dna_manifests(&file_tree)?
+        .into_values()
+        .map(|m| m.name())
+        .collect();
+    let is_app_level_file = |path: &Path| {
+        ["ui", "tests"].iter().any(|folder| {
+            let src_path = PathBuf::from(folder).join("src");
+            path.starts_with(&src_path)
+                && !dna_names
+                    .iter()
+                    .any(|dna_name| path.starts_with(src_path.join(dna_name)))
+        })
+    };
+
+    let mut protected_identifiers = vec![dna_name.clone()];
+    match dna_manifest {
+        DnaManifest::V1(m) => protected_identifiers.extend(
+            m.integrity
+                .zomes
+                .iter()
+                .chain(m.coordinator.zomes.iter())
+                .map(|z| z.name.0.to_string()),
+        ),
+    }
+
+    let replacer = NameReplacer::new(old_name, new_name)
+        .protect(protected_identifiers)
+        .keep(
+            entry_types
+                .iter()
+                .filter(|e| e.to_case(Case::Pascal) != old_name.to_case(Case::Pascal))
+                .map(|e| e.as_str()),
+        )
+        .generated_identifiers(entry_type_identifiers(
+            &file_tree,
+            template_file_tree,
+            &dna_name,
+            &zome_name,
+            coordinator_zomes.first(),
+            &old_entry_def,
+            link_from_original_to_each_update,
+        )?)
+        .generated_identifiers(link_types.iter().flat_map(|l| link_type_identifiers(l)));
+
+    Renaming::new(file_tree).map_files(
+        |path| {
+            is_code_file(path)
+                && (folders.iter().any(|f| path.starts_with(f)) || is_app_level_file(path))
+        },
+        |path, content| Ok(replacer.replace(content, identifier_case(path))),
+        |path| rename_file_name(path, &replacer),
+    )
+}
+
+/// A name that no template contains, to tell the identifiers generated from the name of an entry type
+/// apart from the ones that only happen to contain it, like `createEventDispatcher` for `Event`
+const SAMPLE_ENTRY_TYPE_NAME: &str = "quux";
+
+/// The name of the samples that refer to the sample entry type, like an entry type linked from it or a collection of it
+const OTHER_SAMPLE_NAME: &str = "corge";
+
+/// The identifiers the scaffolder generates from the name of the given entry type in its zome functions,
+/// and with the given templates in its UI and tests and in the ones of the collections and entry types that refer to it,
+/// like `get_latest_post`, `PostDetail` or `fetchPosts`
+///
+/// They are found by rendering the code of an entry type with the same options and a sample name,
+/// with and without the options that can't be told from the existing code
+fn entry_type_identifiers(
+    app_file_tree: &FileTree,
+    template_file_tree: &FileTree,
+    dna_name: &str,
+    integrity_zome_name: &str,
+    coordinator_zome: Option<&ZomeManifest>,
+    entry_def: &EntryDefinition,
+    link_from_original_to_each_update: bool,
+) -> ScaffoldResult<Vec<String>> {
+    let sample_entry_def = EntryDefinition {
+        name: SAMPLE_ENTRY_TYPE_NAME.to_string(),
+        fields: vec![FieldDefinition::from_str("title:String:TextField")?],
+        ..entry_def.clone()
+    };
+    let hash_type = match entry_def.reference_entry_hash {
+        true => "EntryHash",
+        false => "ActionHash",
+    };
+    let linking_entry_def = EntryDefinition {
+        name: OTHER_SAMPLE_NAME.to_string(),
+        fields: vec![FieldDefinition::from_str(&format!(
+            "{SAMPLE_ENTRY_TYPE_NAME}_hash:{hash_type}::{SAMPLE_ENTRY_TYPE_NAME}"
+        ))?],
+        reference_entry_hash: false,
+        visibility: Visibility::Public,
+        permission: None,
+    };
+
+    let full_crud = Crud {
+        update: true,
+        delete: true,
+    };
+    let mut entry_types = vec![(&linking_entry_def, full_crud, false, false)];
+    for update in [true, false] {
+        entry_types.push((
+            &sample_entry_def,
+            Crud {
+                update,
+                delete: true,
+            },
+            link_from_original_to_each_update,
+            false,
+        ));
+        if link_from_original_to_each_update {
+            entry_types.push((
+                &sample_entry_def,
+                Crud {
+                    update,
+                    delete: true,
+                },
+                true,
+                true,
+            ));
+        }
+    }
+
+    let mut generated_code = Vec::new();
+    let mut rendered_file_trees = Vec::new();
+    for (entry_def, crud, link_from_original_to_each_update, paginated_revisions) in entry_types {
+        generated_code.push(
+            render_entry_definition_file(entry_def, &crud)?
+                .to_token_stream()
+                .to_string(),
+        );
+        generated_code.push(
+            initial_crud_handlers(
+                integrity_zome_name,
+                entry_def,
+                &crud,
+                link_from_original_to_each_update,
+                paginated_revisions,
+            )
+            .to_token_stream()
+            .to_string(),
+        );
+
+        if let Some(coordinator_zome) = coordinator_zome {
+            let scaffolded = scaffold_entry_type_templates(
+                app_file_tree.clone(),
+                template_file_tree,
+                dna_name,
+                dna_name,
+                coordinator_zome,
+                entry_def,
+                "",
+                &crud,
+                link_from_original_to_each_update,
+                paginated_revisions,
+                false,
+                false,
+                Interactivity::NonInteractive,
+            )?;
+            rendered_file_trees.push(scaffolded.file_tree);
+        }
+    }
+
+    if let Some(coordinator_zome) = coordinator_zome {
+        let collection_types = [
+            CollectionType::Global,
+            CollectionType::ByAuthor,
+            CollectionType::ByTime {
+                granularity: Default::default(),
+            },
+        ];
+        for collection_type in &collection_types {
+            for paginated in [false, true] {
+                let scaffolded = scaffold_collection_templates(
+                    app_file_tree.clone(),
+                    template_file_tree,
+                    dna_name,
+                    dna_name,
+                    coordinator_zome,
+                    collection_type,
+                    OTHER_SAMPLE_NAME,
+                    &EntryTypeReference {
+                        entry_type: SAMPLE_ENTRY_TYPE_NAME.to_string(),
+                        reference_entry_hash: entry_def.reference_entry_hash,
+                    },
+                    true,
+                    paginated,
+                    false,
+                    false,
+                    Interactivity::NonInteractive,
+                )?;
+                rendered_file_trees.push(scaffolded.file_tree);
+            }
+        }
+    }
+
+    for file_tree in &rendered_file_trees {
+        for (path, content) in flatten_file_tree(file_tree) {
+            if file_content(app_file_tree, &path).ok() != content {
+                generated_code.push(path.to_string_lossy().to_string());
+                generated_code.extend(content);
+            }
+        }
+    }
+
+    let sample_plural = pluralizer::pluralize(SAMPLE_ENTRY_TYPE_NAME, 2, false);
+    let other_sample_plural = pluralizer::pluralize(OTHER_SAMPLE_NAME, 2, false);
+    let snake_name = entry_def.name.to_case(Case::Snake);
+    let snake_plural = pluralizer::pluralize(&snake_name, 2, false);
+    let identifier_regex =
+        Regex::new(r"[A-Za-z0-9_]+(?:-[A-Za-z0-9_]+)*").expect("Failed to create regex");
+
+    let identifiers: BTreeSet<String> = generated_code
+        .iter()
+        .flat_map(|code| identifier_regex.find_iter(code))
+        .map(|identifier| identifier.as_str().to_case(Case::Snake))
+        .filter(|identifier| {
+            let words: Vec<&str> = identifier.split('_').collect();
+            words
+                .iter()
+                .any(|w| *w == SAMPLE_ENTRY_TYPE_NAME || *w == sample_plural)
+                && !words
+                    .iter()
+                    .any(|w| *w == OTHER_SAMPLE_NAME || *w == other_sample_plural)
+        })
+        .map(|identifier| {
+            identifier
+                .split('_')
+                .map(|word| match word {
+                    w if w == SAMPLE_ENTRY_TYPE_NAME => snake_name.as_str(),
+                    w if w == sample_plural => snake_plural.as_str(),
+                    w => w,
+                })
+                .join("_")
+        })
+        .collect();
+
+    Ok(identifiers.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scaffold::web_app::template_type::TemplateType;
+    use build_fs_tree::file;
+
+    #[test]
+    fn removes_the_typescript_types_of_the_entry_type() {
+        let content = r#"/* dprint-ignore-start */
+export type EntryTypes =
+ | ({ type: 'Comment'; } & Comment)
+ | ({  type: 'Post'; } & Post);
+/* dprint-ignore-end */
+
+export type Status = "Draft" | "Published";
+
+export interface Post {
+  title: string;
+  status: Status;
+}
+
+export interface Comment {
+  comment: string;
+  post_hash: ActionHash;
+}
+"#;
+
+        let content = remove_ts_entry_type(content, "post").unwrap();
+        assert_eq!(
+            content,
+            r#"/* dprint-ignore-start */
+export type EntryTypes =
+ | ({ type: 'Comment'; } & Comment);
+/* dprint-ignore-end */
+
+export interface Comment {
+  comment: string;
+  post_hash: ActionHash;
+}
+"#
+        );
+
+        assert_eq!(
+            remove_ts_entry_type(&content, "comment").unwrap(),
+            "export type EntryTypes = {};\n"
+        );
+    }
+
+    #[test]
+    fn removes_the_typescript_types_of_the_entry_type_from_an_edited_file() {
+        let content = r#"// Types of the posts zome
+export type EntryTypes =
+ | ({ type: 'Post'; } & Post)
+ // Comments are only shown to members
+ | ({ type: 'Comment'; } & Comment);
+
+export interface Post {
+  title: string;
+}
+
+export interface Comment {
+  comment: string;
+}
+"#;
+
+        let content = remove_ts_entry_type(content, "comment").unwrap();
+        assert_eq!(
+            content,
+            r#"// Types of the posts zome
+export type EntryTypes =
+ | ({ type: 'Post'; } & Post);
+ // Comments are only shown to members
+
+export interface Post {
+  title: string;
+}
+"#
+        );
+
+        assert_eq!(
+            remove_ts_entry_type(&content, "post").unwrap(),
+            "// Types of the posts zome\nexport type EntryTypes = {};\n // Comments are only shown to members\n"
+        );
+    }
+
+    #[test]
+    fn only_requires_editing_the_code_generated_for_the_entry_type() {
+        let common = r#"export async function samplePost(cell: CallableCell, partialPost = {}) {
+  return {
+    ...{
+      title: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
+    },
+    ...partialPost,
+  };
+}
+
+export async function sampleComment(cell: CallableCell, partialComment = {}) {
+  return {
+    ...{
+      title: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
+    },
+    ...partialComment,
+  };
+}
+"#;
+        let file_tree: FileTree = dir! {
+            "tests" => dir! {
+                "common.ts" => build_fs_tree::file!(common)
+            }
+        };
+        let reference = |path: &str, line_number: usize| LeftoverReference {
+            path: PathBuf::from(path),
+            line_number,
+            line: String::new(),
+        };
+
+        for (path, line_number, generated) in [
+            ("tests/common.ts", 4, true),
+            ("tests/common.ts", 13, false),
+            ("tests/post.test.ts", 10, true),
+            ("ui/CreatePost.svelte", 10, true),
+            ("ui/edit-post.ts", 10, true),
+            ("ui/CreateComment.svelte", 10, false),
+            ("coordinator/src/post.rs", 10, true),
+            ("coordinator/src/comment.rs", 10, false),
+        ] {
+            assert_eq!(
+                is_generated_for_entry_type(&file_tree, &reference(path, line_number), "post"),
+                generated,
+                "{path}:{line_number}"
+            );
+        }
+    }
+
+    #[test]
+    fn identifiers_generated_for_an_entry_type_are_found_in_its_code_and_templates() {
+        let coordinator_zome = ZomeManifest {
+            name: "events".into(),
+            hash: None,
+            location: mr_bundle::Location::Bundled(PathBuf::from(
+                "../../../target/wasm32-unknown-unknown/release/events.wasm",
+            )),
+            dependencies: None,
+            dylib: None,
+        };
+        let app_file_tree = dir! {
+            "ui" => dir! {
+                "src" => dir! {
+                    "calendar" => dir! {
+                        "events" => dir! {
+                            "types.ts" => file!("export type EntryTypes = {};\n"),
+                        },
+                    },
+                },
+            },
+        };
+        let identifiers = entry_type_identifiers(
+            &app_file_tree,
+            &TemplateType::Svelte.file_tree().unwrap(),
+            "calendar",
+            "events_integrity",
+            Some(&coordinator_zome),
+            &EntryDefinition {
+                name: "event".to_string(),
+                fields: vec![],
+                reference_entry_hash: false,
+                visibility: Visibility::Public,
+                permission: None,
+            },
+            true,
+        )
+        .unwrap();
+
+        for identifier in [
+            "create_event",
+            "get_all_revisions_for_event",
+            "validate_update_event",
+            "event_detail",
+            "sample_event",
+            "fetch_events",
+            "links_to_events",
+            "deleted_links_to_events",
+        ] {
+            assert!(
+                identifiers.iter().any(|i| i == identifier),
+                "{identifier} should be generated for the entry type"
+            );
+        }
+        // Only the tests of private entry types read them from the source chain of Alice
+        assert!(!identifiers.iter().any(|i| i == "alice_events"));
+        assert!(!identifiers
+            .iter()
+            .any(|i| i == "create_event_dispatcher" || i.contains(SAMPLE_ENTRY_TYPE_NAME)));
+    }
+}
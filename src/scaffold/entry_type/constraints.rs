@@ -0,0 +1,395 @@
+//! Constraints on the values of the fields of an entry type, eg. "title:String{min=1,max=140}",
+//! which are checked in the validation of the entry and in the forms of the UI
+
+use convert_case::{Case, Casing};
+use regex::Regex;
+use regex_syntax::hir::{Class, Hir, HirKind};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::{ScaffoldError, ScaffoldResult};
+
+use super::definitions::FieldType;
+
+const LOREM_IPSUM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+
+#[derive(Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConstraintKind {
+    /// The minimum number of characters of a string, or the minimum value of a number
+    Min,
+    /// The maximum number of characters of a string, or the maximum value of a number
+    Max,
+    /// A regular expression that strings must match
+    Regex,
+}
+
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct ConstraintRule {
+    pub kind: ConstraintKind,
+    pub value: String,
+    /// The reason given when the value of the field breaks this rule
+    pub message: String,
+}
+
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct FieldConstraints {
+    pub rules: Vec<ConstraintRule>,
+    /// A value that satisfies all the rules, as JSON
+    pub sample: String,
+    /// A value that breaks one of the rules, as JSON
+    pub invalid_sample: Option<String>,
+}
+
+impl FieldConstraints {
+    /// Whether checking the constraints needs the regex crate
+    pub fn has_regex(&self) -> bool {
+        self.rules.iter().any(|r| r.kind == ConstraintKind::Regex)
+    }
+
+    /// Parses the constraints of a field, eg. "min=1,max=140", "range=1..=5" or "regex=^[a-z-]+$"
+    pub fn parse(
+        constraints_str: &str,
+        field_name: &str,
+        field_type: &FieldType,
+    ) -> ScaffoldResult<FieldConstraints> {
+        check_can_have_constraints(field_name, field_type)?;
+        let is_string = matches!(field_type, FieldType::String);
+
+        let mut values: Vec<(ConstraintKind, String)> = vec![];
+        for constraint in split_constraints(constraints_str) {
+            let (key, value) = constraint.split_once('=').ok_or_else(|| {
+                ScaffoldError::InvalidArguments(format!(
+                    "Invalid constraint \"{constraint}\" of field {field_name}, expected <CONSTRAINT>=<VALUE>"
+                ))
+            })?;
+            match key.trim() {
+                "min" => values.push((ConstraintKind::Min, value.trim().to_string())),
+                "max" => values.push((ConstraintKind::Max, value.trim().to_string())),
+                "range" if !is_string => {
+                    let (min, max) = value.trim().split_once("..=").ok_or_else(|| {
+                        ScaffoldError::InvalidArguments(format!(
+                            "Invalid range \"{value}\" of field {field_name}, expected an inclusive range like 1..=5"
+                        ))
+                    })?;
+                    values.push((ConstraintKind::Min, min.to_string()));
+                    values.push((ConstraintKind::Max, max.to_string()));
+                }
+                "regex" if is_string => values.push((ConstraintKind::Regex, value.to_string())),
+                _ => {
+                    return Err(ScaffoldError::InvalidArguments(format!(
+                        "Invalid constraint \"{key}\" for {field_type} field {field_name}: use {}",
+                        if is_string {
+                            "min, max or regex"
+                        } else {
+                            "min, max or range"
+                        }
+                    )))
+                }
+            }
+        }
+
+        FieldConstraints::new(values, field_name, field_type)
+    }
+
+    /// Builds the constraints of a field from the values of their rules, checking that they can be satisfied
+    pub fn new(
+        values: Vec<(ConstraintKind, String)>,
+        field_name: &str,
+        field_type: &FieldType,
+    ) -> ScaffoldResult<FieldConstraints> {
+        check_can_have_constraints(field_name, field_type)?;
+        let is_string = matches!(field_type, FieldType::String);
+
+        if values.is_empty() {
+            return Err(ScaffoldError::InvalidArguments(format!(
+                "Field {field_name} has empty constraints"
+            )));
+        }
+
+        let label = field_name.to_case(Case::Title);
+        let rules = values
+            .into_iter()
+            .map(|(kind, value)| {
+                if kind == ConstraintKind::Regex {
+                    Regex::new(&value).map_err(|e| {
+                        ScaffoldError::InvalidArguments(format!(
+                            "Invalid regex of field {field_name}: {e}"
+                        ))
+                    })?;
+                } else if !is_valid_number(&value, field_type) {
+                    return Err(ScaffoldError::InvalidArguments(format!(
+                        "Invalid {} \"{value}\" of field {field_name}: expected {}",
+                        if kind == ConstraintKind::Min {
+                            "min"
+                        } else {
+                            "max"
+                        },
+                        if is_string {
+                            "a number of characters".to_string()
+                        } else {
+                            format!("a {field_type}")
+                        }
+                    )));
+                }
+
+                let message = match (kind, is_string) {
+                    (ConstraintKind::Min, true) => {
+                        format!("{label} must be at least {value} characters long")
+                    }
+                    (ConstraintKind::Max, true) => {
+                        format!("{label} must be at most {value} characters long")
+                    }
+                    (ConstraintKind::Min, false) => format!("{label} must be at least {value}"),
+                    (ConstraintKind::Max, false) => format!("{label} must be at most {value}"),
+                    (ConstraintKind::Regex, _) => format!("{label} must match {value}"),
+                };
+                Ok(ConstraintRule {
+                    kind,
+                    value,
+                    message,
+                })
+            })
+            .collect::<ScaffoldResult<Vec<_>>>()?;
+
+        let sample = valid_sample(&rules, field_type)
+            .filter(|sample| satisfies(&rules, sample))
+            .ok_or_else(|| {
+                ScaffoldError::InvalidArguments(format!(
+                    "No value of field {field_name} can satisfy all its constraints"
+                ))
+            })?;
+        let invalid_sample = rules
+            .iter()
+            .find_map(|rule| breaking_sample(rule, field_type))
+            .map(|v| v.to_string());
+
+        Ok(FieldConstraints {
+            rules,
+            sample: sample.to_string(),
+            invalid_sample,
+        })
+    }
+}
+
+fn check_can_have_constraints(field_name: &str, field_type: &FieldType) -> ScaffoldResult<()> {
+    if !matches!(field_type, FieldType::String) && !is_number(field_type) {
+        return Err(ScaffoldError::InvalidArguments(format!(
+            "Field {field_name} can't have constraints: only String, u8, u32, i32 and f32 fields can"
+        )));
+    }
+    Ok(())
+}
+
+fn is_number(field_type: &FieldType) -> bool {
+    matches!(
+        field_type,
+        FieldType::U8 | FieldType::U32 | FieldType::I32 | FieldType::F32
+    )
+}
+
+fn is_valid_number(value: &str, field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::String => value.parse::<usize>().is_ok(),
+        FieldType::U8 => value.parse::<u8>().is_ok(),
+        FieldType::U32 => value.parse::<u32>().is_ok(),
+        FieldType::I32 => value.parse::<i32>().is_ok(),
+        FieldType::F32 => value.parse::<f32>().is_ok_and(|v| v.is_finite()),
+        _ => false,
+    }
+}
+
+/// Splits the constraints on the commas that are not part of the repetitions of a regex, like "{1,3}"
+fn split_constraints(constraints_str: &str) -> Vec<&str> {
+    let mut constraints = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in constraints_str.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                constraints.push(&constraints_str[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    constraints.push(&constraints_str[start..]);
+    constraints
+        .into_iter()
+        .filter(|c| !c.trim().is_empty())
+        .collect()
+}
+
+fn rule_number(rules: &[ConstraintRule], kind: ConstraintKind) -> Option<f64> {
+    rules
+        .iter()
+        .filter(|r| r.kind == kind)
+        .filter_map(|r| r.value.parse::<f64>().ok())
+        .reduce(|a, b| match kind {
+            ConstraintKind::Min => a.max(b),
+            _ => a.min(b),
+        })
+}
+
+fn number_value(value: f64, field_type: &FieldType) -> Value {
+    match field_type {
+        FieldType::F32 => Value::from(value),
+        _ => Value::from(value as i64),
+    }
+}
+
+/// Whether the given value satisfies all the rules
+fn satisfies(rules: &[ConstraintRule], value: &Value) -> bool {
+    rules.iter().all(|rule| match (rule.kind, value) {
+        (ConstraintKind::Regex, Value::String(s)) => {
+            Regex::new(&rule.value).is_ok_and(|regex| regex.is_match(s))
+        }
+        (kind, Value::String(s)) => {
+            let length = s.chars().count() as f64;
+            let Ok(bound) = rule.value.parse::<f64>() else {
+                return false;
+            };
+            match kind {
+                ConstraintKind::Min => length >= bound,
+                _ => length <= bound,
+            }
+        }
+        (kind, Value::Number(n)) => {
+            let (Some(n), Ok(bound)) = (n.as_f64(), rule.value.parse::<f64>()) else {
+                return false;
+            };
+            match kind {
+                ConstraintKind::Min => n >= bound,
+                _ => n <= bound,
+            }
+        }
+        _ => false,
+    })
+}
+
+/// Builds a value that should satisfy all the rules
+fn valid_sample(rules: &[ConstraintRule], field_type: &FieldType) -> Option<Value> {
+    let min = rule_number(rules, ConstraintKind::Min);
+    let max = rule_number(rules, ConstraintKind::Max);
+
+    if is_number(field_type) {
+        let value = match (min, max) {
+            (Some(min), _) => min,
+            (None, Some(max)) => max.min(0.0),
+            (None, None) => 0.0,
+        };
+        return Some(number_value(value, field_type));
+    }
+
+    let min_length = min.unwrap_or(1.0) as usize;
+    let mut sample = match rules.iter().find(|r| r.kind == ConstraintKind::Regex) {
+        Some(rule) => sample_matching(&regex_syntax::parse(&rule.value).ok()?, min_length),
+        None => LOREM_IPSUM.repeat(min_length / LOREM_IPSUM.len() + 1),
+    };
+    if let Some(max) = max {
+        sample = sample.chars().take(max as usize).collect();
+    }
+    Some(Value::from(sample))
+}
+
+/// Builds a string matching the given regex, repeating its repetitions up to the given number of times
+fn sample_matching(hir: &Hir, repetitions: usize) -> String {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => String::new(),
+        HirKind::Literal(literal) => String::from_utf8_lossy(&literal.0).to_string(),
+        HirKind::Class(Class::Unicode(class)) => ['a', 'A', '0', ' ']
+            .into_iter()
+            .find(|c| {
+                class
+                    .ranges()
+                    .iter()
+                    .any(|r| r.start() <= *c && *c <= r.end())
+            })
+            .or_else(|| class.ranges().first().map(|r| r.start()))
+            .map(String::from)
+            .unwrap_or_default(),
+        HirKind::Class(Class::Bytes(class)) => class
+            .ranges()
+            .first()
+            .map(|r| char::from(r.start()).to_string())
+            .unwrap_or_default(),
+        HirKind::Repetition(repetition) => {
+            let count = (repetition.min as usize)
+                .max(repetitions)
+                .min(repetition.max.map_or(usize::MAX, |max| max as usize));
+            sample_matching(&repetition.sub, repetitions).repeat(count)
+        }
+        HirKind::Capture(capture) => sample_matching(&capture.sub, repetitions),
+        HirKind::Concat(hirs) => hirs
+            .iter()
+            .map(|hir| sample_matching(hir, repetitions))
+            .collect(),
+        HirKind::Alternation(hirs) => hirs
+            .first()
+            .map(|hir| sample_matching(hir, repetitions))
+            .unwrap_or_default(),
+    }
+}
+
+/// Builds a value that breaks the given rule, if there is any
+fn breaking_sample(rule: &ConstraintRule, field_type: &FieldType) -> Option<Value> {
+    let value = match (rule.kind, field_type) {
+        (ConstraintKind::Regex, _) => ["", "!", " ", "a", "0"]
+            .into_iter()
+            .map(Value::from)
+            .find(|v| !satisfies(std::slice::from_ref(rule), v))?,
+        (ConstraintKind::Min, FieldType::String) => {
+            let min = rule.value.parse::<usize>().ok()?;
+            Value::from("a".repeat(min.checked_sub(1)?))
+        }
+        (ConstraintKind::Max, FieldType::String) => {
+            let max = rule.value.parse::<usize>().ok()?;
+            Value::from("a".repeat(max + 1))
+        }
+        (kind, _) => {
+            let bound = rule.value.parse::<f64>().ok()?;
+            let value = match kind {
+                ConstraintKind::Min => bound - 1.0,
+                _ => bound + 1.0,
+            };
+            if !is_valid_number(&number_value(value, field_type).to_string(), field_type) {
+                return None;
+            }
+            number_value(value, field_type)
+        }
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_samples_that_satisfy_the_constraints() {
+        let constraints =
+            FieldConstraints::parse("min=3,regex=^[a-z-]+$", "slug", &FieldType::String).unwrap();
+        assert_eq!(constraints.sample, r#""aaa""#);
+        assert_eq!(constraints.invalid_sample.as_deref(), Some(r#""aa""#));
+        assert_eq!(constraints.rules[1].message, "Slug must match ^[a-z-]+$");
+
+        let constraints = FieldConstraints::parse("range=1..=5", "rating", &FieldType::U8).unwrap();
+        assert_eq!(constraints.sample, "1");
+        assert_eq!(constraints.invalid_sample.as_deref(), Some("0"));
+
+        let constraints =
+            FieldConstraints::parse(r"regex=^\d{3}-\d{2}$", "code", &FieldType::String).unwrap();
+        assert_eq!(constraints.sample, r#""000-00""#);
+
+        assert!(FieldConstraints::parse("min=0", "rating", &FieldType::U8)
+            .unwrap()
+            .invalid_sample
+            .is_none());
+        assert!(FieldConstraints::parse("min=5,max=2", "title", &FieldType::String).is_err());
+        assert!(FieldConstraints::parse("regex=a", "rating", &FieldType::U8).is_err());
+        assert!(FieldConstraints::parse("min=-1", "rating", &FieldType::U32).is_err());
+        assert!(FieldConstraints::parse("min=1", "done", &FieldType::Bool).is_err());
+    }
+}
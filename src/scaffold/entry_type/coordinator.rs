@@ -18,7 +18,7 @@ use crate::{
 
 use super::{
     crud::Crud,
-    definitions::{Cardinality, EntryDefinition, FieldType},
+    definitions::{Cardinality, EntryDefinition, FieldType, Visibility},
     integrity::find_ending_match_expr_in_block,
 };
 
@@ -28,6 +28,7 @@ pub fn add_crud_functions_to_coordinator(
     entry_def: &EntryDefinition,
     crud: &Crud,
     link_from_original_to_each_update: bool,
+    paginated_revisions: bool,
 ) -> ScaffoldResult<ZomeFileTree> {
     let dna_manifest_path = zome_file_tree.dna_file_tree.dna_manifest_path.clone();
     let zome_manifest = zome_file_tree.zome_manifest.clone();
@@ -43,6 +44,7 @@ pub fn add_crud_functions_to_coordinator(
         entry_def,
         crud,
         link_from_original_to_each_update,
+        paginated_revisions,
     ));
 
     insert_file(
@@ -263,7 +265,10 @@ pub fn updates_link_name(entry_def_name: &str) -> String {
     format!("{}Updates", entry_def_name.to_case(Case::Pascal))
 }
 
-fn read_handler_with_linking_to_updates(entry_def: &EntryDefinition) -> TokenStream {
+fn read_handler_with_linking_to_updates(
+    entry_def: &EntryDefinition,
+    paginated_revisions: bool,
+) -> TokenStream {
     let snake_entry_def_name = entry_def.snake_case_name();
 
     let updates_link_name = format_ident!("{}", updates_link_name(&entry_def.name));
@@ -310,7 +315,10 @@ fn read_handler_with_linking_to_updates(entry_def: &EntryDefinition) -> TokenStr
     let get_all_revisions_function_name =
         format_ident!("get_all_revisions_for_{snake_entry_def_name}");
 
-    let get_all_revisions_function_name = quote! {
+    let get_all_revisions_function_name = if paginated_revisions {
+        paginated_get_all_revisions(entry_def)
+    } else {
+        quote! {
         #[hdk_extern]
         pub fn #get_all_revisions_function_name(#original_hash_param_name: ActionHash) -> ExternResult<Vec<Record>> {
             let Some(original_record) = #get_original_function_name(#original_hash_param_name.clone())? else {
@@ -336,6 +344,7 @@ fn read_handler_with_linking_to_updates(entry_def: &EntryDefinition) -> TokenStr
 
             Ok(records)
         }
+        }
     };
 
     quote! {
@@ -347,6 +356,70 @@ fn read_handler_with_linking_to_updates(entry_def: &EntryDefinition) -> TokenStr
     }
 }
 
+/// Gets at most `input.limit` revisions, oldest first, after the `input.after` cursor
+fn paginated_get_all_revisions(entry_def: &EntryDefinition) -> TokenStream {
+    let snake_entry_def_name = entry_def.snake_case_name();
+    let pascal_entry_def_name = entry_def.pascal_case_name();
+
+    let updates_link_name = format_ident!("{}", updates_link_name(&entry_def.name));
+    let original_hash_field_name = format_ident!("original_{snake_entry_def_name}_hash");
+    let get_original_function_name = format_ident!("get_original_{snake_entry_def_name}");
+    let get_all_revisions_function_name =
+        format_ident!("get_all_revisions_for_{snake_entry_def_name}");
+    let input_name = format_ident!("GetAllRevisionsFor{pascal_entry_def_name}Input");
+
+    quote! {
+        #[derive(Serialize, Deserialize, Debug)]
+        pub struct #input_name {
+            pub #original_hash_field_name: ActionHash,
+            /// The timestamp and the hash of the action of the last revision of the previous page
+            pub after: Option<(Timestamp, ActionHash)>,
+            pub limit: u32,
+        }
+
+        #[hdk_extern]
+        pub fn #get_all_revisions_function_name(input: #input_name) -> ExternResult<Vec<Record>> {
+            let Some(original_record) = #get_original_function_name(input.#original_hash_field_name.clone())? else {
+                return Ok(vec![]);
+            };
+
+            let links = get_links(
+                GetLinksInputBuilder::try_new(input.#original_hash_field_name.clone(), LinkTypes::#updates_link_name)?.build(),
+            )?;
+
+            /// Each link is created right after its update, so the links older than the cursor
+            /// are the ones of revisions of the previous pages
+            let get_input: Vec<GetInput> = links
+                .into_iter()
+                .filter(|link| !input.after.as_ref().is_some_and(|(timestamp, _)| link.timestamp < *timestamp))
+                .map(|link| Ok(GetInput::new(
+                    link.target.into_action_hash().ok_or(wasm_error!(WasmErrorInner::Guest("No action hash associated with link".to_string())))?.into(),
+                    GetOptions::default(),
+                )))
+                .collect::<ExternResult<Vec<GetInput>>>()?;
+
+            // load the records for all the links
+            let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+
+            /// Revisions created at the same time are sorted by their hash, so none of them is skipped at the boundary of a page
+            let mut revisions: Vec<Record> = std::iter::once(original_record)
+                .chain(records.into_iter().flatten())
+                .filter(|record| {
+                    !input.after.as_ref().is_some_and(|(timestamp, hash)| {
+                        (record.action().timestamp(), record.action_address()) <= (*timestamp, hash)
+                    })
+                })
+                .collect();
+            revisions.sort_by(|a, b| {
+                (a.action().timestamp(), a.action_address()).cmp(&(b.action().timestamp(), b.action_address()))
+            });
+            revisions.truncate(input.limit as usize);
+
+            Ok(revisions)
+        }
+    }
+}
+
 fn create_link_for_cardinality(
     entry_def: &EntryDefinition,
     field_name: &str,
@@ -667,21 +740,259 @@ fn delete_handler(entry_def: &EntryDefinition) -> TokenStream {
     }
 }
 
-fn initial_crud_handlers(
+/// CRUD functions for a private entry type, which read the entries from the source chain
+/// of the agent with `query()` since they are not published to the DHT
+fn private_crud_handlers(entry_def: &EntryDefinition, crud: &Crud) -> TokenStream {
+    let snake_entry_def_name = entry_def.snake_case_name();
+    let pascal_entry_def_name = entry_def.pascal_case_name();
+    let snake_plural_entry_def_name = pluralizer::pluralize(&snake_entry_def_name, 2, false);
+
+    let entry_def_struct = format_ident!("{pascal_entry_def_name}");
+    let entry_def_variable = format_ident!("{snake_entry_def_name}");
+    let entry_hash_variable_name = format_ident!("{snake_entry_def_name}_hash");
+    let original_hash_param_name = format_ident!("original_{snake_entry_def_name}_hash");
+
+    let create_function_name = format_ident!("create_{snake_entry_def_name}");
+    let query_all_function_name = format_ident!("query_{snake_plural_entry_def_name}");
+    let query_function_name = format_ident!("query_{snake_entry_def_name}");
+    let get_my_function_name = format_ident!("get_my_{snake_plural_entry_def_name}");
+    let created_error_message = format!("Could not find the newly created {pascal_entry_def_name}");
+
+    let (deleted_hashes, not_deleted_filter) = if crud.delete {
+        (
+            quote! {
+                let deleted_hashes: Vec<ActionHash> = query(ChainQueryFilter::new().action_type(ActionType::Delete))?
+                    .into_iter()
+                    .filter_map(|record| match record.action() {
+                        Action::Delete(delete) => Some(delete.deletes_address.clone()),
+                        _ => None,
+                    })
+                    .collect();
+            },
+            quote! {
+                .filter(|record| !deleted_hashes.contains(record.action_address()))
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    let read_handlers = if !crud.update {
+        let get_function_name = format_ident!("get_{snake_entry_def_name}");
+        if entry_def.reference_entry_hash {
+            quote! {
+                #[hdk_extern]
+                pub fn #get_function_name(#entry_hash_variable_name: EntryHash) -> ExternResult<Option<Record>> {
+                    Ok(#query_all_function_name()?
+                        .into_iter()
+                        .find(|record| record.action().entry_hash() == Some(&#entry_hash_variable_name)))
+                }
+            }
+        } else {
+            quote! {
+                #[hdk_extern]
+                pub fn #get_function_name(#entry_hash_variable_name: ActionHash) -> ExternResult<Option<Record>> {
+                    #query_function_name(#entry_hash_variable_name)
+                }
+            }
+        }
+    } else {
+        let get_original_function_name = format_ident!("get_original_{snake_entry_def_name}");
+        let get_latest_function_name = format_ident!("get_latest_{snake_entry_def_name}");
+        let get_all_revisions_function_name =
+            format_ident!("get_all_revisions_for_{snake_entry_def_name}");
+        let latest_hash_variable_name = format_ident!("latest_{snake_entry_def_name}_hash");
+
+        let update_input_struct = format_ident!("Update{pascal_entry_def_name}Input");
+        let previous_hash_field = format_ident!("previous_{snake_entry_def_name}_hash");
+        let updated_entry_field = format_ident!("updated_{snake_entry_def_name}");
+        let updated_hash_variable_name = format_ident!("updated_{snake_entry_def_name}_hash");
+        let update_function_name = format_ident!("update_{snake_entry_def_name}");
+        let updated_error_message =
+            format!("Could not find the newly updated {pascal_entry_def_name}");
+
+        quote! {
+            #[hdk_extern]
+            pub fn #get_original_function_name(#original_hash_param_name: ActionHash) -> ExternResult<Option<Record>> {
+                #query_function_name(#original_hash_param_name)
+            }
+
+            #[hdk_extern]
+            pub fn #get_latest_function_name(#original_hash_param_name: ActionHash) -> ExternResult<Option<Record>> {
+                let records = #query_all_function_name()?;
+
+                let mut #latest_hash_variable_name = #original_hash_param_name;
+                while let Some(update) = records.iter().rev().find(|record| match record.action() {
+                    Action::Update(update) => update.original_action_address == #latest_hash_variable_name,
+                    _ => false,
+                }) {
+                    #latest_hash_variable_name = update.action_address().clone();
+                }
+
+                Ok(records.into_iter().find(|record| record.action_address() == &#latest_hash_variable_name))
+            }
+
+            #[hdk_extern]
+            pub fn #get_all_revisions_function_name(#original_hash_param_name: ActionHash) -> ExternResult<Vec<Record>> {
+                let records = #query_all_function_name()?;
+
+                let Some(original_record) = records.iter().find(|record| record.action_address() == &#original_hash_param_name) else {
+                    return Ok(vec![]);
+                };
+
+                // The source chain is ordered, so each update comes after the revision it updates
+                let mut revisions = vec![original_record.clone()];
+                for record in &records {
+                    if let Action::Update(update) = record.action() {
+                        if revisions.iter().any(|revision| revision.action_address() == &update.original_action_address) {
+                            revisions.push(record.clone());
+                        }
+                    }
+                }
+
+                Ok(revisions)
+            }
+
+            #[derive(Serialize, Deserialize, Debug)]
+            pub struct #update_input_struct {
+                pub #previous_hash_field: ActionHash,
+                pub #updated_entry_field: #entry_def_struct
+            }
+
+            #[hdk_extern]
+            pub fn #update_function_name(input: #update_input_struct) -> ExternResult<Record> {
+                let #updated_hash_variable_name = update_entry(
+                    input.#previous_hash_field, &input.#updated_entry_field
+                )?;
+
+                let record = #query_function_name(#updated_hash_variable_name)?
+                    .ok_or(wasm_error!(WasmErrorInner::Guest(#updated_error_message.to_string())))?;
+
+                Ok(record)
+            }
+        }
+    };
+
+    let delete_handlers = if crud.delete {
+        let delete_function_name = format_ident!("delete_{snake_entry_def_name}");
+        let get_all_deletes_function_name =
+            format_ident!("get_all_deletes_for_{snake_entry_def_name}");
+        let get_oldest_delete_function_name =
+            format_ident!("get_oldest_delete_for_{snake_entry_def_name}");
+
+        quote! {
+            #[hdk_extern]
+            pub fn #delete_function_name(#original_hash_param_name: ActionHash) -> ExternResult<ActionHash> {
+                delete_entry(#original_hash_param_name)
+            }
+
+            #[hdk_extern]
+            pub fn #get_all_deletes_function_name(
+                #original_hash_param_name: ActionHash,
+            ) -> ExternResult<Option<Vec<SignedActionHashed>>> {
+                if #query_function_name(#original_hash_param_name.clone())?.is_none() {
+                    return Ok(None);
+                }
+                let deletes = query(ChainQueryFilter::new().action_type(ActionType::Delete))?
+                    .into_iter()
+                    .filter(|record| match record.action() {
+                        Action::Delete(delete) => delete.deletes_address == #original_hash_param_name,
+                        _ => false,
+                    })
+                    .map(|record| record.signed_action)
+                    .collect();
+                Ok(Some(deletes))
+            }
+
+            #[hdk_extern]
+            pub fn #get_oldest_delete_function_name(
+                #original_hash_param_name: ActionHash,
+            ) -> ExternResult<Option<SignedActionHashed>> {
+                let Some(mut deletes) = #get_all_deletes_function_name(#original_hash_param_name)? else {
+                    return Ok(None);
+                };
+                deletes.sort_by(|delete_a, delete_b| delete_a.action().timestamp().cmp(&delete_b.action().timestamp()));
+                Ok(deletes.first().cloned())
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #[hdk_extern]
+        pub fn #create_function_name(#entry_def_variable: #entry_def_struct) -> ExternResult<Record> {
+            let #entry_hash_variable_name = create_entry(
+                &EntryTypes::#entry_def_struct(#entry_def_variable.clone())
+            )?;
+
+            let record = #query_function_name(#entry_hash_variable_name)?
+                .ok_or(wasm_error!(WasmErrorInner::Guest(#created_error_message.to_string())))?;
+            Ok(record)
+        }
+
+        /// The records that create or update the entries of this type in the source chain of this agent
+        fn #query_all_function_name() -> ExternResult<Vec<Record>> {
+            let filter = ChainQueryFilter::new()
+                .entry_type(UnitEntryTypes::#entry_def_struct.try_into()?)
+                .include_entries(true);
+            query(filter)
+        }
+
+        fn #query_function_name(#entry_hash_variable_name: ActionHash) -> ExternResult<Option<Record>> {
+            Ok(#query_all_function_name()?
+                .into_iter()
+                .find(|record| record.action_address() == &#entry_hash_variable_name))
+        }
+
+        /// The entries of this type created by this agent, which other agents can't read
+        #[hdk_extern]
+        pub fn #get_my_function_name() -> ExternResult<Vec<Record>> {
+            #deleted_hashes
+            let records = #query_all_function_name()?
+                .into_iter()
+                .filter(|record| matches!(record.action(), Action::Create(_)))
+                #not_deleted_filter
+                .collect();
+            Ok(records)
+        }
+
+        #read_handlers
+
+        #delete_handlers
+    }
+}
+
+/// The zome functions to create, read, update and delete the entries of a new entry type
+pub fn initial_crud_handlers(
     integrity_zome_name: &str,
     entry_def: &EntryDefinition,
     crud: &Crud,
     link_from_original_to_each_update: bool,
+    paginated_revisions: bool,
 ) -> syn::File {
     let integrity_zome_name = format_ident!("{integrity_zome_name}");
 
+    if entry_def.visibility == Visibility::Private {
+        let private_crud_handlers = private_crud_handlers(entry_def, crud);
+        return syn::parse_quote! {
+            use hdk::prelude::*;
+            use #integrity_zome_name::*;
+
+            #private_crud_handlers
+        };
+    }
+
     let create_handler = create_handler(entry_def);
     let mut update_delete_and_read_handlers = Vec::new();
 
     if !crud.update {
         update_delete_and_read_handlers.push(no_update_read_handler(entry_def));
     } else if link_from_original_to_each_update {
-        update_delete_and_read_handlers.push(read_handler_with_linking_to_updates(entry_def));
+        update_delete_and_read_handlers.push(read_handler_with_linking_to_updates(
+            entry_def,
+            paginated_revisions,
+        ));
     } else {
         update_delete_and_read_handlers.push(read_handler_without_linking_to_updates(entry_def));
     }
@@ -796,3 +1107,48 @@ fn signal_action_match_arms() -> ScaffoldResult<Vec<syn::Arm>> {
         },
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+
+    use super::*;
+
+    #[test]
+    fn private_entries_are_read_from_the_source_chain() {
+        let entry_def = EntryDefinition {
+            name: "draft".to_string(),
+            fields: vec![],
+            reference_entry_hash: false,
+            visibility: Visibility::Private,
+            permission: None,
+        };
+        let file = initial_crud_handlers(
+            "posts_integrity",
+            &entry_def,
+            &Crud {
+                update: true,
+                delete: true,
+            },
+            false,
+            false,
+        );
+        let functions: Vec<String> = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Fn(f) => Some(f.sig.ident.to_string()),
+                _ => None,
+            })
+            .collect();
+        let code = file.to_token_stream().to_string().replace(' ', "");
+
+        assert!(functions.contains(&String::from("get_my_drafts")));
+        assert!(functions.contains(&String::from("get_latest_draft")));
+        assert!(code.contains("query("));
+        // Nothing about private entries is published to the DHT
+        for dht_call in ["create_link(", "get_links(", "get(", "get_details("] {
+            assert!(!code.contains(dht_call), "{dht_call}");
+        }
+    }
+}
@@ -4,7 +4,10 @@ use anyhow::anyhow;
 use dialoguer::{theme::ColorfulTheme, MultiSelect};
 use serde::Serialize;
 
-use crate::error::{ScaffoldError, ScaffoldResult};
+use crate::{
+    error::{ScaffoldError, ScaffoldResult},
+    utils::Interactivity,
+};
 
 #[derive(Debug, Default, Serialize, Clone, Copy)]
 pub struct Crud {
@@ -14,7 +17,9 @@ pub struct Crud {
 }
 
 impl Crud {
-    pub fn choose() -> ScaffoldResult<Self> {
+    pub fn choose(interactivity: Interactivity) -> ScaffoldResult<Self> {
+        interactivity.ensure_interactive("--crud")?;
+
         let selections = MultiSelect::with_theme(&ColorfulTheme::default())
             .with_prompt("Which CRUD functions should be scaffolded (SPACE to select/unselect, ENTER to continue)?")
             .item_checked("Update", true)
@@ -6,7 +6,7 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use regex::Regex;
 use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
-use std::str::FromStr;
+use std::{collections::BTreeMap, str::FromStr};
 
 use crate::{
     error::{ScaffoldError, ScaffoldResult},
@@ -14,6 +14,8 @@ use crate::{
     utils::check_case,
 };
 
+use super::constraints::FieldConstraints;
+
 #[derive(Deserialize, Debug, Clone, Serialize, Eq, PartialEq)]
 #[serde(tag = "type")]
 pub enum FieldType {
@@ -37,6 +39,18 @@ pub enum FieldType {
     Enum {
         label: String,
         variants: Vec<String>,
+        /// The fields of the variants that carry data, by the name of the variant
+        #[serde(
+            default,
+            skip_deserializing,
+            skip_serializing_if = "BTreeMap::is_empty"
+        )]
+        variant_fields: BTreeMap<String, VariantFields>,
+    },
+    #[serde(skip_deserializing)]
+    Struct {
+        label: String,
+        fields: Vec<FieldDefinition>,
     },
 }
 
@@ -74,6 +88,7 @@ impl std::fmt::Display for FieldType {
             FieldType::ExternalHash => "ExternalHash",
             FieldType::AgentPubKey => "AgentPubKey",
             FieldType::Enum { .. } => "Enum",
+            FieldType::Struct { .. } => "Struct",
         };
         write!(f, "{str}")
     }
@@ -97,26 +112,40 @@ impl FieldType {
             FieldType::Enum {
                 label: String::new(),
                 variants: Vec::new(),
+                variant_fields: BTreeMap::new(),
+            },
+            FieldType::Struct {
+                label: String::new(),
+                fields: Vec::new(),
             },
         ]
     }
 
     pub fn parse_enum(fields_str: &str) -> ScaffoldResult<FieldType> {
-        let mut str_path = fields_str.split(':');
+        let mut str_path = split_top_level(fields_str, ':').into_iter();
 
-        let variants = str_path
+        let variants_str = str_path
             .next_back()
             .context(format!("Enum variants missing from: {}", fields_str))?;
-        let variants = variants
-            .split('.')
-            .map(|v| v.to_case(Case::Pascal))
-            .collect::<Vec<_>>();
+        let mut variants = Vec::new();
+        let mut variant_fields = BTreeMap::new();
+        for variant_str in split_top_level(variants_str, '.') {
+            let (variant, fields) = parse_variant(variant_str)?;
+            if let Some(fields) = fields {
+                variant_fields.insert(variant.clone(), fields);
+            }
+            variants.push(variant);
+        }
         let label = str_path
             .next_back()
             .context(format!("Enum label missing from: {}", fields_str))?
             .to_string();
 
-        Ok(FieldType::Enum { label, variants })
+        Ok(FieldType::Enum {
+            label,
+            variants,
+            variant_fields,
+        })
     }
 
     pub fn rust_type(&self) -> TokenStream {
@@ -135,7 +164,7 @@ impl FieldType {
             EntryHash => quote!(EntryHash),
             ExternalHash => quote!(ExternalHash),
             AgentPubKey => quote!(AgentPubKey),
-            Enum { label, .. } => {
+            Enum { label, .. } | Struct { label, .. } => {
                 let ident = format_ident!("{}", label);
                 quote!(#ident)
             }
@@ -158,40 +187,152 @@ impl FieldType {
             EntryHash => "EntryHash",
             DnaHash => "DnaHash",
             ExternalHash => "ExternalHash",
-            Enum { label, .. } => label,
+            Enum { label, .. } | Struct { label, .. } => label,
         }
     }
 
-    // Define a non-primitive rust type for this widget
+    /// Define a non-primitive rust type for this widget, preceded by the types of its fields
     pub fn rust_type_definition(&self) -> Option<TokenStream> {
         match self {
-            FieldType::Enum { label, variants } => {
-                let variants_expressions: Vec<syn::Expr> = variants
+            FieldType::Enum {
+                label,
+                variants,
+                variant_fields,
+            } => {
+                let nested_definitions = variants
                     .iter()
-                    .cloned()
-                    .map(|variant| {
-                        let e: syn::Expr = syn::parse_str(variant.to_case(Case::Pascal).as_str())
-                            .expect("Unable to parse");
-                        e
-                    })
-                    .collect();
+                    .filter_map(|variant| variant_fields.get(variant))
+                    .flat_map(|v| &v.fields)
+                    .filter_map(|f| f.field_type.rust_type_definition());
+                let variants_expressions = variants.iter().map(|variant| {
+                    let ident = format_ident!("{}", variant.to_case(Case::Pascal));
+                    match variant_fields.get(variant) {
+                        None => quote!(#ident),
+                        Some(VariantFields {
+                            fields,
+                            newtype: true,
+                        }) => {
+                            let field_types = fields.iter().map(|f| f.rust_type());
+                            quote!(#ident(#(#field_types),*))
+                        }
+                        Some(VariantFields {
+                            fields,
+                            newtype: false,
+                        }) => {
+                            let field_names = fields
+                                .iter()
+                                .map(|f| format_ident!("{}", f.field_name.to_case(Case::Snake)));
+                            let field_types = fields.iter().map(|f| f.rust_type());
+                            quote!(#ident { #(#field_names: #field_types),* })
+                        }
+                    }
+                });
+                // Variants that carry data can't be internally tagged if their data is not a map
+                let serde_attribute = match variant_fields.is_empty() {
+                    true => quote!(#[serde(tag = "type")]),
+                    false => quote!(#[serde(tag = "type", content = "content")]),
+                };
 
                 let label_ident = format_ident!("{}", label);
                 let enum_definition = quote! {
+                    #(#nested_definitions)*
                     #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-                    #[serde(tag = "type")]
+                    #serde_attribute
                     pub enum #label_ident {
                       #(#variants_expressions),*
                     }
                 };
                 Some(enum_definition)
             }
+            FieldType::Struct { label, fields } => {
+                let nested_definitions = fields
+                    .iter()
+                    .filter_map(|f| f.field_type.rust_type_definition());
+                let field_names = fields
+                    .iter()
+                    .map(|f| format_ident!("{}", f.field_name.to_case(Case::Snake)));
+                let field_types = fields.iter().map(|f| f.rust_type());
+
+                let label_ident = format_ident!("{}", label);
+                let struct_definition = quote! {
+                    #(#nested_definitions)*
+                    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+                    pub struct #label_ident {
+                      #(pub #field_names: #field_types),*
+                    }
+                };
+                Some(struct_definition)
+            }
+            _ => None,
+        }
+    }
+
+    /// Define a non-primitive typescript type for this widget
+    pub fn ts_type_definition(&self) -> Option<String> {
+        match self {
+            FieldType::Enum {
+                label,
+                variants,
+                variant_fields,
+            } => {
+                let mut definitions: Vec<String> = variants
+                    .iter()
+                    .filter_map(|variant| variant_fields.get(variant))
+                    .flat_map(|v| &v.fields)
+                    .filter_map(|f| f.field_type.ts_type_definition())
+                    .collect();
+                definitions.push(format!(
+                    "export type {label} = {};",
+                    variants
+                        .iter()
+                        .map(|v| match variant_fields.get(v) {
+                            None => format!("{{type: '{}'}}", v),
+                            Some(VariantFields {
+                                fields,
+                                newtype: true,
+                            }) => format!(
+                                "{{type: '{}', content: {}}}",
+                                v,
+                                fields.iter().map(|f| f.ts_type()).join(", ")
+                            ),
+                            Some(VariantFields {
+                                fields,
+                                newtype: false,
+                            }) => format!(
+                                "{{type: '{}', content: {{{}}}}}",
+                                v,
+                                fields
+                                    .iter()
+                                    .map(|f| format!(
+                                        "{}: {}",
+                                        f.field_name.to_case(Case::Snake),
+                                        f.ts_type()
+                                    ))
+                                    .join("; ")
+                            ),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                ));
+                Some(definitions.join("\n\n"))
+            }
+            FieldType::Struct { label, fields } => {
+                let mut definitions: Vec<String> = fields
+                    .iter()
+                    .filter_map(|f| f.field_type.ts_type_definition())
+                    .collect();
+                definitions.push(format!(
+                    "export interface {label} {{\n{}\n}}",
+                    fields.iter().map(|f| f.ts_field_codegen()).join("\n")
+                ));
+                Some(definitions.join("\n\n"))
+            }
             _ => None,
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 pub enum Cardinality {
     #[serde(rename = "single")]
     Single,
@@ -201,13 +342,26 @@ pub enum Cardinality {
     Option,
 }
 
-#[derive(Serialize, Debug, Clone)]
+/// The fields of a variant of an enum that carries data
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct VariantFields {
+    pub fields: Vec<FieldDefinition>,
+    /// Whether the variant holds its only field without a name, like `Fixed(u32)`,
+    /// instead of named fields, like `Scheduled { at: Timestamp }`
+    pub newtype: bool,
+}
+
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
 pub struct FieldDefinition {
     pub field_name: String,
     pub field_type: FieldType,
     pub widget: Option<String>,
     pub cardinality: Cardinality,
     pub linked_from: Option<Referenceable>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<FieldConstraints>,
+    /// Whether the value of the field can't be changed when the entry is updated
+    pub immutable: bool,
 }
 
 impl FieldDefinition {
@@ -225,11 +379,38 @@ impl FieldDefinition {
             widget,
             cardinality,
             linked_from,
+            constraints: None,
+            immutable: false,
         })
     }
 }
 
 impl FieldDefinition {
+    /// Generate the field as a member of a typescript interface
+    pub fn ts_field_codegen(&self) -> String {
+        format!(
+            "  {}: {};",
+            self.field_name.to_case(Case::Snake),
+            self.ts_type()
+        )
+    }
+
+    /// The typescript type of the field, with its cardinality
+    pub fn ts_type(&self) -> String {
+        let ts_type = self.field_type.ts_type();
+        match self.cardinality {
+            Cardinality::Single => ts_type.to_string(),
+            Cardinality::Option => format!("{ts_type} | undefined"),
+            Cardinality::Vector => {
+                if matches!(self.field_type, FieldType::U8) {
+                    String::from("Uint8Array")
+                } else {
+                    format!("Array<{ts_type}>")
+                }
+            }
+        }
+    }
+
     pub fn rust_type(&self) -> TokenStream {
         match self.cardinality {
             Cardinality::Single => self.field_type.rust_type(),
@@ -247,11 +428,186 @@ impl FieldDefinition {
     }
 }
 
+/// Splits the given string on the separators that are not between the braces or the parentheses
+/// of a struct or a variant
+fn split_top_level(s: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' | '(' => depth += 1,
+            '}' | ')' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Parses a list of fields separated by commas, like the value of the `--fields` argument,
+/// whose constraints can also be separated by commas, eg. "title:String{min=1,max=140},body:String"
+pub fn parse_fields(fields_str: &str) -> ScaffoldResult<Vec<FieldDefinition>> {
+    split_top_level(fields_str, ',')
+        .into_iter()
+        .filter(|f| !f.is_empty())
+        .map(FieldDefinition::from_str)
+        .collect()
+}
+
+/// Parses the fields of a struct or of an enum variant, separated by semicolons
+fn parse_nested_fields(fields_str: &str, owner: &str) -> ScaffoldResult<Vec<FieldDefinition>> {
+    let fields = split_top_level(fields_str, ';')
+        .into_iter()
+        .filter(|f| !f.is_empty())
+        .map(FieldDefinition::from_str)
+        .collect::<ScaffoldResult<Vec<_>>>()?;
+
+    if fields.is_empty() {
+        return Err(ScaffoldError::InvalidArguments(format!(
+            "The {owner} has no fields"
+        )));
+    }
+    if let Some(field) = fields.iter().find(|f| f.linked_from.is_some()) {
+        return Err(ScaffoldError::InvalidArguments(format!(
+            "Field {} of the {owner} can't be linked from, only the fields of the entry can",
+            field.field_name
+        )));
+    }
+    if let Some(field) = fields.iter().find(|f| f.constraints.is_some()) {
+        return Err(ScaffoldError::InvalidArguments(format!(
+            "Field {} of the {owner} can't have constraints, only the fields of the entry can",
+            field.field_name
+        )));
+    }
+    if let Some(field) = fields.iter().find(|f| f.immutable) {
+        return Err(ScaffoldError::InvalidArguments(format!(
+            "Field {} of the {owner} can't be immutable, only the fields of the entry can",
+            field.field_name
+        )));
+    }
+
+    Ok(fields)
+}
+
+/// Parses a variant of an enum, with its named fields between braces, eg. "Scheduled{at:Timestamp}",
+/// or with its only field between parentheses, eg. "Fixed(u32)"
+fn parse_variant(variant_str: &str) -> ScaffoldResult<(String, Option<VariantFields>)> {
+    if let Some((name, rest)) = variant_str.split_once('{') {
+        let name = name.to_case(Case::Pascal);
+        let body = rest
+            .strip_suffix('}')
+            .context(format!("Missing closing brace in: {}", variant_str))?;
+        let fields = parse_nested_fields(body, &format!("variant {name}"))?;
+        return Ok((
+            name,
+            Some(VariantFields {
+                fields,
+                newtype: false,
+            }),
+        ));
+    }
+
+    if let Some((name, rest)) = variant_str.split_once('(') {
+        let field_type = rest
+            .strip_suffix(')')
+            .context(format!("Missing closing parenthesis in: {}", variant_str))?;
+        // The field is named after the variant, so that it has a label in the UI
+        let field =
+            FieldDefinition::from_str(&format!("{}:{field_type}", name.to_case(Case::Snake)))?;
+        if field.constraints.is_some() {
+            return Err(ScaffoldError::InvalidArguments(format!(
+                "The value of variant {name} can't have constraints, only the fields of the entry can"
+            )));
+        }
+        return Ok((
+            name.to_case(Case::Pascal),
+            Some(VariantFields {
+                fields: vec![field],
+                newtype: true,
+            }),
+        ));
+    }
+
+    Ok((variant_str.to_case(Case::Pascal), None))
+}
+
+impl FieldDefinition {
+    /// Parses a struct field, whose fields are between braces and separated by semicolons,
+    /// eg. "address:Struct:Address{street:String:TextField;city:String:TextField}:Fieldset"
+    fn parse_struct_field(fields_str: &str) -> ScaffoldResult<Self> {
+        let (head, rest) = fields_str
+            .split_once('{')
+            .context(format!("Struct fields missing from: {}", fields_str))?;
+        let (body, widget) = rest
+            .rsplit_once('}')
+            .context(format!("Missing closing brace in: {}", fields_str))?;
+
+        let mut str_path = head.split(':');
+        let field_name = str_path
+            .next()
+            .context(format!("field_name is missing from: {}", fields_str))?;
+        check_case(field_name, "field_name", Case::Snake)?;
+
+        let cardinality = match str_path.next() {
+            Some("Struct") => Cardinality::Single,
+            Some("Option<Struct>") => Cardinality::Option,
+            Some("Vec<Struct>") => Cardinality::Vector,
+            _ => {
+                return Err(ScaffoldError::InvalidArguments(format!(
+                    "Only struct fields can have their fields between braces: {}",
+                    fields_str
+                )))
+            }
+        };
+        let label = str_path
+            .next()
+            .context(format!("Struct label missing from: {}", fields_str))?;
+        check_case(label, "struct label", Case::Pascal)?;
+
+        let fields = parse_nested_fields(body, &format!("struct {label}"))?;
+
+        let widget = widget
+            .strip_prefix(':')
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string());
+
+        FieldDefinition::new(
+            field_name.to_string(),
+            FieldType::Struct {
+                label: label.to_string(),
+                fields,
+            },
+            widget,
+            cardinality,
+            None,
+        )
+    }
+}
+
 impl FromStr for FieldDefinition {
     type Err = ScaffoldError;
 
     fn from_str(fields_str: &str) -> Result<Self, Self::Err> {
-        let mut str_path = fields_str.split(':');
+        // Immutable fields are prefixed with a keyword, eg. "immutable created_for:AgentPubKey"
+        if let Some(field_str) = fields_str.strip_prefix("immutable ") {
+            let mut field_definition = FieldDefinition::from_str(field_str.trim_start())?;
+            field_definition.immutable = true;
+            return Ok(field_definition);
+        }
+
+        let segments = split_top_level(fields_str, ':');
+        let is_enum = segments.get(1).is_some_and(|t| t.contains("Enum"));
+        let has_constraints = segments.get(1).is_some_and(|t| t.contains('{'));
+        if fields_str.contains('{') && !is_enum && !has_constraints {
+            return FieldDefinition::parse_struct_field(fields_str);
+        }
+
+        let mut str_path = segments.into_iter();
 
         let field_name = str_path.next().context(format!(
             "field_name is missing from: {}\nExample: \"{}\"",
@@ -271,6 +627,19 @@ impl FromStr for FieldDefinition {
             "title:String".italic()
         ))?;
 
+        // The constraints on the values of the field follow its type, eg. "String{min=1,max=140}"
+        let (field_type_str, constraints_str) = match field_type_str.split_once('{') {
+            Some((field_type_str, constraints)) => (
+                field_type_str,
+                Some(
+                    constraints
+                        .strip_suffix('}')
+                        .context(format!("Missing closing brace in: {}", fields_str))?,
+                ),
+            ),
+            None => (field_type_str, None),
+        };
+
         let vec_regex = Regex::new(r"Vec<(?P<a>(.)*)>\z").unwrap();
         let option_regex = Regex::new(r"Option<(?P<a>(.)*)>\z").unwrap();
 
@@ -320,13 +689,20 @@ impl FromStr for FieldDefinition {
             })
             .unwrap_or_default();
 
-        FieldDefinition::new(
+        let constraints = constraints_str
+            .map(|c| FieldConstraints::parse(c, field_name, &field_type))
+            .transpose()?;
+
+        let mut field_definition = FieldDefinition::new(
             field_name.to_string(),
             field_type,
             widget,
             cardinality,
             linked_from,
-        )
+        )?;
+        field_definition.constraints = constraints;
+
+        Ok(field_definition)
     }
 }
 
@@ -385,7 +761,7 @@ impl FromStr for EntryTypeReference {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd)]
 pub enum Referenceable {
     Agent { role: String },
     EntryType(EntryTypeReference),
@@ -466,11 +842,65 @@ impl Referenceable {
     }
 }
 
+/// Whether the entries of an entry type are published to the DHT, or only kept in the source chain of their author
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    #[default]
+    Public,
+    Private,
+}
+
+impl FromStr for Visibility {
+    type Err = ScaffoldError;
+
+    fn from_str(s: &str) -> ScaffoldResult<Self> {
+        match s {
+            "public" => Ok(Visibility::Public),
+            "private" => Ok(Visibility::Private),
+            _ => Err(ScaffoldError::InvalidArguments(format!(
+                "Invalid visibility \"{s}\", expected one of: public, private"
+            ))),
+        }
+    }
+}
+
+/// Which agents can update and delete the entries of an entry type
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Permission {
+    /// Only the author of the original entry
+    Author,
+    /// The author of the original entry, and the agents listed in the given field of the original entry
+    Editors { field: String },
+    /// Only the agent set as the "progenitor" property of the DNA
+    Progenitor,
+}
+
+impl FromStr for Permission {
+    type Err = ScaffoldError;
+
+    fn from_str(s: &str) -> ScaffoldResult<Self> {
+        match s.split_once(':') {
+            None if s == "author" => Ok(Permission::Author),
+            None if s == "progenitor" => Ok(Permission::Progenitor),
+            Some(("editors", field)) if !field.is_empty() => Ok(Permission::Editors {
+                field: field.to_case(Case::Snake),
+            }),
+            _ => Err(ScaffoldError::InvalidArguments(format!(
+                "Invalid permission \"{s}\", expected one of: author, editors:<FIELD_NAME>, progenitor"
+            ))),
+        }
+    }
+}
+
 #[derive(Serialize, Clone, Debug)]
 pub struct EntryDefinition {
     pub name: String,
     pub fields: Vec<FieldDefinition>,
     pub reference_entry_hash: bool,
+    pub visibility: Visibility,
+    pub permission: Option<Permission>,
 }
 
 impl EntryDefinition {
@@ -499,40 +929,11 @@ impl EntryDefinition {
         let mut ts_enums = String::new();
 
         for field in &self.fields {
-            let ts_type = field.field_type.ts_type();
-            if let FieldType::Enum { label, variants } = &field.field_type {
-                let enum_definition = format!(
-                    "export type {label} = {};\n",
-                    variants
-                        .iter()
-                        .map(|v| format!("{{type: '{}'}}", v))
-                        .collect::<Vec<_>>()
-                        .join(" | ")
-                );
+            if let Some(enum_definition) = field.field_type.ts_type_definition() {
                 ts_enums.push_str(&enum_definition);
+                ts_enums.push('\n');
             }
-            let ts_field = match field.cardinality {
-                Cardinality::Single => {
-                    format!("  {}: {};", &field.field_name.to_case(Case::Snake), ts_type)
-                }
-                Cardinality::Option => format!(
-                    "  {}: {} | undefined;",
-                    &field.field_name.to_case(Case::Snake),
-                    ts_type
-                ),
-                Cardinality::Vector => {
-                    if matches!(field.field_type, FieldType::U8) {
-                        format!("  {}: Uint8Array;", &field.field_name.to_case(Case::Snake),)
-                    } else {
-                        format!(
-                            "  {}: Array<{}>;",
-                            &field.field_name.to_case(Case::Snake),
-                            ts_type
-                        )
-                    }
-                }
-            };
-            ts_interface.push_str(&ts_field);
+            ts_interface.push_str(&field.ts_field_codegen());
             ts_interface.push('\n');
         }
         ts_interface.push('}');
@@ -558,6 +959,8 @@ mod tests {
                     widget: Some("TextField".to_string()),
                     cardinality: Cardinality::Single,
                     linked_from: None,
+                    constraints: None,
+                    immutable: false,
                 },
                 FieldDefinition {
                     field_name: "content".to_string(),
@@ -565,9 +968,13 @@ mod tests {
                     widget: Some("TextArea".to_string()),
                     cardinality: Cardinality::Single,
                     linked_from: None,
+                    constraints: None,
+                    immutable: false,
                 },
             ],
             reference_entry_hash: false,
+            visibility: Visibility::Public,
+            permission: None,
         };
 
         let comment_entry = EntryDefinition {
@@ -579,6 +986,8 @@ mod tests {
                     widget: Some("TextArea".to_string()),
                     cardinality: Cardinality::Single,
                     linked_from: None,
+                    constraints: None,
+                    immutable: false,
                 },
                 FieldDefinition {
                     field_name: "post_hash".to_string(),
@@ -589,9 +998,13 @@ mod tests {
                         entry_type: post_entry.name.to_string(),
                         reference_entry_hash: false,
                     })),
+                    constraints: None,
+                    immutable: false,
                 },
             ],
             reference_entry_hash: false,
+            visibility: Visibility::Public,
+            permission: None,
         };
 
         let post_ts_interface = &post_entry.ts_type_codegen();
@@ -620,6 +1033,8 @@ mod tests {
                     widget: None,
                     cardinality: Cardinality::Single,
                     linked_from: None,
+                    constraints: None,
+                    immutable: false,
                 },
                 FieldDefinition {
                     field_name: "field_two".to_string(),
@@ -627,6 +1042,8 @@ mod tests {
                     widget: None,
                     cardinality: Cardinality::Option,
                     linked_from: None,
+                    constraints: None,
+                    immutable: false,
                 },
                 FieldDefinition {
                     field_name: "field_three".to_string(),
@@ -634,6 +1051,8 @@ mod tests {
                     widget: None,
                     cardinality: Cardinality::Vector,
                     linked_from: None,
+                    constraints: None,
+                    immutable: false,
                 },
                 FieldDefinition {
                     field_name: "enum_field".to_string(),
@@ -644,13 +1063,18 @@ mod tests {
                             "Variant2".to_string(),
                             "Variant3".to_string(),
                         ],
+                        variant_fields: BTreeMap::new(),
                     },
                     widget: None,
                     cardinality: Cardinality::Single,
                     linked_from: None,
+                    constraints: None,
+                    immutable: false,
                 },
             ],
             reference_entry_hash: false,
+            visibility: Visibility::Public,
+            permission: None,
         };
 
         let ts_interface = &other_entry.ts_type_codegen();
@@ -666,4 +1090,132 @@ export interface ExampleEntry {
 
         assert_eq!(ts_interface, expected_ts_interface);
     }
+
+    #[test]
+    fn test_struct_field_definitions() {
+        let field = FieldDefinition::from_str(
+            "address:Struct:Address{street:String:TextField;location:Option<Struct>:Location{lat:f32;lng:f32}}:Fieldset",
+        )
+        .unwrap();
+        assert_eq!(field.field_name, "address");
+        assert_eq!(field.widget.as_deref(), Some("Fieldset"));
+
+        let entry = EntryDefinition {
+            name: "place".to_string(),
+            fields: vec![field.clone()],
+            reference_entry_hash: false,
+            visibility: Visibility::Public,
+            permission: None,
+        };
+        let expected_ts_interface = r#"export interface Location {
+  lat: number;
+  lng: number;
+}
+
+export interface Address {
+  street: string;
+  location: Location | undefined;
+}
+
+export interface Place {
+  address: Address;
+}"#;
+        assert_eq!(entry.ts_type_codegen(), expected_ts_interface);
+
+        let rust_definition = field.field_type.rust_type_definition().unwrap();
+        let file: syn::File = syn::parse2(rust_definition).unwrap();
+        assert_eq!(
+            prettyplease::unparse(&file),
+            r#"#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Location {
+    pub lat: f32,
+    pub lng: f32,
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Address {
+    pub street: String,
+    pub location: Option<Location>,
+}
+"#
+        );
+
+        assert!(FieldDefinition::from_str("address:String:Address{street:String}").is_err());
+        assert!(FieldDefinition::from_str("address:Struct:Address{}").is_err());
+        assert!(
+            FieldDefinition::from_str("address:Struct:Address{author:AgentPubKey::author}")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_enum_variants_with_data() {
+        let field = FieldDefinition::from_str(
+            "status:Enum:Select:Status:Draft.Scheduled{at:Timestamp:DateTimePicker;note:Option<String>}.Fixed(u32:Slider)",
+        )
+        .unwrap();
+        assert_eq!(field.widget.as_deref(), Some("Select"));
+
+        let entry = EntryDefinition {
+            name: "event".to_string(),
+            fields: vec![field.clone()],
+            reference_entry_hash: false,
+            visibility: Visibility::Public,
+            permission: None,
+        };
+        let expected_ts_type = r#"export type Status = {type: 'Draft'} | {type: 'Scheduled', content: {at: number; note: string | undefined}} | {type: 'Fixed', content: number};
+
+export interface Event {
+  status: Status;
+}"#;
+        assert_eq!(entry.ts_type_codegen(), expected_ts_type);
+
+        let rust_definition = field.field_type.rust_type_definition().unwrap();
+        let file: syn::File = syn::parse2(rust_definition).unwrap();
+        assert_eq!(
+            prettyplease::unparse(&file),
+            r#"#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", content = "content")]
+pub enum Status {
+    Draft,
+    Scheduled { at: Timestamp, note: Option<String> },
+    Fixed(u32),
+}
+"#
+        );
+
+        assert!(FieldDefinition::from_str("status:Enum:Select:Status:Draft.Scheduled{}").is_err());
+        assert!(FieldDefinition::from_str("status:Enum:Select:Status:Draft.Fixed()").is_err());
+    }
+
+    #[test]
+    fn test_immutable_fields() {
+        let field =
+            FieldDefinition::from_str("immutable created_for:AgentPubKey::profiles").unwrap();
+        assert_eq!(field.field_name, "created_for");
+        assert!(field.immutable);
+        assert!(field.linked_from.is_some());
+
+        assert!(!FieldDefinition::from_str("title:String").unwrap().immutable);
+        assert!(FieldDefinition::from_str(
+            "address:Struct:Address{immutable street:String;city:String}"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_permission_from_str() {
+        assert_eq!(Permission::from_str("author").unwrap(), Permission::Author);
+        assert_eq!(
+            Permission::from_str("editors:coAuthors").unwrap(),
+            Permission::Editors {
+                field: "co_authors".to_string()
+            }
+        );
+        assert_eq!(
+            Permission::from_str("progenitor").unwrap(),
+            Permission::Progenitor
+        );
+        assert!(Permission::from_str("editors:").is_err());
+        assert!(Permission::from_str("everyone").is_err());
+    }
 }
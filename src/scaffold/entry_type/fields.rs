@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use colored::Colorize;
 use convert_case::{Case, Casing};
@@ -9,11 +9,14 @@ use crate::{
     file_tree::{dir_content, FileTree},
     reserved_words::check_for_reserved_keywords,
     scaffold::zome::ZomeFileTree,
-    utils::{check_case, input_with_case, input_with_custom_validation},
+    utils::{check_case, input_with_case, input_with_custom_validation, Interactivity},
 };
 
 use super::{
-    definitions::{Cardinality, EntryTypeReference, FieldDefinition, FieldType, Referenceable},
+    constraints::FieldConstraints,
+    definitions::{
+        Cardinality, EntryTypeReference, FieldDefinition, FieldType, Referenceable, VariantFields,
+    },
     integrity::get_all_entry_types,
 };
 
@@ -22,7 +25,10 @@ pub fn choose_fields(
     zome_file_tree: &ZomeFileTree,
     field_types_templates: &FileTree,
     no_ui: bool,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<Vec<FieldDefinition>> {
+    interactivity.ensure_interactive("--fields")?;
+
     let mut finished = false;
     let mut fields: Vec<FieldDefinition> = Vec::new();
 
@@ -35,6 +41,8 @@ pub fn choose_fields(
             field_types_templates,
             no_ui,
             None,
+            false,
+            interactivity,
         )?;
         println!();
 
@@ -88,6 +96,8 @@ pub fn choose_fields(
                             field_types_templates,
                             no_ui,
                             Some(&fields[field_to_change].field_name),
+                            false,
+                            interactivity,
                         )?;
                         fields[field_to_change] = new_field;
                     } else {
@@ -102,6 +112,8 @@ pub fn choose_fields(
                         field_types_templates,
                         no_ui,
                         None,
+                        false,
+                        interactivity,
                     )?;
                     fields.push(new_field);
                 }
@@ -144,6 +156,7 @@ pub fn choose_fields(
             zome_file_tree,
             field_types_templates,
             no_ui,
+            interactivity,
         );
     }
 
@@ -156,6 +169,8 @@ fn choose_field(
     field_types_templates: &FileTree,
     no_ui: bool,
     initial_field_name: Option<&str>,
+    in_struct: bool,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<FieldDefinition> {
     let field_types = FieldType::list();
     let field_type_names: Vec<String> = field_types
@@ -164,8 +179,10 @@ fn choose_field(
         .map(|s| s.to_string())
         .collect();
 
-    let field_name =
-        input_with_custom_validation("Field name (snake_case):", initial_field_name, |input| {
+    let field_name = input_with_custom_validation(
+        "Field name (snake_case):",
+        initial_field_name,
+        |input| {
             if let Err(e) = check_case(&input, "field_name", Case::Snake) {
                 return Err(e.to_string());
             }
@@ -173,7 +190,9 @@ fn choose_field(
                 return Err(e.to_string());
             }
             Ok(())
-        })?;
+        },
+        interactivity,
+    )?;
 
     let selection = Select::with_theme(&ColorfulTheme::default())
         .with_prompt("Choose field type:")
@@ -220,9 +239,11 @@ fn choose_field(
                 }
                 Ok(())
             },
+            interactivity,
         )?;
 
         let mut variants = Vec::new();
+        let mut variant_fields = BTreeMap::new();
         let mut another_variant = true;
 
         while another_variant {
@@ -238,7 +259,53 @@ fn choose_field(
                     }
                     Ok(())
                 },
+                interactivity,
             )?;
+
+            let data = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Does the {variant} variant carry data?"))
+                .default(0)
+                .items(&["No", "Named fields", "A single value"])
+                .interact()?;
+            match data {
+                1 => {
+                    let fields = choose_nested_fields(
+                        entry_type_name,
+                        zome_file_tree,
+                        field_types_templates,
+                        no_ui,
+                        &format!("{variant} variant"),
+                        interactivity,
+                    )?;
+                    variant_fields.insert(
+                        variant.clone(),
+                        VariantFields {
+                            fields,
+                            newtype: false,
+                        },
+                    );
+                }
+                2 => {
+                    let field = choose_field(
+                        entry_type_name,
+                        zome_file_tree,
+                        field_types_templates,
+                        no_ui,
+                        Some(&variant.to_case(Case::Snake)),
+                        true,
+                        interactivity,
+                    )?;
+                    variant_fields.insert(
+                        variant.clone(),
+                        VariantFields {
+                            fields: vec![field],
+                            newtype: true,
+                        },
+                    );
+                }
+                _ => {}
+            }
+
             variants.push(variant);
             another_variant = Confirm::with_theme(&ColorfulTheme::default())
                 .with_prompt("Add another variant to the enum?")
@@ -253,14 +320,56 @@ fn choose_field(
 
         return FieldDefinition::new(
             label.to_case(Case::Snake),
-            FieldType::Enum { label, variants },
+            FieldType::Enum {
+                label,
+                variants,
+                variant_fields,
+            },
             widget,
             cardinality,
             None,
         );
     }
 
+    if let FieldType::Struct { .. } = field_type {
+        let label = input_with_custom_validation(
+            "Enter the name of the struct (PascalCase):",
+            None,
+            |input: String| {
+                if !input.is_case(Case::Pascal) {
+                    return Err(format!("Input must be {:?} case.", Case::Pascal));
+                }
+                if input.to_ascii_lowercase() == entry_type_name {
+                    return Err(format!(
+                        "Struct name: {input} conflicts with entry-type name: {entry_type_name}"
+                    ));
+                }
+                Ok(())
+            },
+            interactivity,
+        )?;
+
+        let fields = choose_nested_fields(
+            entry_type_name,
+            zome_file_tree,
+            field_types_templates,
+            no_ui,
+            &format!("{label} struct"),
+            interactivity,
+        )?;
+
+        let field_type = FieldType::Struct { label, fields };
+        let widget = (!no_ui)
+            .then(|| choose_widget(&field_type, &cardinality, field_types_templates))
+            .transpose()?
+            .flatten();
+
+        return FieldDefinition::new(field_name, field_type, widget, cardinality, None);
+    }
+
+    // Only the fields of the entry can be linked from, not the fields of its structs
     let linked_from = match &field_type {
+        _ if in_struct => None,
         FieldType::AgentPubKey => {
             let should_link_from_agent_pubkey = Confirm::with_theme(&ColorfulTheme::default())
                 .with_prompt(
@@ -272,7 +381,7 @@ fn choose_field(
                 let role = input_with_case(
                     "Which role does this agent play in the relationship ? (eg. \"creator\", \"invitee\")",
                     None,
-                    Case::Snake
+                    Case::Snake, interactivity
                 )?;
                 Some(Referenceable::Agent { role })
             } else {
@@ -337,12 +446,96 @@ fn choose_field(
         _ => None,
     };
 
+    // Only the fields of the entry are validated, so only they can have constraints
+    let constraints = match &field_type {
+        FieldType::String | FieldType::U8 | FieldType::U32 | FieldType::I32 | FieldType::F32
+            if !in_struct =>
+        {
+            let should_constrain = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Should the values of this field be constrained?")
+                .default(false)
+                .interact()?;
+
+            if should_constrain {
+                let example = match field_type {
+                    FieldType::String => "min=1,max=140 or regex=^[a-z-]+$",
+                    _ => "min=1,max=10 or range=1..=5",
+                };
+                let constraints = input_with_custom_validation(
+                    &format!("Enter the constraints (eg. {example}):"),
+                    None,
+                    |input: String| {
+                        FieldConstraints::parse(&input, &field_name, &field_type)
+                            .map(|_| ())
+                            .map_err(|e| e.to_string())
+                    },
+                    interactivity,
+                )?;
+                Some(FieldConstraints::parse(
+                    &constraints,
+                    &field_name,
+                    &field_type,
+                )?)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
+
+    let immutable = !in_struct
+        && Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(
+                "Should this field be immutable, so that updates of the entry can't change it?",
+            )
+            .default(false)
+            .interact()?;
+
     let widget = (!no_ui)
         .then(|| choose_widget(&field_type, &cardinality, field_types_templates))
         .transpose()?
         .flatten();
 
-    FieldDefinition::new(field_name, field_type, widget, cardinality, linked_from)
+    let mut field_definition =
+        FieldDefinition::new(field_name, field_type, widget, cardinality, linked_from)?;
+    field_definition.constraints = constraints;
+    field_definition.immutable = immutable;
+
+    Ok(field_definition)
+}
+
+/// Asks for the fields of a struct or of an enum variant, which can't be linked from
+fn choose_nested_fields(
+    entry_type_name: &str,
+    zome_file_tree: &ZomeFileTree,
+    field_types_templates: &FileTree,
+    no_ui: bool,
+    owner: &str,
+    interactivity: Interactivity,
+) -> ScaffoldResult<Vec<FieldDefinition>> {
+    println!("\nWhich fields should the {owner} contain?\n");
+
+    let mut fields = Vec::new();
+    let mut another_field = true;
+
+    while another_field {
+        fields.push(choose_field(
+            entry_type_name,
+            zome_file_tree,
+            field_types_templates,
+            no_ui,
+            None,
+            true,
+            interactivity,
+        )?);
+        another_field = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Add another field to the {owner}?"))
+            .report(false)
+            .interact()?;
+    }
+    println!();
+
+    Ok(fields)
 }
 
 fn choose_widget(
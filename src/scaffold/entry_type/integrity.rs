@@ -1,12 +1,18 @@
 use convert_case::{Case, Casing};
 use itertools::Itertools;
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
 use std::{ffi::OsString, path::PathBuf};
+use syn::parse::Parser;
 
 use crate::error::{ScaffoldError, ScaffoldResult};
-use crate::file_tree::insert_file;
+use crate::file_tree::{file_exists, insert_file};
+use crate::scaffold::app::cargo::add_crate_external_dependency;
 use crate::scaffold::dna::DnaFileTree;
+use crate::scaffold::remove::{
+    map_crate_rust_files, mentions_field, mentions_ident, remove_enum_variant, remove_match_arms,
+    statements_to_remove,
+};
 use crate::scaffold::zome::coordinator::find_extern_function_in_zomes;
 use crate::scaffold::zome::utils::get_coordinator_zomes_for_integrity;
 use crate::utils::unparse_pretty;
@@ -15,11 +21,16 @@ use crate::{
     scaffold::zome::ZomeFileTree,
 };
 
+use super::constraints::ConstraintKind;
 use super::crud::Crud;
 use super::definitions::{
-    Cardinality, EntryDefinition, EntryTypeReference, FieldDefinition, Referenceable,
+    Cardinality, EntryDefinition, EntryTypeReference, FieldDefinition, FieldType, Permission,
+    Referenceable, Visibility,
 };
 
+/// The version of the regex crate that the integrity zomes depend on to validate regex constraints
+const REGEX_VERSION: &str = "1";
+
 pub fn add_entry_type_to_integrity_zome(
     zome_file_tree: ZomeFileTree,
     entry_def: &EntryDefinition,
@@ -47,6 +58,15 @@ pub fn add_entry_type_to_integrity_zome(
         &unparse_pretty(&entry_def_file),
     )?;
 
+    if needs_regex(&entry_def.fields) {
+        file_tree = add_crate_external_dependency(
+            file_tree,
+            &zome_file_tree.zome_crate_path,
+            "regex",
+            REGEX_VERSION,
+        )?;
+    }
+
     // 2. Add this file as a module in the entry point for the crate
 
     let lib_rs_path = crate_src_path.join("lib.rs");
@@ -59,6 +79,24 @@ pub use {snake_entry_def_name}::*;
         ))
     })?;
 
+    let progenitor_path = crate_src_path.join("progenitor.rs");
+    if entry_def.permission == Some(Permission::Progenitor)
+        && !file_exists(&file_tree, &progenitor_path)
+    {
+        insert_file(
+            &mut file_tree,
+            &progenitor_path,
+            &unparse_pretty(&render_progenitor_file()),
+        )?;
+        map_file(&mut file_tree, &lib_rs_path, |contents| {
+            Ok(format!(
+                r#"pub mod progenitor;
+pub use progenitor::*;
+{contents}"#,
+            ))
+        })?;
+    }
+
     let pascal_entry_def_name = entry_def.name.to_case(Case::Pascal);
 
     let v: Vec<OsString> = crate_src_path
@@ -151,8 +189,14 @@ pub use {snake_entry_def_name}::*;
                             }
                             found = true;
                             let pascal_entry_def_name = format_ident!("{pascal_entry_def_name}");
-                            let new_variant = syn::parse_quote! {
-                                #pascal_entry_def_name(#pascal_entry_def_name)
+                            let new_variant = match entry_def.visibility {
+                                Visibility::Public => syn::parse_quote! {
+                                    #pascal_entry_def_name(#pascal_entry_def_name)
+                                },
+                                Visibility::Private => syn::parse_quote! {
+                                    #[entry_type(visibility = "private")]
+                                    #pascal_entry_def_name(#pascal_entry_def_name)
+                                },
                             };
                             item_enum.variants.push(new_variant);
                             return Ok(syn::Item::Enum(item_enum));
@@ -186,6 +230,30 @@ pub use {snake_entry_def_name}::*;
     Ok(zome_file_tree)
 }
 
+/// Reads the progenitor of the DNA from its properties, for the entry types that only it can modify
+fn render_progenitor_file() -> syn::File {
+    syn::parse_quote! {
+        use hdi::prelude::*;
+
+        /// The properties of the DNA, that can be set when installing the app
+        #[derive(Serialize, Deserialize, Debug)]
+        pub struct DnaProperties {
+            pub progenitor: Option<AgentPubKeyB64>,
+        }
+
+        /// The agent set as the "progenitor" property of the DNA, if any
+        ///
+        /// If there is no progenitor, no agent can modify the entries that only the progenitor could
+        pub fn progenitor() -> ExternResult<Option<AgentPubKey>> {
+            let properties: Option<DnaProperties> = decode(dna_info()?.modifiers.properties.bytes())
+                .map_err(|e| wasm_error!(e))?;
+            Ok(properties
+                .and_then(|properties| properties.progenitor)
+                .map(AgentPubKey::from))
+        }
+    }
+}
+
 pub fn render_entry_definition_file(
     entry_def: &EntryDefinition,
     crud: &Crud,
@@ -201,14 +269,68 @@ pub fn render_entry_definition_file(
         .filter_map(|field_def| field_def.field_type.rust_type_definition())
         .collect();
 
+    let constrained_fields: Vec<&FieldDefinition> = entry_def
+        .fields
+        .iter()
+        .filter(|f| f.constraints.is_some())
+        .collect();
+    let immutable_fields: Vec<&FieldDefinition> =
+        entry_def.fields.iter().filter(|f| f.immutable).collect();
+
+    // The arguments of the update and delete validations that the permission checks use
+    let permission_arg = |used: bool, name: &str| match used {
+        true => format_ident!("{name}"),
+        false => format_ident!("_{name}"),
+    };
+    let original_entry_name = format!("original_{}", entry_def.name.to_case(Case::Snake));
+    let (uses_action, uses_original_action, uses_original_entry) = match &entry_def.permission {
+        None => (false, false, false),
+        Some(Permission::Author) => (true, true, false),
+        Some(Permission::Editors { .. }) => (true, true, true),
+        Some(Permission::Progenitor) => (true, false, false),
+    };
+
     let validate_update_fn =
         format_ident!("validate_update_{}", entry_def.name.to_case(Case::Snake));
-    let new_entry_arg = format_ident!("_{}", entry_def.name.to_case(Case::Snake));
-    let original_entry_arg = format_ident!("_original_{}", entry_def.name.to_case(Case::Snake));
+    let new_entry_arg = match (
+        crud.update,
+        constrained_fields.len() + immutable_fields.len(),
+    ) {
+        (true, 1..) => format_ident!("{}", entry_def.name.to_case(Case::Snake)),
+        _ => format_ident!("_{}", entry_def.name.to_case(Case::Snake)),
+    };
+    let update_action_arg = permission_arg(crud.update && uses_action, "action");
+    let update_original_action_arg =
+        permission_arg(crud.update && uses_original_action, "original_action");
+    let original_entry_arg = permission_arg(
+        crud.update && (uses_original_entry || !immutable_fields.is_empty()),
+        &original_entry_name,
+    );
     let updated_invalid_reason = format!("{plural_name_title} cannot be updated");
 
     let validate_update_result = if crud.update {
+        let permission_validation = entry_def.permission.as_ref().map(|permission| {
+            render_permission_validation(entry_def, permission, &original_entry_arg, "update")
+        });
+        let immutability_validation = immutable_fields.iter().map(|field_def| {
+            render_immutability_validation(
+                &entry_def.pascal_case_name(),
+                &new_entry_arg,
+                &original_entry_arg,
+                field_def,
+            )
+        });
+        let constraints_validation = constrained_fields
+            .iter()
+            .map(|field_def| render_constraints_validation(&new_entry_arg, field_def))
+            .collect::<ScaffoldResult<Vec<TokenStream>>>()?;
         quote! {
+            #permission_validation
+
+            #(#immutability_validation)*
+
+            #(#constraints_validation)*
+
             /// TODO: add the appropriate validation rules
             Ok(ValidateCallbackResult::Valid)
         }
@@ -220,9 +342,9 @@ pub fn render_entry_definition_file(
 
     let validate_update = quote! {
         pub fn #validate_update_fn(
-            _action: Update,
+            #update_action_arg: Update,
             #new_entry_arg: #name_pascal,
-            _original_action: EntryCreationAction,
+            #update_original_action_arg: EntryCreationAction,
             #original_entry_arg: #name_pascal
         ) -> ExternResult<ValidateCallbackResult> {
             #validate_update_result
@@ -231,11 +353,19 @@ pub fn render_entry_definition_file(
 
     let validate_delete_fn =
         format_ident!("validate_delete_{}", entry_def.name.to_case(Case::Snake));
-    let deleted_post_arg = format_ident!("_original_{}", entry_def.name.to_case(Case::Snake));
+    let delete_action_arg = permission_arg(crud.delete && uses_action, "action");
+    let delete_original_action_arg =
+        permission_arg(crud.delete && uses_original_action, "original_action");
+    let deleted_post_arg = permission_arg(crud.delete && uses_original_entry, &original_entry_name);
     let deleted_invalid_reason = format!("{plural_name_title} cannot be deleted");
 
     let validate_delete_result = if crud.delete {
+        let permission_validation = entry_def.permission.as_ref().map(|permission| {
+            render_permission_validation(entry_def, permission, &deleted_post_arg, "delete")
+        });
         quote! {
+            #permission_validation
+
             /// TODO: add the appropriate validation rules
             Ok(ValidateCallbackResult::Valid)
         }
@@ -247,8 +377,8 @@ pub fn render_entry_definition_file(
 
     let validate_delete = quote! {
         pub fn #validate_delete_fn(
-            _action: Delete,
-            _original_action: EntryCreationAction,
+            #delete_action_arg: Delete,
+            #delete_original_action_arg: EntryCreationAction,
             #deleted_post_arg: #name_pascal
         ) -> ExternResult<ValidateCallbackResult> {
             #validate_delete_result
@@ -269,60 +399,20 @@ pub fn render_entry_definition_file(
         })
         .collect();
 
-    let create_new_entry_arg = match deps.len() {
+    let create_new_entry_arg = match deps.len() + constrained_fields.len() {
         0 => format_ident!("_{}", entry_def.name.to_case(Case::Snake)),
         _ => format_ident!("{}", entry_def.name.to_case(Case::Snake)),
     };
     let deps_validation: Vec<TokenStream> = deps
-        .into_iter()
+        .iter()
         .map(|(field_def, reference)| {
-            let field_name = format_ident!("{}",field_def.field_name);
-            let dependant_entry_type_snake = format_ident!("_{}", reference.entry_type.to_case(Case::Snake));
-            let dependant_entry_type_pascal = format_ident!("{}", reference.entry_type.to_case(Case::Pascal));
-            match (field_def.cardinality, reference.reference_entry_hash) {
-                (Cardinality::Single, false) => quote! {
-                    let record = must_get_valid_record(#create_new_entry_arg.#field_name.clone())?;
-
-                    let #dependant_entry_type_snake: crate::#dependant_entry_type_pascal = record.entry().to_app_option()
-                        .map_err(|e| wasm_error!(e))?
-                        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Dependant action must be accompanied by an entry"))))?;
-                },
-                (Cardinality::Option, false) => quote! {
-                    if let Some(action_hash) = #create_new_entry_arg.#field_name.clone() {
-                        let record = must_get_valid_record(action_hash)?;
-
-                        let #dependant_entry_type_snake: crate::#dependant_entry_type_pascal = record.entry().to_app_option()
-                            .map_err(|e| wasm_error!(e))?
-                            .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Dependant action must be accompanied by an entry"))))?;
-                    }
-                },
-                (Cardinality::Vector, false) => quote! {
-                    for action_hash in #create_new_entry_arg.#field_name.clone() {
-                        let record = must_get_valid_record(action_hash)?;
-                        let #dependant_entry_type_snake: crate::#dependant_entry_type_pascal = record.entry().to_app_option()
-                            .map_err(|e| wasm_error!(e))?
-                            .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Dependant action must be accompanied by an entry"))))?;
-                    }
-                },
-                (Cardinality::Single,true) => quote! {
-                    let entry = must_get_entry(#create_new_entry_arg.#field_name.clone())?;
-                    let #dependant_entry_type_snake = crate::#dependant_entry_type_pascal::try_from(entry)?;
-                },
-                (Cardinality::Option, true) => quote! {
-                    if let Some(entry_hash) = #create_new_entry_arg.#field_name.clone() {
-                        let entry = must_get_entry(entry_hash)?;
-                        let #dependant_entry_type_snake = crate::#dependant_entry_type_pascal::try_from(entry)?;
-                    }
-                },
-                (Cardinality::Vector, true) => quote! {
-                    for entry_hash in #create_new_entry_arg.#field_name.clone() {
-                        let entry = must_get_entry(entry_hash)?;
-                        let #dependant_entry_type_snake = crate::#dependant_entry_type_pascal::try_from(entry)?;
-                    }
-                },
-            }
+            render_dependency_validation(&create_new_entry_arg, field_def, reference)
         })
         .collect();
+    let constraints_validation: Vec<TokenStream> = constrained_fields
+        .iter()
+        .map(|field_def| render_constraints_validation(&create_new_entry_arg, field_def))
+        .collect::<ScaffoldResult<_>>()?;
 
     let token_stream = syn::parse_quote! {
         use hdi::prelude::*;
@@ -339,6 +429,8 @@ pub fn render_entry_definition_file(
         ) -> ExternResult<ValidateCallbackResult> {
             #(#deps_validation)*
 
+            #(#constraints_validation)*
+
             /// TODO: add the appropriate validation rules
             Ok(ValidateCallbackResult::Valid)
         }
@@ -351,6 +443,217 @@ pub fn render_entry_definition_file(
     Ok(token_stream)
 }
 
+/// Validates that the entries referenced by the given field exist and are of the expected entry type
+pub fn render_dependency_validation(
+    create_new_entry_arg: &syn::Ident,
+    field_def: &FieldDefinition,
+    reference: &EntryTypeReference,
+) -> TokenStream {
+    let field_name = format_ident!("{}", field_def.field_name);
+    let dependant_entry_type_snake =
+        format_ident!("_{}", reference.entry_type.to_case(Case::Snake));
+    let dependant_entry_type_pascal =
+        format_ident!("{}", reference.entry_type.to_case(Case::Pascal));
+    match (&field_def.cardinality, reference.reference_entry_hash) {
+        (Cardinality::Single, false) => quote! {
+            let record = must_get_valid_record(#create_new_entry_arg.#field_name.clone())?;
+
+            let #dependant_entry_type_snake: crate::#dependant_entry_type_pascal = record.entry().to_app_option()
+                .map_err(|e| wasm_error!(e))?
+                .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Dependant action must be accompanied by an entry"))))?;
+        },
+        (Cardinality::Option, false) => quote! {
+            if let Some(action_hash) = #create_new_entry_arg.#field_name.clone() {
+                let record = must_get_valid_record(action_hash)?;
+
+                let #dependant_entry_type_snake: crate::#dependant_entry_type_pascal = record.entry().to_app_option()
+                    .map_err(|e| wasm_error!(e))?
+                    .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Dependant action must be accompanied by an entry"))))?;
+            }
+        },
+        (Cardinality::Vector, false) => quote! {
+            for action_hash in #create_new_entry_arg.#field_name.clone() {
+                let record = must_get_valid_record(action_hash)?;
+                let #dependant_entry_type_snake: crate::#dependant_entry_type_pascal = record.entry().to_app_option()
+                    .map_err(|e| wasm_error!(e))?
+                    .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Dependant action must be accompanied by an entry"))))?;
+            }
+        },
+        (Cardinality::Single, true) => quote! {
+            let entry = must_get_entry(#create_new_entry_arg.#field_name.clone())?;
+            let #dependant_entry_type_snake = crate::#dependant_entry_type_pascal::try_from(entry)?;
+        },
+        (Cardinality::Option, true) => quote! {
+            if let Some(entry_hash) = #create_new_entry_arg.#field_name.clone() {
+                let entry = must_get_entry(entry_hash)?;
+                let #dependant_entry_type_snake = crate::#dependant_entry_type_pascal::try_from(entry)?;
+            }
+        },
+        (Cardinality::Vector, true) => quote! {
+            for entry_hash in #create_new_entry_arg.#field_name.clone() {
+                let entry = must_get_entry(entry_hash)?;
+                let #dependant_entry_type_snake = crate::#dependant_entry_type_pascal::try_from(entry)?;
+            }
+        },
+    }
+}
+
+/// Checks that the author of an update or a delete of an entry is allowed to modify it
+fn render_permission_validation(
+    entry_def: &EntryDefinition,
+    permission: &Permission,
+    original_entry_arg: &syn::Ident,
+    verb: &str,
+) -> TokenStream {
+    let pascal_entry_def_name = entry_def.pascal_case_name();
+    match permission {
+        Permission::Author => {
+            let message = format!("Only the author of a {pascal_entry_def_name} can {verb} it");
+            quote! {
+                if action.author != *original_action.author() {
+                    return Ok(ValidateCallbackResult::Invalid(String::from(#message)));
+                }
+            }
+        }
+        Permission::Editors { field } => {
+            let field = format_ident!("{field}");
+            let message = format!(
+                "Only the author or the editors of a {pascal_entry_def_name} can {verb} it"
+            );
+            quote! {
+                if action.author != *original_action.author() && !#original_entry_arg.#field.contains(&action.author) {
+                    return Ok(ValidateCallbackResult::Invalid(String::from(#message)));
+                }
+            }
+        }
+        Permission::Progenitor => {
+            let plural_entry_def_name = pluralizer::pluralize(&pascal_entry_def_name, 2, false);
+            let message =
+                format!("Only the progenitor of the DNA can {verb} {plural_entry_def_name}");
+            let missing_progenitor_message = format!(
+                "The DNA has no progenitor property, so no agent can {verb} {plural_entry_def_name}"
+            );
+            quote! {
+                let Some(progenitor) = crate::progenitor()? else {
+                    return Ok(ValidateCallbackResult::Invalid(String::from(#missing_progenitor_message)));
+                };
+                if action.author != progenitor {
+                    return Ok(ValidateCallbackResult::Invalid(String::from(#message)));
+                }
+            }
+        }
+    }
+}
+
+/// Checks that an update of an entry keeps the value of the given field
+fn render_immutability_validation(
+    pascal_entry_def_name: &str,
+    new_entry_arg: &syn::Ident,
+    original_entry_arg: &syn::Ident,
+    field_def: &FieldDefinition,
+) -> TokenStream {
+    let field = format_ident!("{}", field_def.field_name.to_case(Case::Snake));
+    let message = format!(
+        "{} can't be changed when a {} is updated",
+        field_def.field_name.to_case(Case::Snake),
+        pascal_entry_def_name
+    );
+    quote! {
+        if #new_entry_arg.#field != #original_entry_arg.#field {
+            return Ok(ValidateCallbackResult::Invalid(String::from(#message)));
+        }
+    }
+}
+
+/// Whether validating the constraints of the given fields needs the regex crate
+fn needs_regex(fields: &[FieldDefinition]) -> bool {
+    fields
+        .iter()
+        .any(|f| f.constraints.as_ref().is_some_and(|c| c.has_regex()))
+}
+
+/// Validates that the value of the given field satisfies its constraints
+pub fn render_constraints_validation(
+    new_entry_arg: &syn::Ident,
+    field_def: &FieldDefinition,
+) -> ScaffoldResult<TokenStream> {
+    let Some(constraints) = &field_def.constraints else {
+        return Ok(quote! {});
+    };
+    let field_name = format_ident!("{}", field_def.field_name);
+    // Each value of a vector is named after the singular of the field
+    let item = format_ident!(
+        "{}",
+        match field_def.cardinality {
+            Cardinality::Vector => pluralizer::pluralize(&field_def.field_name, 1, false),
+            _ => field_def.field_name.clone(),
+        }
+    );
+    let value = match field_def.cardinality {
+        Cardinality::Single => quote! { #new_entry_arg.#field_name },
+        _ => quote! { #item },
+    };
+    let number = match field_def.cardinality {
+        Cardinality::Single => value.clone(),
+        _ => quote! { *#item },
+    };
+
+    let checks: Vec<TokenStream> = constraints
+        .rules
+        .iter()
+        .map(|rule| -> ScaffoldResult<TokenStream> {
+            let is_broken = match (rule.kind, &field_def.field_type) {
+                (ConstraintKind::Regex, _) => {
+                    let pattern = &rule.value;
+                    quote! {
+                        !regex::Regex::new(#pattern)
+                            .map_err(|e| wasm_error!(WasmErrorInner::Guest(e.to_string())))?
+                            .is_match(&#value)
+                    }
+                }
+                (kind, FieldType::String) => {
+                    let length: syn::Expr = syn::parse_str(&rule.value)?;
+                    match kind {
+                        ConstraintKind::Min => quote! { #value.chars().count() < #length },
+                        _ => quote! { #value.chars().count() > #length },
+                    }
+                }
+                (kind, field_type) => {
+                    let mut bound = rule.value.clone();
+                    if matches!(field_type, FieldType::F32) && !bound.contains('.') {
+                        bound.push_str(".0");
+                    }
+                    let bound: syn::Expr = syn::parse_str(&bound)?;
+                    match kind {
+                        ConstraintKind::Min => quote! { #number < #bound },
+                        _ => quote! { #number > #bound },
+                    }
+                }
+            };
+            let message = &rule.message;
+            Ok(quote! {
+                if #is_broken {
+                    return Ok(ValidateCallbackResult::Invalid(String::from(#message)));
+                }
+            })
+        })
+        .collect::<ScaffoldResult<_>>()?;
+
+    Ok(match field_def.cardinality {
+        Cardinality::Single => quote! { #(#checks)* },
+        Cardinality::Option => quote! {
+            if let Some(#item) = &#new_entry_arg.#field_name {
+                #(#checks)*
+            }
+        },
+        Cardinality::Vector => quote! {
+            for #item in &#new_entry_arg.#field_name {
+                #(#checks)*
+            }
+        },
+    })
+}
+
 pub fn render_entry_definition_struct(entry_def: &EntryDefinition) -> ScaffoldResult<TokenStream> {
     let name: syn::Expr = syn::parse_str(entry_def.name.to_case(Case::Pascal).as_str())?;
 
@@ -371,9 +674,8 @@ pub fn render_entry_definition_struct(entry_def: &EntryDefinition) -> ScaffoldRe
     })
 }
 
-pub fn get_all_entry_types(
-    zome_file_tree: &ZomeFileTree,
-) -> ScaffoldResult<Option<Vec<EntryTypeReference>>> {
+/// Finds the `#[hdk_entry_types]` enum of the integrity zome, if it has one
+fn find_entry_types_enum(zome_file_tree: &ZomeFileTree) -> ScaffoldResult<Option<syn::ItemEnum>> {
     let crate_src_path = zome_file_tree.zome_crate_path.join("src");
     let crate_src_path_iter: Vec<OsString> =
         crate_src_path.iter().map(|s| s.to_os_string()).collect();
@@ -403,9 +705,20 @@ pub fn get_all_entry_types(
 
     match entry_defs_instances.len() {
         0 => Ok(None),
-        1 => {
-            let entry_def_enum = entry_defs_instances.values().next().unwrap();
+        1 => Ok(entry_defs_instances.into_values().next()),
+        _ => Err(ScaffoldError::MultipleEntryTypesDefsFoundForIntegrityZome(
+            zome_file_tree.dna_file_tree.dna_manifest.name(),
+            zome_file_tree.zome_manifest.name.0.to_string(),
+        )),
+    }
+}
 
+pub fn get_all_entry_types(
+    zome_file_tree: &ZomeFileTree,
+) -> ScaffoldResult<Option<Vec<EntryTypeReference>>> {
+    match find_entry_types_enum(zome_file_tree)? {
+        None => Ok(None),
+        Some(entry_def_enum) => {
             let variants: Vec<String> = entry_def_enum
                 .clone()
                 .variants
@@ -450,13 +763,40 @@ pub fn get_all_entry_types(
 
             Ok(Some(entry_types))
         }
-        _ => Err(ScaffoldError::MultipleEntryTypesDefsFoundForIntegrityZome(
-            zome_file_tree.dna_file_tree.dna_manifest.name(),
-            zome_file_tree.zome_manifest.name.0.to_string(),
-        )),
     }
 }
 
+/// Whether the given entry type is declared with `#[entry_type(visibility = "private")]`
+pub fn is_private_entry_type(
+    zome_file_tree: &ZomeFileTree,
+    entry_type_name: &str,
+) -> ScaffoldResult<bool> {
+    let Some(entry_def_enum) = find_entry_types_enum(zome_file_tree)? else {
+        return Ok(false);
+    };
+
+    Ok(entry_def_enum
+        .variants
+        .iter()
+        .filter(|v| v.ident == entry_type_name.to_case(Case::Pascal))
+        .flat_map(|v| v.attrs.iter())
+        .filter(|a| a.path().is_ident("entry_type"))
+        .any(|a| {
+            let mut private = false;
+            let _ = a.parse_nested_meta(|meta| {
+                let value: syn::Expr = meta.value()?.parse()?;
+                if meta.path.is_ident("visibility") {
+                    private = matches!(
+                        value,
+                        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) if s.value() == "private"
+                    );
+                }
+                Ok(())
+            });
+            private
+        }))
+}
+
 fn add_entry_type_to_validation_arms(
     item: &mut syn::Item,
     entry_def: &EntryDefinition,
@@ -844,6 +1184,383 @@ fn handle_register_delete_arm(
     Ok(())
 }
 
+/// Removes the given entry type from the `#[hdk_entry_types]` enum and from the validation match arms
+pub fn remove_entry_type_from_integrity_zome(
+    zome_file_tree: ZomeFileTree,
+    entry_type_name: &str,
+) -> ScaffoldResult<ZomeFileTree> {
+    let crate_src_path = zome_file_tree.zome_crate_path.join("src");
+    let dna_manifest_path = zome_file_tree.dna_file_tree.dna_manifest_path.clone();
+    let zome_manifest = zome_file_tree.zome_manifest.clone();
+    let pascal_entry_def_name = entry_type_name.to_case(Case::Pascal);
+
+    let mut file_tree = zome_file_tree.dna_file_tree.file_tree();
+
+    map_crate_rust_files(&mut file_tree, &crate_src_path, |_file_path, mut file| {
+        remove_enum_variant(&mut file, "hdk_entry_types", &pascal_entry_def_name);
+        remove_match_arms(&mut file, "EntryTypes", &pascal_entry_def_name);
+        Ok(file)
+    })?;
+
+    let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+    ZomeFileTree::from_zome_manifest(dna_file_tree, zome_manifest)
+}
+
+/// Adds the given fields to the struct of the entry type, with the type definitions they need
+/// and the validation of the entries they are linked from
+pub fn add_fields_to_entry_type(
+    zome_file_tree: ZomeFileTree,
+    entry_type_name: &str,
+    fields: &[FieldDefinition],
+) -> ScaffoldResult<ZomeFileTree> {
+    let existing_fields = get_entry_type_fields(&zome_file_tree, entry_type_name)?;
+    if let Some(field_def) = fields
+        .iter()
+        .find(|f| existing_fields.contains(&f.field_name.to_case(Case::Snake)))
+    {
+        return Err(ScaffoldError::FieldAlreadyExists(
+            field_def.field_name.clone(),
+            entry_type_name.to_owned(),
+        ));
+    }
+
+    let pascal_entry_def_name = entry_type_name.to_case(Case::Pascal);
+    let validate_create_fn = format!("validate_create_{}", entry_type_name.to_case(Case::Snake));
+    let validate_update_fn = format!("validate_update_{}", entry_type_name.to_case(Case::Snake));
+    let constrained_fields: Vec<&FieldDefinition> =
+        fields.iter().filter(|f| f.constraints.is_some()).collect();
+    let immutable_fields: Vec<&FieldDefinition> = fields.iter().filter(|f| f.immutable).collect();
+    let deps: Vec<(&FieldDefinition, &EntryTypeReference)> = fields
+        .iter()
+        .filter_map(|f| match &f.linked_from {
+            Some(Referenceable::EntryType(entry_type_reference)) => Some((f, entry_type_reference)),
+            _ => None,
+        })
+        .collect();
+
+    let crate_src_path = zome_file_tree.zome_crate_path.join("src");
+    let dna_manifest_path = zome_file_tree.dna_file_tree.dna_manifest_path.clone();
+    let zome_manifest = zome_file_tree.zome_manifest.clone();
+    let mut file_tree = zome_file_tree.dna_file_tree.file_tree();
+
+    if needs_regex(fields) {
+        file_tree = add_crate_external_dependency(
+            file_tree,
+            &zome_file_tree.zome_crate_path,
+            "regex",
+            REGEX_VERSION,
+        )?;
+    }
+
+    map_crate_rust_files(&mut file_tree, &crate_src_path, |_file_path, mut file| {
+        if let Some(struct_index) = file.items.iter().position(
+            |i| matches!(i, syn::Item::Struct(s) if is_entry_struct(s, &pascal_entry_def_name)),
+        ) {
+            if let syn::Item::Struct(item_struct) = &mut file.items[struct_index] {
+                if let syn::Fields::Named(named_fields) = &mut item_struct.fields {
+                    for field_def in fields {
+                        let name = format_ident!("{}", field_def.field_name.to_case(Case::Snake));
+                        let rust_type = field_def.rust_type();
+                        named_fields.named.push(
+                            syn::Field::parse_named.parse2(quote! { pub #name: #rust_type })?,
+                        );
+                    }
+                }
+            }
+
+            for field_def in fields {
+                let (FieldType::Enum { label, .. } | FieldType::Struct { label, .. }) =
+                    &field_def.field_type
+                else {
+                    continue;
+                };
+                let already_defined = file.items.iter().any(|i| match i {
+                    syn::Item::Enum(e) => e.ident == label,
+                    syn::Item::Struct(s) => s.ident == label,
+                    _ => false,
+                });
+                if let (false, Some(type_definition)) =
+                    (already_defined, field_def.field_type.rust_type_definition())
+                {
+                    let type_definitions: syn::File = syn::parse2(type_definition)?;
+                    for (i, item) in type_definitions.items.into_iter().enumerate() {
+                        file.items.insert(struct_index + i, item);
+                    }
+                }
+            }
+        }
+
+        if deps.is_empty() && constrained_fields.is_empty() && immutable_fields.is_empty() {
+            return Ok(file);
+        }
+
+        for item in &mut file.items {
+            let syn::Item::Fn(item_fn) = item else {
+                continue;
+            };
+            let is_create = item_fn.sig.ident == validate_create_fn;
+            if !is_create
+                && (item_fn.sig.ident != validate_update_fn
+                    || (constrained_fields.is_empty() && immutable_fields.is_empty()))
+            {
+                continue;
+            }
+
+            // The entry arguments were unused if the entry type had no dependencies, constraints
+            // nor immutable fields
+            let mut use_arg = |index: usize| {
+                let Some(syn::FnArg::Typed(pat_type)) = item_fn.sig.inputs.iter_mut().nth(index)
+                else {
+                    return None;
+                };
+                let syn::Pat::Ident(pat_ident) = pat_type.pat.as_mut() else {
+                    return None;
+                };
+                pat_ident.ident =
+                    format_ident!("{}", pat_ident.ident.to_string().trim_start_matches('_'));
+                Some(pat_ident.ident.clone())
+            };
+            let Some(entry_arg) = use_arg(1) else {
+                continue;
+            };
+            let immutability_validation = match (is_create, immutable_fields.is_empty()) {
+                (false, false) => {
+                    let Some(original_entry_arg) = use_arg(3) else {
+                        continue;
+                    };
+                    immutable_fields
+                        .iter()
+                        .map(|field_def| {
+                            render_immutability_validation(
+                                &pascal_entry_def_name,
+                                &entry_arg,
+                                &original_entry_arg,
+                                field_def,
+                            )
+                        })
+                        .collect()
+                }
+                _ => vec![],
+            };
+
+            let deps_validation =
+                deps.iter()
+                    .filter(|_| is_create)
+                    .map(|(field_def, reference)| {
+                        render_dependency_validation(&entry_arg, field_def, reference)
+                    });
+            let constraints_validation = constrained_fields
+                .iter()
+                .map(|field_def| render_constraints_validation(&entry_arg, field_def))
+                .collect::<ScaffoldResult<Vec<TokenStream>>>()?;
+            let block: syn::Block = syn::parse2(quote! {{
+                #(#deps_validation)*
+                #(#immutability_validation)*
+                #(#constraints_validation)*
+            }})?;
+            let index = item_fn.block.stmts.len().saturating_sub(1);
+            item_fn.block.stmts.splice(index..index, block.stmts);
+        }
+
+        Ok(file)
+    })?;
+
+    let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+    ZomeFileTree::from_zome_manifest(dna_file_tree, zome_manifest)
+}
+
+/// Removes the given fields from the struct of the entry type, together with their validation
+/// and the type definitions that are not used anymore
+pub fn remove_fields_from_entry_type(
+    zome_file_tree: ZomeFileTree,
+    entry_type_name: &str,
+    field_names: &[String],
+) -> ScaffoldResult<ZomeFileTree> {
+    let existing_fields = get_entry_type_fields(&zome_file_tree, entry_type_name)?;
+    let field_names: Vec<String> = field_names.iter().map(|f| f.to_case(Case::Snake)).collect();
+    if let Some(field_name) = field_names.iter().find(|f| !existing_fields.contains(f)) {
+        return Err(ScaffoldError::FieldNotFound(
+            field_name.clone(),
+            entry_type_name.to_owned(),
+        ));
+    }
+
+    let pascal_entry_def_name = entry_type_name.to_case(Case::Pascal);
+    let validate_create_fn = format!("validate_create_{}", entry_type_name.to_case(Case::Snake));
+    let validate_update_fn = format!("validate_update_{}", entry_type_name.to_case(Case::Snake));
+
+    let crate_src_path = zome_file_tree.zome_crate_path.join("src");
+    let dna_manifest_path = zome_file_tree.dna_file_tree.dna_manifest_path.clone();
+    let zome_manifest = zome_file_tree.zome_manifest.clone();
+    let mut file_tree = zome_file_tree.dna_file_tree.file_tree();
+
+    map_crate_rust_files(&mut file_tree, &crate_src_path, |_file_path, mut file| {
+        let is_removed = |field: &syn::Field| {
+            field
+                .ident
+                .as_ref()
+                .is_some_and(|i| field_names.contains(&i.to_string()))
+        };
+        let mut removed_types: Vec<TokenStream> = vec![];
+
+        for item in &mut file.items {
+            match item {
+                syn::Item::Struct(item_struct)
+                    if is_entry_struct(item_struct, &pascal_entry_def_name) =>
+                {
+                    if let syn::Fields::Named(named_fields) = &mut item_struct.fields {
+                        removed_types.extend(
+                            named_fields
+                                .named
+                                .iter()
+                                .filter(|f| is_removed(f))
+                                .map(|f| f.ty.to_token_stream()),
+                        );
+                        named_fields.named = named_fields
+                            .named
+                            .clone()
+                            .into_iter()
+                            .filter(|f| !is_removed(f))
+                            .collect();
+                    }
+                }
+                syn::Item::Fn(item_fn)
+                    if item_fn.sig.ident == validate_create_fn
+                        || item_fn.sig.ident == validate_update_fn =>
+                {
+                    remove_field_validations(item_fn, &field_names);
+                }
+                _ => {}
+            }
+        }
+
+        // Type definitions that were only used by the removed fields
+        let unused_types: Vec<String> = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Enum(item_enum) => Some(item_enum.ident.to_string()),
+                syn::Item::Struct(item_struct) => Some(item_struct.ident.to_string()),
+                _ => None,
+            })
+            .filter(|ident| {
+                removed_types
+                    .iter()
+                    .any(|t| mentions_ident(t.clone(), ident))
+            })
+            .filter(|ident| {
+                !file.items.iter().any(|item| match item {
+                    syn::Item::Enum(item_enum) if item_enum.ident == ident => false,
+                    syn::Item::Struct(item_struct) if item_struct.ident == ident => false,
+                    _ => mentions_ident(item.to_token_stream(), ident),
+                })
+            })
+            .collect();
+        file.items.retain(|item| match item {
+            syn::Item::Enum(item_enum) => !unused_types.contains(&item_enum.ident.to_string()),
+            syn::Item::Struct(item_struct) => {
+                !unused_types.contains(&item_struct.ident.to_string())
+            }
+            _ => true,
+        });
+
+        Ok(file)
+    })?;
+
+    let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+    ZomeFileTree::from_zome_manifest(dna_file_tree, zome_manifest)
+}
+
+fn remove_field_validations(item_fn: &mut syn::ItemFn, field_names: &[String]) {
+    let Some(syn::FnArg::Typed(pat_type)) = item_fn.sig.inputs.iter().nth(1) else {
+        return;
+    };
+    let syn::Pat::Ident(pat_ident) = pat_type.pat.as_ref() else {
+        return;
+    };
+    let entry_arg = pat_ident.ident.to_string();
+
+    let stmts = &item_fn.block.stmts;
+    let removed = statements_to_remove(stmts, |stmt| {
+        field_names
+            .iter()
+            .any(|f| mentions_field(stmt.to_token_stream(), &entry_arg, f))
+    });
+    item_fn.block.stmts = stmts
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !removed.contains(i))
+        .map(|(_, s)| s.clone())
+        .collect();
+
+    // The arguments that only the removed validations used are now unused
+    let block = item_fn.block.to_token_stream();
+    for arg in item_fn.sig.inputs.iter_mut() {
+        let syn::FnArg::Typed(pat_type) = arg else {
+            continue;
+        };
+        let syn::Pat::Ident(pat_ident) = pat_type.pat.as_mut() else {
+            continue;
+        };
+        let arg_name = pat_ident.ident.to_string();
+        if !arg_name.starts_with('_') && !mentions_ident(block.clone(), &arg_name) {
+            pat_ident.ident = format_ident!("_{arg_name}");
+        }
+    }
+}
+
+/// The names of the fields of the struct that defines the given entry type
+fn get_entry_type_fields(
+    zome_file_tree: &ZomeFileTree,
+    entry_type_name: &str,
+) -> ScaffoldResult<Vec<String>> {
+    let pascal_entry_def_name = entry_type_name.to_case(Case::Pascal);
+    let crate_src_path = zome_file_tree.zome_crate_path.join("src");
+    let crate_src_path_iter: Vec<OsString> =
+        crate_src_path.iter().map(|s| s.to_os_string()).collect();
+
+    let fields = find_map_rust_files(
+        zome_file_tree
+            .dna_file_tree
+            .file_tree_ref()
+            .path(&mut crate_src_path_iter.iter())
+            .ok_or(ScaffoldError::PathNotFound(crate_src_path.clone()))?,
+        &|_file_path, rust_file| {
+            rust_file.items.iter().find_map(|item| match item {
+                syn::Item::Struct(item_struct)
+                    if is_entry_struct(item_struct, &pascal_entry_def_name) =>
+                {
+                    Some(
+                        item_struct
+                            .fields
+                            .iter()
+                            .filter_map(|f| f.ident.as_ref().map(|i| i.to_string()))
+                            .collect::<Vec<String>>(),
+                    )
+                }
+                _ => None,
+            })
+        },
+    );
+
+    fields
+        .into_values()
+        .next()
+        .ok_or(ScaffoldError::EntryTypeNotFound(
+            entry_type_name.to_owned(),
+            zome_file_tree.dna_file_tree.dna_manifest.name(),
+            zome_file_tree.zome_manifest.name.to_string(),
+        ))
+}
+
+fn is_entry_struct(item_struct: &syn::ItemStruct, pascal_entry_def_name: &str) -> bool {
+    item_struct.ident == pascal_entry_def_name
+        && item_struct
+            .attrs
+            .iter()
+            .any(|a| a.path().is_ident("hdk_entry_helper"))
+}
+
 pub fn find_ending_match_expr_in_block(block: &mut syn::Block) -> Option<&mut syn::ExprMatch> {
     if let Some(e) = block.stmts.last_mut() {
         match e {
@@ -871,3 +1588,173 @@ fn is_entry(pat: &syn::Pat, entry: &str) -> bool {
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, str::FromStr};
+
+    use build_fs_tree::{dir, file};
+    use holochain_types::prelude::DnaManifest;
+
+    use super::*;
+    use crate::file_tree::FileTree;
+    use crate::scaffold::entry_type::constraints::{ConstraintRule, FieldConstraints};
+
+    fn tokens_without_spaces(tokens: impl ToTokens) -> String {
+        tokens.to_token_stream().to_string().replace(' ', "")
+    }
+
+    fn item_fn(file: &syn::File, name: &str) -> String {
+        tokens_without_spaces(
+            file.items
+                .iter()
+                .find_map(|item| match item {
+                    syn::Item::Fn(f) if f.sig.ident == name => Some(f),
+                    _ => None,
+                })
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn only_the_progenitor_can_modify_progenitor_entries() {
+        let entry_def = EntryDefinition {
+            name: "post".to_string(),
+            fields: vec![],
+            reference_entry_hash: false,
+            visibility: Visibility::Public,
+            permission: Some(Permission::Progenitor),
+        };
+        let file = render_entry_definition_file(
+            &entry_def,
+            &Crud {
+                update: true,
+                delete: true,
+            },
+        )
+        .unwrap();
+
+        for (validate_fn, verb) in [
+            ("validate_update_post", "update"),
+            ("validate_delete_post", "delete"),
+        ] {
+            let validation = item_fn(&file, validate_fn);
+            // Entries can't be modified when the DNA has no progenitor
+            assert!(validation.contains(&format!(
+                "letSome(progenitor)=crate::progenitor()?else{{returnOk(ValidateCallbackResult::Invalid(String::from(\"TheDNAhasnoprogenitorproperty,sonoagentcan{verb}Posts\")));}};"
+            )));
+            assert!(validation.contains(&format!(
+                "ifaction.author!=progenitor{{returnOk(ValidateCallbackResult::Invalid(String::from(\"OnlytheprogenitoroftheDNAcan{verb}Posts\")));}}"
+            )));
+        }
+
+        let progenitor = item_fn(&render_progenitor_file(), "progenitor");
+        assert!(progenitor.contains("ExternResult<Option<AgentPubKey>>"));
+    }
+
+    #[test]
+    fn private_entry_types_are_declared_private() {
+        let dna_manifest = r#"manifest_version: '1'
+name: forum
+integrity:
+  network_seed: null
+  properties: null
+  origin_time: 1792297352899577
+  zomes:
+  - name: posts_integrity
+    hash: null
+    bundled: ../../../target/wasm32-unknown-unknown/release/posts_integrity.wasm
+    dependencies: null
+    dylib: null
+coordinator:
+  zomes: []
+lineage: []
+"#;
+        let file_tree: FileTree = dir! {
+            "Cargo.toml" => file!("[workspace]\nmembers = [\"dnas/*/zomes/integrity/*\"]\nresolver = \"2\"\n"),
+            "dnas" => dir! {
+                "forum" => dir! {
+                    "workdir" => dir! {
+                        "dna.yaml" => file!(dna_manifest)
+                    },
+                    "zomes" => dir! {
+                        "integrity" => dir! {
+                            "posts" => dir! {
+                                "Cargo.toml" => file!("[package]\nname = \"posts_integrity\"\nversion = \"0.0.1\"\nedition = \"2021\"\n"),
+                                "src" => dir! {
+                                    "lib.rs" => file!("use hdi::prelude::*;\n")
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        };
+        let dna_file_tree = DnaFileTree::from_dna_manifest_path(
+            file_tree,
+            Path::new("dnas/forum/workdir/dna.yaml"),
+        )
+        .unwrap();
+        let integrity_zome = match dna_file_tree.dna_manifest.clone() {
+            DnaManifest::V1(m) => m.integrity.zomes[0].clone(),
+        };
+        let mut zome_file_tree =
+            ZomeFileTree::from_zome_manifest(dna_file_tree, integrity_zome).unwrap();
+
+        for (name, visibility) in [("post", Visibility::Public), ("draft", Visibility::Private)] {
+            let entry_def = EntryDefinition {
+                name: name.to_string(),
+                fields: vec![FieldDefinition::from_str("title:String:TextField").unwrap()],
+                reference_entry_hash: false,
+                visibility,
+                permission: None,
+            };
+            zome_file_tree = add_entry_type_to_integrity_zome(
+                zome_file_tree,
+                &entry_def,
+                &Crud {
+                    update: true,
+                    delete: true,
+                },
+            )
+            .unwrap();
+        }
+
+        let entry_types = find_entry_types_enum(&zome_file_tree).unwrap().unwrap();
+        let draft = entry_types
+            .variants
+            .iter()
+            .find(|v| v.ident == "Draft")
+            .unwrap();
+        assert_eq!(
+            tokens_without_spaces(draft),
+            "#[entry_type(visibility=\"private\")]Draft(Draft)"
+        );
+        assert!(is_private_entry_type(&zome_file_tree, "draft").unwrap());
+        assert!(!is_private_entry_type(&zome_file_tree, "post").unwrap());
+    }
+
+    #[test]
+    fn constraints_with_a_bound_that_is_not_an_expression_fail_to_render() {
+        let mut field_def = FieldDefinition::from_str("rating:u32").unwrap();
+        let rule = |value: &str| ConstraintRule {
+            kind: ConstraintKind::Max,
+            value: value.to_string(),
+            message: String::from("rating is too high"),
+        };
+        field_def.constraints = Some(FieldConstraints {
+            rules: vec![rule("5")],
+            sample: String::from("1"),
+            invalid_sample: None,
+        });
+        let entry_arg = format_ident!("rating");
+
+        assert!(render_constraints_validation(&entry_arg, &field_def)
+            .unwrap()
+            .to_string()
+            .contains("rating . rating > 5"));
+
+        field_def.constraints.as_mut().unwrap().rules = vec![rule("5 +")];
+        assert!(render_constraints_validation(&entry_arg, &field_def).is_err());
+    }
+}
@@ -0,0 +1,805 @@
+//! Conversion of a JSON Schema, or of a sample JSON document, into the fields of an entry type
+//!
+//! Properties can be mapped onto holochain types with the custom `x-holochain` keyword, either as
+//! the name of the type (`"x-holochain": "AgentPubKey"`) or as an object that can also set the
+//! entry type or role that the field is linked from, and its widget:
+//! `"x-holochain": { "type": "ActionHash", "linked_from": "post", "widget": "..." }`
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use convert_case::{Case, Casing};
+use serde_json::{Map, Value};
+
+use crate::error::{ScaffoldError, ScaffoldResult};
+
+use super::{
+    constraints::{ConstraintKind, FieldConstraints},
+    definitions::{Cardinality, EntryTypeReference, FieldDefinition, FieldType, Referenceable},
+};
+
+/// Keywords that only annotate a schema, without restricting its values
+const ANNOTATION_KEYWORDS: [&str; 5] =
+    ["title", "description", "$comment", "examples", "deprecated"];
+
+/// Keywords supported in the schema of the entry type, besides the annotations
+const OBJECT_KEYWORDS: [&str; 5] = ["$schema", "$id", "type", "properties", "required"];
+
+/// Keywords supported in the schemas of array properties, besides the annotations
+const ARRAY_KEYWORDS: [&str; 2] = ["type", "items"];
+
+/// Keywords supported in the schemas of single values, besides the annotations
+const VALUE_KEYWORDS: [&str; 10] = [
+    "type",
+    "enum",
+    "x-holochain",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+];
+
+/// Name of the entry type described by the given schema, from its title
+pub fn entry_type_name_from_schema(schema: &Value) -> Option<String> {
+    schema
+        .get("title")
+        .and_then(|t| t.as_str())
+        .map(|t| t.to_case(Case::Snake))
+}
+
+/// Maps the properties of the given JSON Schema, which must describe an object, onto fields
+///
+/// The bounds and patterns of strings and numbers become the constraints of their fields.
+/// Fails with the JSON pointers of all the constructs that can't be mapped, including every keyword
+/// that isn't supported
+pub fn fields_from_schema(schema: &Value) -> ScaffoldResult<Vec<FieldDefinition>> {
+    let mut unsupported = Unsupported::default();
+
+    check_keywords(schema, "#", &OBJECT_KEYWORDS, &mut unsupported);
+
+    if schema.get("type").and_then(|t| t.as_str()) != Some("object") {
+        unsupported.add("#", "the schema must describe an object");
+    }
+    let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) else {
+        unsupported.add("#", "the schema has no properties");
+        return Err(unsupported.into_error());
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|n| n.as_str()).collect())
+        .unwrap_or_default();
+
+    let mut fields = vec![];
+    for (name, property) in properties {
+        let pointer = format!("#/properties/{}", escape_pointer_token(name));
+        if let Some(field) = field_from_property(
+            name,
+            property,
+            required.contains(&name.as_str()),
+            &pointer,
+            &mut unsupported,
+        ) {
+            fields.push(field);
+        }
+    }
+
+    unsupported.into_result(fields)
+}
+
+/// Infers the fields from the values of the given sample object, which are all required
+///
+/// Strings that hold a holochain hash or public key encoded in base64 are mapped to that type
+pub fn fields_from_sample(sample: &Value) -> ScaffoldResult<Vec<FieldDefinition>> {
+    let mut unsupported = Unsupported::default();
+
+    let Some(object) = sample.as_object() else {
+        unsupported.add("#", "the sample must be an object");
+        return Err(unsupported.into_error());
+    };
+
+    let mut fields = vec![];
+    for (name, value) in object {
+        let pointer = format!("#/{}", escape_pointer_token(name));
+        if !is_field_name(name, &pointer, &mut unsupported) {
+            continue;
+        }
+
+        let (field_type, cardinality) = match value {
+            Value::Array(items) => {
+                if items.is_empty() {
+                    unsupported.add(
+                        &pointer,
+                        "the type of the items of an empty array can't be inferred",
+                    );
+                    continue;
+                };
+                let item_types: Option<Vec<FieldType>> = items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| sample_type(item, &format!("{pointer}/{i}"), &mut unsupported))
+                    .collect();
+                let Some(item_types) = item_types else {
+                    continue;
+                };
+                if item_types.iter().any(|t| t != &item_types[0]) {
+                    unsupported.add(&pointer, "the items of the array have different types");
+                    continue;
+                }
+                let field_type = item_types[0].clone();
+                (field_type, Cardinality::Vector)
+            }
+            value => {
+                let Some(field_type) = sample_type(value, &pointer, &mut unsupported) else {
+                    continue;
+                };
+                (field_type, Cardinality::Single)
+            }
+        };
+
+        match FieldDefinition::new(name.clone(), field_type, None, cardinality, None) {
+            Ok(field) => fields.push(field),
+            Err(e) => unsupported.add(&pointer, &e.to_string()),
+        }
+    }
+
+    unsupported.into_result(fields)
+}
+
+/// JSON pointers of the constructs that can't be mapped, with the reason why
+#[derive(Default)]
+struct Unsupported(Vec<String>);
+
+impl Unsupported {
+    fn add(&mut self, pointer: &str, reason: &str) {
+        self.0.push(format!("{pointer} ({reason})"));
+    }
+
+    fn into_error(self) -> ScaffoldError {
+        ScaffoldError::UnsupportedSchemaConstructs(self.0.join(", "))
+    }
+
+    fn into_result<T>(self, value: T) -> ScaffoldResult<T> {
+        if self.0.is_empty() {
+            Ok(value)
+        } else {
+            Err(self.into_error())
+        }
+    }
+}
+
+/// Escapes a property name to be used in a JSON pointer, as described in RFC 6901
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn is_field_name(name: &str, pointer: &str, unsupported: &mut Unsupported) -> bool {
+    let is_snake_case = name.is_case(Case::Snake) && !name.starts_with(|c: char| c.is_numeric());
+    if !is_snake_case {
+        unsupported.add(pointer, "the property name must be snake_case");
+    }
+    is_snake_case
+}
+
+fn field_from_property(
+    name: &str,
+    property: &Value,
+    required: bool,
+    pointer: &str,
+    unsupported: &mut Unsupported,
+) -> Option<FieldDefinition> {
+    if !is_field_name(name, pointer, unsupported) {
+        return None;
+    }
+
+    let (field_type, cardinality, widget, linked_from, value_schema, value_pointer) =
+        if is_array(property) {
+            if !check_keywords(property, pointer, &ARRAY_KEYWORDS, unsupported) {
+                return None;
+            }
+            if is_nullable(property) {
+                unsupported.add(pointer, "optional arrays are not supported");
+                return None;
+            }
+            let Some(items) = property.get("items") else {
+                unsupported.add(pointer, "arrays must define the schema of their items");
+                return None;
+            };
+            let items_pointer = format!("{pointer}/items");
+            let (field_type, nullable, widget, linked_from) =
+                property_type(name, items, &items_pointer, unsupported)?;
+            if nullable || is_array(items) {
+                unsupported.add(
+                    &items_pointer,
+                    "only arrays of required single values are supported",
+                );
+                return None;
+            }
+            (
+                field_type,
+                Cardinality::Vector,
+                widget,
+                linked_from,
+                items,
+                items_pointer,
+            )
+        } else {
+            let (field_type, nullable, widget, linked_from) =
+                property_type(name, property, pointer, unsupported)?;
+            let cardinality = if required && !nullable {
+                Cardinality::Single
+            } else {
+                Cardinality::Option
+            };
+            (
+                field_type,
+                cardinality,
+                widget,
+                linked_from,
+                property,
+                pointer.to_string(),
+            )
+        };
+
+    let constraints =
+        schema_constraints(name, value_schema, &field_type, &value_pointer, unsupported)?;
+
+    let mut field = FieldDefinition::new(
+        name.to_string(),
+        field_type,
+        widget,
+        cardinality,
+        linked_from,
+    )
+    .map_err(|e| unsupported.add(pointer, &e.to_string()))
+    .ok()?;
+    field.constraints = constraints;
+    Some(field)
+}
+
+/// Reports every keyword of the schema that is neither supported nor an annotation
+///
+/// Returns whether all its keywords are supported
+fn check_keywords(
+    schema: &Value,
+    pointer: &str,
+    supported: &[&str],
+    unsupported: &mut Unsupported,
+) -> bool {
+    let Some(schema) = schema.as_object() else {
+        return true;
+    };
+
+    let mut all_supported = true;
+    for keyword in schema.keys() {
+        if !supported.contains(&keyword.as_str())
+            && !ANNOTATION_KEYWORDS.contains(&keyword.as_str())
+        {
+            unsupported.add(
+                &format!("{pointer}/{}", escape_pointer_token(keyword)),
+                "this keyword is not supported",
+            );
+            all_supported = false;
+        }
+    }
+    all_supported
+}
+
+/// Maps the bounds and the pattern of the given schema onto the constraints of a field of the given type
+///
+/// Bounds that the type of the field already enforces are skipped
+fn schema_constraints(
+    name: &str,
+    schema: &Value,
+    field_type: &FieldType,
+    pointer: &str,
+    unsupported: &mut Unsupported,
+) -> Option<Option<FieldConstraints>> {
+    let is_integer = matches!(field_type, FieldType::U8 | FieldType::U32 | FieldType::I32);
+    let is_number = is_integer || matches!(field_type, FieldType::F32);
+    let is_string = matches!(field_type, FieldType::String);
+
+    let mut values = vec![];
+    let mut all_supported = true;
+    for (keyword, kind, applies) in [
+        ("minLength", ConstraintKind::Min, is_string),
+        ("maxLength", ConstraintKind::Max, is_string),
+        ("pattern", ConstraintKind::Regex, is_string),
+        ("minimum", ConstraintKind::Min, is_number),
+        ("maximum", ConstraintKind::Max, is_number),
+        ("exclusiveMinimum", ConstraintKind::Min, is_integer),
+        ("exclusiveMaximum", ConstraintKind::Max, is_integer),
+    ] {
+        let Some(value) = schema.get(keyword) else {
+            continue;
+        };
+        let keyword_pointer = format!("{pointer}/{keyword}");
+        if !applies {
+            unsupported.add(
+                &keyword_pointer,
+                &format!("this keyword can't be mapped onto a {field_type} field"),
+            );
+            all_supported = false;
+            continue;
+        }
+
+        let value = match (value, keyword) {
+            (Value::String(pattern), "pattern") => Some(pattern.clone()),
+            (Value::Number(n), "exclusiveMinimum") => n.as_i64().map(|n| (n + 1).to_string()),
+            (Value::Number(n), "exclusiveMaximum") => n.as_i64().map(|n| (n - 1).to_string()),
+            (Value::Number(n), keyword) if keyword != "pattern" => Some(n.to_string()),
+            _ => None,
+        };
+        let Some(value) = value else {
+            unsupported.add(&keyword_pointer, "the value of this keyword is invalid");
+            all_supported = false;
+            continue;
+        };
+        if !is_implied_by_type(kind, &value, field_type) {
+            values.push((kind, value));
+        }
+    }
+
+    if !all_supported {
+        return None;
+    }
+    if values.is_empty() {
+        return Some(None);
+    }
+    FieldConstraints::new(values, name, field_type)
+        .map_err(|e| unsupported.add(pointer, &e.to_string()))
+        .ok()
+        .map(Some)
+}
+
+/// Whether the given bound is already enforced by the integer type of the field
+fn is_implied_by_type(kind: ConstraintKind, value: &str, field_type: &FieldType) -> bool {
+    let (type_min, type_max) = match field_type {
+        FieldType::U8 => (0, u8::MAX as i64),
+        FieldType::U32 => (0, u32::MAX as i64),
+        FieldType::I32 => (i32::MIN as i64, i32::MAX as i64),
+        _ => return false,
+    };
+    match (kind, value.parse::<i64>()) {
+        (ConstraintKind::Min, Ok(min)) => min <= type_min,
+        (ConstraintKind::Max, Ok(max)) => max >= type_max,
+        _ => false,
+    }
+}
+
+/// Whether the type of the property is "array", or `["array", "null"]`
+fn is_array(property: &Value) -> bool {
+    match property.get("type") {
+        Some(Value::String(t)) => t == "array",
+        Some(Value::Array(types)) => types.iter().any(|t| t == "array"),
+        _ => false,
+    }
+}
+
+fn is_nullable(property: &Value) -> bool {
+    property
+        .get("type")
+        .and_then(|t| t.as_array())
+        .is_some_and(|types| types.iter().any(|t| t == "null"))
+}
+
+/// Maps the given schema onto a field type, whether it can be null,
+/// and its widget and what it's linked from as set with the `x-holochain` keyword
+fn property_type(
+    name: &str,
+    schema: &Value,
+    pointer: &str,
+    unsupported: &mut Unsupported,
+) -> Option<(FieldType, bool, Option<String>, Option<Referenceable>)> {
+    if !check_keywords(schema, pointer, &VALUE_KEYWORDS, unsupported) {
+        return None;
+    }
+
+    if schema.get("x-holochain").is_some() {
+        holochain_type(schema, pointer, unsupported)
+    } else {
+        let (field_type, nullable) = schema_type(name, schema, pointer, unsupported)?;
+        Some((field_type, nullable, None, None))
+    }
+}
+
+/// Maps the standard keywords of the given schema onto a field type, and whether it can be null
+fn schema_type(
+    name: &str,
+    schema: &Value,
+    pointer: &str,
+    unsupported: &mut Unsupported,
+) -> Option<(FieldType, bool)> {
+    let (type_name, nullable) = match schema.get("type") {
+        Some(Value::String(t)) => (Some(t.as_str()), false),
+        Some(Value::Array(types)) => {
+            let non_null: Vec<&str> = types
+                .iter()
+                .filter_map(|t| t.as_str())
+                .filter(|t| *t != "null")
+                .collect();
+            if non_null.len() != 1 {
+                unsupported.add(
+                    &format!("{pointer}/type"),
+                    "only a single type, optionally with \"null\", is supported",
+                );
+                return None;
+            }
+            (Some(non_null[0]), non_null.len() < types.len())
+        }
+        Some(_) => {
+            unsupported.add(
+                &format!("{pointer}/type"),
+                "the type must be a string or an array of strings",
+            );
+            return None;
+        }
+        None => (None, false),
+    };
+
+    if let Some(variants) = schema.get("enum") {
+        let variants: Option<Vec<String>> = variants
+            .as_array()
+            .map(|v| {
+                v.iter()
+                    .filter(|v| !v.is_null())
+                    .map(|v| v.as_str().map(|s| s.to_case(Case::Pascal)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let Some(variants) = variants.filter(|v| !v.is_empty()) else {
+            unsupported.add(
+                &format!("{pointer}/enum"),
+                "only enums of strings are supported",
+            );
+            return None;
+        };
+        let label = schema
+            .get("title")
+            .and_then(|t| t.as_str())
+            .unwrap_or(name)
+            .to_case(Case::Pascal);
+
+        return Some((
+            FieldType::Enum {
+                label,
+                variants,
+                variant_fields: BTreeMap::new(),
+            },
+            nullable,
+        ));
+    }
+
+    let field_type = match type_name {
+        Some("string") => FieldType::String,
+        Some("boolean") => FieldType::Bool,
+        Some("number") => FieldType::F32,
+        Some("integer") => integer_type(schema, pointer, unsupported)?,
+        Some(other) => {
+            unsupported.add(
+                &format!("{pointer}/type"),
+                &format!("type \"{other}\" is not supported"),
+            );
+            return None;
+        }
+        None => {
+            unsupported.add(pointer, "the property has no type");
+            return None;
+        }
+    };
+
+    Some((field_type, nullable))
+}
+
+/// Picks the smallest integer type that holds the range of the given schema
+fn integer_type(schema: &Value, pointer: &str, unsupported: &mut Unsupported) -> Option<FieldType> {
+    let bound = |keyword: &str, exclusive: &str, offset: f64| {
+        schema.get(keyword).and_then(|b| b.as_f64()).or_else(|| {
+            schema
+                .get(exclusive)
+                .and_then(|b| b.as_f64())
+                .map(|b| b + offset)
+        })
+    };
+    let minimum = bound("minimum", "exclusiveMinimum", 1.0);
+    let maximum = bound("maximum", "exclusiveMaximum", -1.0);
+
+    match minimum {
+        Some(minimum) if minimum >= 0.0 => match maximum {
+            Some(maximum) if maximum <= u8::MAX as f64 => Some(FieldType::U8),
+            Some(maximum) if maximum > u32::MAX as f64 => {
+                unsupported.add(pointer, "the maximum doesn't fit in u32");
+                None
+            }
+            _ => Some(FieldType::U32),
+        },
+        _ if minimum.is_some_and(|m| m < i32::MIN as f64)
+            || maximum.is_some_and(|m| m > i32::MAX as f64) =>
+        {
+            unsupported.add(pointer, "the range of the integer doesn't fit in i32");
+            None
+        }
+        _ => Some(FieldType::I32),
+    }
+}
+
+/// Maps the `x-holochain` keyword of the given schema onto a field type, whether it can be null,
+/// its widget and what it's linked from
+fn holochain_type(
+    schema: &Value,
+    pointer: &str,
+    unsupported: &mut Unsupported,
+) -> Option<(FieldType, bool, Option<String>, Option<Referenceable>)> {
+    let keyword_pointer = format!("{pointer}/x-holochain");
+    let (type_name, options) = match schema.get("x-holochain") {
+        Some(Value::String(t)) => (t.as_str(), Map::new()),
+        Some(Value::Object(options)) => match options.get("type").and_then(|t| t.as_str()) {
+            Some(t) => (t, options.clone()),
+            None => {
+                unsupported.add(
+                    &format!("{keyword_pointer}/type"),
+                    "the holochain type is missing",
+                );
+                return None;
+            }
+        },
+        _ => {
+            unsupported.add(
+                &keyword_pointer,
+                "must be the name of a holochain type or an object",
+            );
+            return None;
+        }
+    };
+
+    let field_type = match FieldType::from_str(type_name) {
+        Ok(FieldType::Enum { .. } | FieldType::Struct { .. }) | Err(_) => {
+            unsupported.add(
+                &keyword_pointer,
+                &format!("\"{type_name}\" is not a holochain type"),
+            );
+            return None;
+        }
+        Ok(field_type) => field_type,
+    };
+
+    let nullable = is_nullable(schema);
+    let widget = options
+        .get("widget")
+        .and_then(|w| w.as_str())
+        .map(String::from);
+
+    let linked_from = match options.get("linked_from") {
+        None => None,
+        Some(Value::String(linked_from)) => match field_type {
+            FieldType::AgentPubKey => Some(Referenceable::Agent {
+                role: linked_from.to_case(Case::Snake),
+            }),
+            FieldType::ActionHash | FieldType::EntryHash => {
+                Some(Referenceable::EntryType(EntryTypeReference {
+                    entry_type: linked_from.to_case(Case::Snake),
+                    reference_entry_hash: matches!(field_type, FieldType::EntryHash),
+                }))
+            }
+            _ => {
+                unsupported.add(
+                    &format!("{keyword_pointer}/linked_from"),
+                    "only AgentPubKey, ActionHash and EntryHash fields can be linked from",
+                );
+                return None;
+            }
+        },
+        Some(_) => {
+            unsupported.add(
+                &format!("{keyword_pointer}/linked_from"),
+                "must be a string",
+            );
+            return None;
+        }
+    };
+
+    Some((field_type, nullable, widget, linked_from))
+}
+
+/// Infers the type of a value of a sample document
+fn sample_type(value: &Value, pointer: &str, unsupported: &mut Unsupported) -> Option<FieldType> {
+    match value {
+        Value::Bool(_) => Some(FieldType::Bool),
+        Value::String(s) => Some(hash_type(s).unwrap_or(FieldType::String)),
+        Value::Number(n) if n.is_f64() => Some(FieldType::F32),
+        Value::Number(n) => match n.as_i64() {
+            Some(n) if i32::try_from(n).is_ok() => Some(FieldType::I32),
+            Some(n) if u32::try_from(n).is_ok() => Some(FieldType::U32),
+            _ => {
+                unsupported.add(pointer, "the integer doesn't fit in u32 nor i32");
+                None
+            }
+        },
+        Value::Null => {
+            unsupported.add(pointer, "the type of a null value can't be inferred");
+            None
+        }
+        Value::Array(_) => {
+            unsupported.add(pointer, "nested arrays are not supported");
+            None
+        }
+        Value::Object(_) => {
+            unsupported.add(pointer, "nested objects are not supported");
+            None
+        }
+    }
+}
+
+/// The holochain type of a base64 encoded hash, from its prefix
+fn hash_type(s: &str) -> Option<FieldType> {
+    // 'u' for the multibase encoding, followed by the 39 bytes of the hash
+    if s.len() != 53 {
+        return None;
+    }
+    match &s[..5] {
+        "uhCAk" => Some(FieldType::AgentPubKey),
+        "uhCkk" => Some(FieldType::ActionHash),
+        "uhCEk" => Some(FieldType::EntryHash),
+        "uhC0k" => Some(FieldType::DnaHash),
+        "uhC8k" => Some(FieldType::ExternalHash),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn maps_schema_properties_onto_fields() {
+        let schema = json!({
+            "title": "BlogPost",
+            "type": "object",
+            "required": ["title", "author", "rating", "status"],
+            "properties": {
+                "title": { "type": "string" },
+                "summary": { "type": "string" },
+                "published": { "type": ["boolean", "null"] },
+                "rating": { "type": "integer", "minimum": 0, "maximum": 5 },
+                "status": { "enum": ["draft", "published"] },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "author": { "type": "string", "x-holochain": { "type": "AgentPubKey", "linked_from": "author" } },
+                "reply_to": { "type": "string", "x-holochain": { "type": "ActionHash", "linked_from": "post" } }
+            }
+        });
+
+        let fields = fields_from_schema(&schema).unwrap();
+
+        assert_eq!(
+            entry_type_name_from_schema(&schema).as_deref(),
+            Some("blog_post")
+        );
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|f| format!("{}:{}", f.field_name, f.rust_type()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                "title:String",
+                "summary:Option < String >",
+                "published:Option < bool >",
+                "rating:u8",
+                "status:Status",
+                "tags:Vec < String >",
+                "author:AgentPubKey",
+                "reply_to:Option < ActionHash >",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unsupported_constructs_with_their_json_pointers() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string" },
+                "address": { "type": "object" },
+                "tags": { "type": "array", "items": { "$ref": "#/$defs/tag" } }
+            }
+        });
+
+        let error = fields_from_schema(&schema).unwrap_err().to_string();
+
+        assert!(error.contains("#/properties/address/type"));
+        assert!(error.contains("#/properties/tags/items/$ref"));
+        assert!(!error.contains("#/properties/title"));
+    }
+
+    #[test]
+    fn reports_every_keyword_that_is_not_supported() {
+        let schema = json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "title": { "type": "string", "description": "The title", "format": "email" },
+                "tags": { "type": "array", "items": { "type": "string" }, "uniqueItems": true },
+                "author": { "type": "string", "x-holochain": "AgentPubKey", "minLength": 1 },
+                "score": { "type": "number", "exclusiveMinimum": 0 }
+            }
+        });
+
+        let error = fields_from_schema(&schema).unwrap_err().to_string();
+
+        assert!(error.contains("#/additionalProperties"));
+        assert!(error.contains("#/properties/title/format"));
+        assert!(!error.contains("#/properties/title/description"));
+        assert!(error.contains("#/properties/tags/uniqueItems"));
+        assert!(error.contains("#/properties/author/minLength"));
+        assert!(error.contains("#/properties/score/exclusiveMinimum"));
+    }
+
+    #[test]
+    fn maps_bounds_and_patterns_onto_constraints() {
+        let schema = json!({
+            "type": "object",
+            "required": ["slug", "rating", "tags"],
+            "properties": {
+                "slug": { "type": "string", "minLength": 1, "maxLength": 60, "pattern": "^[a-z-]+$" },
+                "rating": { "type": "integer", "minimum": 0, "exclusiveMaximum": 6 },
+                "tags": { "type": "array", "items": { "type": "string", "maxLength": 20 } },
+                "score": { "type": "number", "minimum": 0.5 }
+            }
+        });
+
+        let fields = fields_from_schema(&schema).unwrap();
+
+        let rules: Vec<Vec<(ConstraintKind, String)>> = fields
+            .iter()
+            .map(|f| {
+                f.constraints
+                    .iter()
+                    .flat_map(|c| c.rules.iter().map(|r| (r.kind, r.value.clone())))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                vec![
+                    (ConstraintKind::Min, "1".to_string()),
+                    (ConstraintKind::Max, "60".to_string()),
+                    (ConstraintKind::Regex, "^[a-z-]+$".to_string()),
+                ],
+                vec![(ConstraintKind::Max, "5".to_string())],
+                vec![(ConstraintKind::Max, "20".to_string())],
+                vec![(ConstraintKind::Min, "0.5".to_string())],
+            ]
+        );
+    }
+
+    #[test]
+    fn infers_fields_from_a_sample() {
+        let sample = json!({
+            "title": "Hello",
+            "likes": 3,
+            "author": "uhCAkWCsAgoKkkfwyJAglj30xX_GLLV-3BXuFy436a2SqpcEwyBzm",
+            "tags": ["a", "b"]
+        });
+
+        let fields: Vec<String> = fields_from_sample(&sample)
+            .unwrap()
+            .iter()
+            .map(|f| format!("{}:{}", f.field_name, f.rust_type()))
+            .collect();
+
+        assert_eq!(
+            fields,
+            vec![
+                "title:String",
+                "likes:i32",
+                "author:AgentPubKey",
+                "tags:Vec < String >"
+            ]
+        );
+    }
+}
@@ -7,10 +7,18 @@ use crate::{
     error::{ScaffoldError, ScaffoldResult},
     reserved_words::check_for_reserved_keywords,
     scaffold::zome::ZomeFileTree,
-    utils::input_with_case,
+    utils::{input_with_case, Interactivity},
 };
 
-pub fn choose_reference_entry_hash(prompt: &str, recommended: bool) -> ScaffoldResult<bool> {
+pub fn choose_reference_entry_hash(
+    prompt: &str,
+    recommended: bool,
+    interactivity: Interactivity,
+) -> ScaffoldResult<bool> {
+    if !interactivity.is_interactive() {
+        return Ok(recommended);
+    }
+
     let options = if recommended {
         [("EntryHash", true), ("ActionHash", false)]
     } else {
@@ -33,6 +41,7 @@ pub fn get_or_choose_referenceable(
     zome_file_tree: &ZomeFileTree,
     entry_type: Option<&Referenceable>,
     all_entries: &[EntryTypeReference],
+    interactivity: Interactivity,
 ) -> ScaffoldResult<Referenceable> {
     match &entry_type {
         Some(Referenceable::Agent { role }) => {
@@ -54,7 +63,7 @@ pub fn get_or_choose_referenceable(
 
             Ok(Referenceable::EntryType(app_entry_reference.clone()))
         }
-        _ => choose_referenceable(all_entries, prompt),
+        _ => choose_referenceable(all_entries, prompt, interactivity),
     }
 }
 
@@ -63,6 +72,7 @@ pub fn get_or_choose_optional_reference_type(
     zome_file_tree: &ZomeFileTree,
     entry_type: Option<&Referenceable>,
     all_entries: &[EntryTypeReference],
+    interactivity: Interactivity,
 ) -> ScaffoldResult<Option<Referenceable>> {
     match entry_type {
         Some(Referenceable::Agent { .. }) => Ok(entry_type.cloned()),
@@ -81,26 +91,30 @@ pub fn get_or_choose_optional_reference_type(
 
             Ok(entry_type.cloned())
         }
-        _ => choose_optional_referenceable(all_entries, prompt),
+        _ => choose_optional_referenceable(all_entries, prompt, interactivity),
     }
 }
 
 pub fn choose_referenceable(
     all_entries: &[EntryTypeReference],
     prompt: &str,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<Referenceable> {
-    let maybe_reference_type = inner_choose_referenceable(all_entries, prompt, None)?;
+    let maybe_reference_type =
+        inner_choose_referenceable(all_entries, prompt, None, interactivity)?;
     Ok(maybe_reference_type.context("Reference type should not be None")?)
 }
 
 pub fn choose_optional_referenceable(
     all_entries: &[EntryTypeReference],
     prompt: &str,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<Option<Referenceable>> {
     inner_choose_referenceable(
         all_entries,
         prompt,
         Some(vec!["[None] (Use this link to attach meta-data only)"]),
+        interactivity,
     )
 }
 
@@ -108,7 +122,10 @@ fn inner_choose_referenceable(
     all_entries: &[EntryTypeReference],
     prompt: &str,
     extra_options: Option<Vec<&str>>,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<Option<Referenceable>> {
+    interactivity.ensure_can_prompt(prompt)?;
+
     let mut all_options: Vec<String> = all_entries
         .iter()
         .map(|r| r.entry_type.to_owned())
@@ -133,6 +150,7 @@ fn inner_choose_referenceable(
             "Which role does this agent play in the relationship ? (eg. \"creator\", \"invitee\")",
             None,
             Case::Snake,
+            interactivity,
         )?;
             check_for_reserved_keywords(&role)?;
             Ok(Some(Referenceable::Agent { role }))
@@ -142,6 +160,7 @@ fn inner_choose_referenceable(
                 "What name should be given to the link for this hash?",
                 None,
                 Case::Snake,
+                interactivity,
             )?;
             Ok(Some(Referenceable::ExternalHash { name }))
         }
@@ -151,6 +170,7 @@ fn inner_choose_referenceable(
             reference_entry_hash: choose_reference_entry_hash(
                 "Reference this entry type with its entry hash or its action hash?",
                 all_entries[selection].reference_entry_hash,
+                interactivity,
             )?,
         }))),
     }
@@ -159,7 +179,10 @@ fn inner_choose_referenceable(
 pub fn choose_entry_type_reference(
     all_entries: &[EntryTypeReference],
     prompt: &str,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<EntryTypeReference> {
+    interactivity.ensure_can_prompt(prompt)?;
+
     let all_options: Vec<String> = all_entries.iter().cloned().map(|r| r.entry_type).collect();
 
     let selection = Select::with_theme(&ColorfulTheme::default())
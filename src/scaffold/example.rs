@@ -1,7 +1,25 @@
-use crate::error::{ScaffoldError, ScaffoldResult};
+use crate::{
+    error::{ScaffoldError, ScaffoldResult},
+    file_tree::FileTree,
+    templates::ScaffoldedTemplate,
+    utils::Interactivity,
+};
 use dialoguer::{theme::ColorfulTheme, Select};
 use serde::Serialize;
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
+
+use super::{
+    app::AppFileTree,
+    collection::{scaffold_collection, CollectionType},
+    dna::{scaffold_dna, DnaFileTree},
+    entry_type::{
+        crud::Crud,
+        definitions::{Cardinality, EntryTypeReference, FieldDefinition, FieldType, Referenceable},
+        scaffold_entry_type,
+    },
+    web_app::{package_manager::PackageManager, scaffold_web_app},
+    zome::{scaffold_coordinator_zome_in_path, scaffold_integrity_zome_with_path, ZomeFileTree},
+};
 
 #[derive(Serialize, Debug, Clone)]
 #[serde(tag = "type")]
@@ -11,7 +29,9 @@ pub enum ExampleType {
 }
 
 impl ExampleType {
-    pub fn choose() -> ScaffoldResult<Self> {
+    pub fn choose(interactivity: Interactivity) -> ScaffoldResult<Self> {
+        interactivity.ensure_interactive("<example>")?;
+
         let examples = [ExampleType::Forum, ExampleType::HelloWorld];
         let selection = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("Choose example:")
@@ -22,7 +42,9 @@ impl ExampleType {
         Ok(examples[selection].clone())
     }
 
-    pub fn choose_non_vanilla() -> ScaffoldResult<Self> {
+    pub fn choose_non_vanilla(interactivity: Interactivity) -> ScaffoldResult<Self> {
+        interactivity.ensure_interactive("<example>")?;
+
         let examples = [ExampleType::Forum];
         let selection = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("Choose example:")
@@ -58,3 +80,209 @@ impl FromStr for ExampleType {
         }
     }
 }
+
+/// Scaffolds the app of the given example in memory, before the example templates are rendered on it
+pub fn example_file_tree(
+    example: &ExampleType,
+    package_manager: PackageManager,
+    template_file_tree: &FileTree,
+    holo_enabled: bool,
+    interactivity: Interactivity,
+) -> ScaffoldResult<FileTree> {
+    let example_name = example.to_string();
+
+    match example {
+        ExampleType::HelloWorld => {
+            // scaffold web-app
+            let ScaffoldedTemplate { file_tree, .. } = scaffold_web_app(
+                &example_name,
+                Some("A simple 'hello world' application."),
+                package_manager,
+                false,
+                template_file_tree,
+                holo_enabled,
+                interactivity,
+            )?;
+
+            Ok(file_tree)
+        }
+        ExampleType::Forum => {
+            // scaffold web-app
+            let ScaffoldedTemplate { file_tree, .. } = scaffold_web_app(
+                &example_name,
+                Some("A simple 'forum' application."),
+                package_manager,
+                false,
+                template_file_tree,
+                holo_enabled,
+                interactivity,
+            )?;
+
+            // scaffold dna hello_world
+            let dna_name = "forum";
+
+            let app_file_tree =
+                AppFileTree::get_or_choose(file_tree, Some(&example_name), interactivity)?;
+            let ScaffoldedTemplate { file_tree, .. } =
+                scaffold_dna(app_file_tree, template_file_tree, dna_name, interactivity)?;
+
+            // scaffold integrity zome posts
+            let dna_file_tree =
+                DnaFileTree::get_or_choose(file_tree, Some(dna_name), interactivity)?;
+            let dna_manifest_path = dna_file_tree.dna_manifest_path.clone();
+
+            let integrity_zome_name = "posts_integrity";
+            let integrity_zome_path = PathBuf::new()
+                .join("dnas")
+                .join(dna_name)
+                .join("zomes")
+                .join("integrity");
+            let ScaffoldedTemplate { file_tree, .. } = scaffold_integrity_zome_with_path(
+                dna_file_tree,
+                template_file_tree,
+                integrity_zome_name,
+                &integrity_zome_path,
+                interactivity,
+            )?;
+
+            let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+
+            let coordinator_zome_name = "posts";
+            let coordinator_zome_path = PathBuf::new()
+                .join("dnas")
+                .join(dna_name)
+                .join("zomes")
+                .join("coordinator");
+            let ScaffoldedTemplate { file_tree, .. } = scaffold_coordinator_zome_in_path(
+                dna_file_tree,
+                template_file_tree,
+                coordinator_zome_name,
+                Some(&vec![integrity_zome_name.to_owned()]),
+                &coordinator_zome_path,
+                interactivity,
+            )?;
+
+            let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+
+            let zome_file_tree = ZomeFileTree::get_or_choose_integrity(
+                dna_file_tree,
+                Some(integrity_zome_name),
+                interactivity,
+            )?;
+
+            let post_entry_type_name = "post";
+
+            let ScaffoldedTemplate { file_tree, .. } = scaffold_entry_type(
+                zome_file_tree,
+                template_file_tree,
+                "post",
+                Some(Crud {
+                    update: true,
+                    delete: true,
+                }),
+                Some(false),
+                Some(true),
+                false,
+                None,
+                None,
+                Some(&vec![
+                    FieldDefinition {
+                        field_name: "title".to_string(),
+                        field_type: FieldType::String,
+                        widget: Some("TextField".to_string()),
+                        cardinality: Cardinality::Single,
+                        linked_from: None,
+                        constraints: None,
+                        immutable: false,
+                    },
+                    FieldDefinition {
+                        field_name: "content".to_string(),
+                        field_type: FieldType::String,
+                        widget: Some("TextArea".to_string()),
+                        cardinality: Cardinality::Single,
+                        linked_from: None,
+                        constraints: None,
+                        immutable: false,
+                    },
+                ]),
+                false,
+                false,
+                interactivity,
+            )?;
+
+            let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+
+            let zome_file_tree = ZomeFileTree::get_or_choose_integrity(
+                dna_file_tree,
+                Some("posts_integrity"),
+                interactivity,
+            )?;
+
+            let ScaffoldedTemplate { file_tree, .. } = scaffold_entry_type(
+                zome_file_tree,
+                template_file_tree,
+                "comment",
+                Some(Crud {
+                    update: false,
+                    delete: true,
+                }),
+                Some(false),
+                Some(true),
+                false,
+                None,
+                None,
+                Some(&vec![
+                    FieldDefinition {
+                        field_name: "comment".to_string(),
+                        field_type: FieldType::String,
+                        widget: Some("TextArea".to_string()),
+                        cardinality: Cardinality::Single,
+                        linked_from: None,
+                        constraints: None,
+                        immutable: false,
+                    },
+                    FieldDefinition {
+                        field_name: "post_hash".to_string(),
+                        field_type: FieldType::ActionHash,
+                        widget: None,
+                        cardinality: Cardinality::Single,
+                        linked_from: Some(Referenceable::EntryType(EntryTypeReference {
+                            entry_type: post_entry_type_name.to_string(),
+                            reference_entry_hash: false,
+                        })),
+                        constraints: None,
+                        immutable: false,
+                    },
+                ]),
+                false,
+                false,
+                interactivity,
+            )?;
+
+            let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+
+            let zome_file_tree = ZomeFileTree::get_or_choose_integrity(
+                dna_file_tree,
+                Some(integrity_zome_name),
+                interactivity,
+            )?;
+
+            let ScaffoldedTemplate { file_tree, .. } = scaffold_collection(
+                zome_file_tree,
+                template_file_tree,
+                "all_posts",
+                Some(CollectionType::Global),
+                Some(EntryTypeReference {
+                    entry_type: "post".to_string(),
+                    reference_entry_hash: false,
+                }),
+                false,
+                false,
+                false,
+                interactivity,
+            )?;
+
+            Ok(file_tree)
+        }
+    }
+}
@@ -0,0 +1,685 @@
+//! Declarative description of a hApp, that can be scaffolded in one go with `hc-scaffold apply`
+//!
+//! ```yaml
+//! app: forum # Optional, only needed if there are multiple apps in the project
+//! dnas:
+//!   - name: forum
+//!     zomes:
+//!       - name: posts # Coordinator zome, its integrity zome will be named "posts_integrity"
+//!         entry_types:
+//!           - name: post
+//!             crud: crud
+//!             reference_entry_hash: false
+//!             link_from_original_to_each_update: true
+//!             visibility: public
+//!             fields:
+//!               - title:String:TextField
+//!               - content:String:TextArea
+//!         link_types:
+//!           - from: post
+//!             to: agent:creator
+//!             bidirectional: false
+//!             delete: true
+//!         collections:
+//!           - name: all_posts
+//!             type: global
+//!             entry_type: post
+//! ```
+//!
+//! Applying a spec is idempotent: every item that already exists in the project is skipped.
+
+use std::{path::Path, str::FromStr};
+
+use convert_case::{Case, Casing};
+use holochain_types::prelude::DnaManifest;
+use serde::Deserialize;
+
+use crate::{
+    error::{ScaffoldError, ScaffoldResult},
+    file_tree::FileTree,
+    templates::ScaffoldedTemplate,
+    utils::Interactivity,
+};
+
+use super::{
+    app::AppFileTree,
+    collection::{scaffold_collection, CollectionType},
+    dna::{find_dna_manifests, scaffold_dna, DnaFileTree},
+    entry_type::{
+        crud::Crud,
+        definitions::{EntryTypeReference, FieldDefinition, Permission, Referenceable, Visibility},
+        integrity::get_all_entry_types,
+        scaffold_entry_type,
+    },
+    link_type::{integrity::get_all_link_types, link_type_name, scaffold_link_type},
+    zome::{
+        integrity_zome_name, scaffold_coordinator_zome_in_path, scaffold_integrity_zome_with_path,
+        ZomeFileTree,
+    },
+};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HappSpec {
+    /// Name of the app the DNAs belong to
+    pub app: Option<String>,
+    #[serde(default)]
+    pub dnas: Vec<DnaSpec>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DnaSpec {
+    pub name: String,
+    #[serde(default)]
+    pub zomes: Vec<ZomeSpec>,
+}
+
+/// An integrity/coordinator zome pair, named after its coordinator zome
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ZomeSpec {
+    pub name: String,
+    #[serde(default)]
+    pub entry_types: Vec<EntryTypeSpec>,
+    #[serde(default)]
+    pub link_types: Vec<LinkTypeSpec>,
+    #[serde(default)]
+    pub collections: Vec<CollectionSpec>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EntryTypeSpec {
+    pub name: String,
+    /// Same syntax as the `--crud` argument, eg. "crud" or "cr"
+    #[serde(default = "default_crud")]
+    pub crud: String,
+    #[serde(default)]
+    pub reference_entry_hash: bool,
+    #[serde(default = "default_true")]
+    pub link_from_original_to_each_update: bool,
+    #[serde(default)]
+    pub paginated_revisions: bool,
+    /// "public" or "private"
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// Same syntax as the `--permission` argument, eg. "author" or "editors:editors"
+    #[serde(default)]
+    pub permission: Option<String>,
+    /// Same syntax as the `--fields` argument, eg. "title:String:TextField"
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LinkTypeSpec {
+    /// Same syntax as the `<from-referenceable>` argument, eg. "post" or "agent:creator"
+    pub from: String,
+    /// Same syntax as the `<to-referenceable>` argument
+    pub to: String,
+    #[serde(default)]
+    pub bidirectional: bool,
+    #[serde(default)]
+    pub delete: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CollectionSpec {
+    pub name: String,
+    /// "global", "by-author" or "by-time:<granularity>", eg. "by-time:day"
+    #[serde(rename = "type")]
+    pub collection_type: String,
+    pub entry_type: String,
+    #[serde(default)]
+    pub paginated: bool,
+}
+
+fn default_crud() -> String {
+    String::from("crud")
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl HappSpec {
+    /// Parses a hApp spec, in YAML or JSON format
+    pub fn from_file_content(spec_path: &Path, content: &str) -> ScaffoldResult<HappSpec> {
+        serde_yaml::from_str(content)
+            .map_err(|e| ScaffoldError::MalformedFile(spec_path.to_path_buf(), e.to_string()))
+    }
+}
+
+pub struct AppliedHappSpec {
+    pub file_tree: FileTree,
+    /// Description of every item that was scaffolded, in order
+    pub scaffolded: Vec<String>,
+    /// Description of every item that was skipped because it already existed
+    pub skipped: Vec<String>,
+}
+
+/// Scaffolds every item of the spec that doesn't exist yet in the given project file tree
+///
+/// All items are scaffolded in memory, in dependency order: DNAs, zomes, entry types, link types and collections
+pub fn apply_happ_spec(
+    file_tree: FileTree,
+    template_file_tree: &FileTree,
+    spec: &HappSpec,
+    no_ui: bool,
+    no_spec: bool,
+    interactivity: Interactivity,
+) -> ScaffoldResult<AppliedHappSpec> {
+    let mut applied = AppliedHappSpec {
+        file_tree,
+        scaffolded: vec![],
+        skipped: vec![],
+    };
+
+    for dna in &spec.dnas {
+        applied = apply_dna(
+            applied,
+            template_file_tree,
+            spec.app.as_deref(),
+            dna,
+            interactivity,
+        )?;
+
+        for zome in &dna.zomes {
+            applied = apply_zome_pair(applied, template_file_tree, &dna.name, zome, interactivity)?;
+
+            let integrity_zome = integrity_zome_name(&zome.name);
+
+            for entry_type in sort_entry_types_by_dependencies(&zome.entry_types)? {
+                applied = apply_entry_type(
+                    applied,
+                    template_file_tree,
+                    &dna.name,
+                    &integrity_zome,
+                    entry_type,
+                    no_ui,
+                    no_spec,
+                    interactivity,
+                )?;
+            }
+
+            for link_type in &zome.link_types {
+                applied = apply_link_type(
+                    applied,
+                    template_file_tree,
+                    &dna.name,
+                    &integrity_zome,
+                    link_type,
+                    no_ui,
+                    no_spec,
+                    interactivity,
+                )?;
+            }
+
+            for collection in &zome.collections {
+                applied = apply_collection(
+                    applied,
+                    template_file_tree,
+                    &dna.name,
+                    &integrity_zome,
+                    collection,
+                    no_ui,
+                    no_spec,
+                    interactivity,
+                )?;
+            }
+        }
+    }
+
+    Ok(applied)
+}
+
+fn apply_dna(
+    mut applied: AppliedHappSpec,
+    template_file_tree: &FileTree,
+    app_name: Option<&str>,
+    dna: &DnaSpec,
+    interactivity: Interactivity,
+) -> ScaffoldResult<AppliedHappSpec> {
+    let description = format!("DNA {}", dna.name);
+
+    if find_dna_manifests(&applied.file_tree)?
+        .values()
+        .any(|m| m.name() == dna.name)
+    {
+        applied.skipped.push(description);
+        return Ok(applied);
+    }
+
+    let app_file_tree = AppFileTree::get_or_choose(applied.file_tree, app_name, interactivity)?;
+    let ScaffoldedTemplate { file_tree, .. } =
+        scaffold_dna(app_file_tree, template_file_tree, &dna.name, interactivity)?;
+
+    applied.file_tree = file_tree;
+    applied.scaffolded.push(description);
+    Ok(applied)
+}
+
+fn apply_zome_pair(
+    mut applied: AppliedHappSpec,
+    template_file_tree: &FileTree,
+    dna_name: &str,
+    zome: &ZomeSpec,
+    interactivity: Interactivity,
+) -> ScaffoldResult<AppliedHappSpec> {
+    let integrity_zome = integrity_zome_name(&zome.name);
+
+    let dna_file_tree =
+        DnaFileTree::get_or_choose(applied.file_tree, Some(dna_name), interactivity)?;
+    let dna_manifest_path = dna_file_tree.dna_manifest_path.clone();
+    let (integrity_exists, coordinator_exists) = match &dna_file_tree.dna_manifest {
+        DnaManifest::V1(v1) => (
+            v1.integrity
+                .zomes
+                .iter()
+                .any(|z| z.name.0.eq(&integrity_zome)),
+            v1.coordinator.zomes.iter().any(|z| z.name.0.eq(&zome.name)),
+        ),
+    };
+
+    // Zomes are scaffolded next to the "workdir" folder of the DNA, as `scaffold_dna` lays it out
+    let zomes_path = dna_manifest_path
+        .parent()
+        .and_then(|workdir| workdir.parent())
+        .map(|dna_path| dna_path.join("zomes"))
+        .unwrap_or_default();
+
+    let mut file_tree = dna_file_tree.file_tree();
+
+    for (zome_name, exists, is_integrity) in [
+        (&integrity_zome, integrity_exists, true),
+        (&zome.name, coordinator_exists, false),
+    ] {
+        let description = format!(
+            "{} zome {} in DNA {}",
+            if is_integrity {
+                "Integrity"
+            } else {
+                "Coordinator"
+            },
+            zome_name,
+            dna_name
+        );
+
+        if exists {
+            applied.skipped.push(description);
+            continue;
+        }
+
+        let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+        let ScaffoldedTemplate {
+            file_tree: new_file_tree,
+            ..
+        } = if is_integrity {
+            scaffold_integrity_zome_with_path(
+                dna_file_tree,
+                template_file_tree,
+                zome_name,
+                &zomes_path.join("integrity"),
+                interactivity,
+            )?
+        } else {
+            scaffold_coordinator_zome_in_path(
+                dna_file_tree,
+                template_file_tree,
+                zome_name,
+                Some(&vec![integrity_zome.clone()]),
+                &zomes_path.join("coordinator"),
+                interactivity,
+            )?
+        };
+
+        file_tree = new_file_tree;
+        applied.scaffolded.push(description);
+    }
+
+    applied.file_tree = file_tree;
+    Ok(applied)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn apply_entry_type(
+    mut applied: AppliedHappSpec,
+    template_file_tree: &FileTree,
+    dna_name: &str,
+    integrity_zome: &str,
+    entry_type: &EntryTypeSpec,
+    no_ui: bool,
+    no_spec: bool,
+    interactivity: Interactivity,
+) -> ScaffoldResult<AppliedHappSpec> {
+    let description = format!("Entry type {} in zome {}", entry_type.name, integrity_zome);
+
+    let crud = Crud::from_str(&entry_type.crud)?;
+    let fields = parse_fields(entry_type)?;
+    let permission = entry_type
+        .permission
+        .as_deref()
+        .map(Permission::from_str)
+        .transpose()?;
+
+    let zome_file_tree =
+        integrity_zome_file_tree(applied.file_tree, dna_name, integrity_zome, interactivity)?;
+
+    let already_exists = get_all_entry_types(&zome_file_tree)?
+        .unwrap_or_default()
+        .iter()
+        .any(|e| e.entry_type == entry_type.name.to_case(Case::Pascal));
+
+    if already_exists {
+        applied.file_tree = zome_file_tree.dna_file_tree.file_tree();
+        applied.skipped.push(description);
+        return Ok(applied);
+    }
+
+    let ScaffoldedTemplate { file_tree, .. } = scaffold_entry_type(
+        zome_file_tree,
+        template_file_tree,
+        &entry_type.name,
+        Some(crud),
+        Some(entry_type.reference_entry_hash),
+        Some(entry_type.link_from_original_to_each_update),
+        entry_type.paginated_revisions,
+        Some(entry_type.visibility),
+        permission,
+        Some(&fields),
+        no_ui,
+        no_spec,
+        interactivity,
+    )?;
+
+    applied.file_tree = file_tree;
+    applied.scaffolded.push(description);
+    Ok(applied)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn apply_link_type(
+    mut applied: AppliedHappSpec,
+    template_file_tree: &FileTree,
+    dna_name: &str,
+    integrity_zome: &str,
+    link_type: &LinkTypeSpec,
+    no_ui: bool,
+    no_spec: bool,
+    interactivity: Interactivity,
+) -> ScaffoldResult<AppliedHappSpec> {
+    let from = Referenceable::from_str(&link_type.from)?;
+    let to = Referenceable::from_str(&link_type.to)?;
+    let name = link_type_name(&from, &to);
+    let description = format!("Link type {} in zome {}", name, integrity_zome);
+
+    let zome_file_tree =
+        integrity_zome_file_tree(applied.file_tree, dna_name, integrity_zome, interactivity)?;
+
+    if get_all_link_types(&zome_file_tree)?.contains(&name) {
+        applied.file_tree = zome_file_tree.dna_file_tree.file_tree();
+        applied.skipped.push(description);
+        return Ok(applied);
+    }
+
+    let ScaffoldedTemplate { file_tree, .. } = scaffold_link_type(
+        zome_file_tree,
+        template_file_tree,
+        Some(&from),
+        Some(&to),
+        Some(link_type.delete),
+        Some(link_type.bidirectional),
+        no_ui,
+        no_spec,
+        interactivity,
+    )?;
+
+    applied.file_tree = file_tree;
+    applied.scaffolded.push(description);
+    Ok(applied)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn apply_collection(
+    mut applied: AppliedHappSpec,
+    template_file_tree: &FileTree,
+    dna_name: &str,
+    integrity_zome: &str,
+    collection: &CollectionSpec,
+    no_ui: bool,
+    no_spec: bool,
+    interactivity: Interactivity,
+) -> ScaffoldResult<AppliedHappSpec> {
+    let description = format!("Collection {} in zome {}", collection.name, integrity_zome);
+
+    let collection_type = CollectionType::from_str(&collection.collection_type)?;
+    let entry_type = EntryTypeReference::from_str(&collection.entry_type)?;
+
+    let zome_file_tree =
+        integrity_zome_file_tree(applied.file_tree, dna_name, integrity_zome, interactivity)?;
+
+    // Collections are indexed with a link type named after them
+    if get_all_link_types(&zome_file_tree)?.contains(&collection.name.to_case(Case::Pascal)) {
+        applied.file_tree = zome_file_tree.dna_file_tree.file_tree();
+        applied.skipped.push(description);
+        return Ok(applied);
+    }
+
+    let ScaffoldedTemplate { file_tree, .. } = scaffold_collection(
+        zome_file_tree,
+        template_file_tree,
+        &collection.name,
+        Some(collection_type),
+        Some(entry_type),
+        collection.paginated,
+        no_ui,
+        no_spec,
+        interactivity,
+    )?;
+
+    applied.file_tree = file_tree;
+    applied.scaffolded.push(description);
+    Ok(applied)
+}
+
+fn integrity_zome_file_tree(
+    file_tree: FileTree,
+    dna_name: &str,
+    integrity_zome: &str,
+    interactivity: Interactivity,
+) -> ScaffoldResult<ZomeFileTree> {
+    let dna_file_tree = DnaFileTree::get_or_choose(file_tree, Some(dna_name), interactivity)?;
+    ZomeFileTree::get_or_choose_integrity(dna_file_tree, Some(integrity_zome), interactivity)
+}
+
+fn parse_fields(entry_type: &EntryTypeSpec) -> ScaffoldResult<Vec<FieldDefinition>> {
+    entry_type
+        .fields
+        .iter()
+        .map(|f| FieldDefinition::from_str(f))
+        .collect()
+}
+
+/// Sorts the entry types so that every entry type comes after the entry types its fields are linked from
+fn sort_entry_types_by_dependencies(
+    entry_types: &[EntryTypeSpec],
+) -> ScaffoldResult<Vec<&EntryTypeSpec>> {
+    let names: Vec<String> = entry_types
+        .iter()
+        .map(|e| e.name.to_case(Case::Pascal))
+        .collect();
+
+    let dependencies = entry_types
+        .iter()
+        .map(|e| {
+            Ok(parse_fields(e)?
+                .into_iter()
+                .filter_map(|f| match f.linked_from {
+                    Some(Referenceable::EntryType(r)) => Some(r.entry_type.to_case(Case::Pascal)),
+                    _ => None,
+                })
+                .filter(|d| names.contains(d) && *d != e.name.to_case(Case::Pascal))
+                .collect::<Vec<String>>())
+        })
+        .collect::<ScaffoldResult<Vec<Vec<String>>>>()?;
+
+    let mut sorted: Vec<&EntryTypeSpec> = Vec::new();
+    let mut sorted_names: Vec<&String> = Vec::new();
+
+    while sorted.len() < entry_types.len() {
+        let next = (0..entry_types.len()).find(|i| {
+            !sorted_names.contains(&&names[*i])
+                && dependencies[*i].iter().all(|d| sorted_names.contains(&d))
+        });
+
+        match next {
+            Some(i) => {
+                sorted.push(&entry_types[i]);
+                sorted_names.push(&names[i]);
+            }
+            None => {
+                return Err(ScaffoldError::InvalidArguments(String::from(
+                    "the entry types in the hApp spec have circular linked_from dependencies",
+                )))
+            }
+        }
+    }
+
+    Ok(sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        file_tree::flatten_file_tree,
+        scaffold::web_app::{
+            package_manager::PackageManager, scaffold_web_app, template_type::TemplateType,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn parses_yaml_and_json_specs_with_defaults() {
+        let yaml = r#"
+dnas:
+  - name: forum
+    zomes:
+      - name: posts
+        entry_types:
+          - name: post
+            fields: ["title:String:TextField"]
+        collections:
+          - name: all_posts
+            type: global
+            entry_type: post
+"#;
+        let spec = HappSpec::from_file_content(Path::new("happ.yaml"), yaml).unwrap();
+        let entry_type = &spec.dnas[0].zomes[0].entry_types[0];
+        assert_eq!(entry_type.crud, "crud");
+        assert!(!entry_type.reference_entry_hash);
+        assert!(entry_type.link_from_original_to_each_update);
+        assert_eq!(
+            spec.dnas[0].zomes[0].collections[0].collection_type,
+            "global"
+        );
+
+        let json = r#"{ "dnas": [{ "name": "forum", "zomes": [{ "name": "posts" }] }] }"#;
+        let spec = HappSpec::from_file_content(Path::new("happ.json"), json).unwrap();
+        assert_eq!(spec.dnas[0].zomes[0].name, "posts");
+
+        let unknown_field = "dnas:\n  - name: forum\n    zome: []\n";
+        assert!(HappSpec::from_file_content(Path::new("happ.yaml"), unknown_field).is_err());
+    }
+
+    #[test]
+    fn sorts_entry_types_after_the_ones_they_link_from() {
+        let entry_type = |name: &str, fields: &[&str]| EntryTypeSpec {
+            name: name.to_string(),
+            crud: default_crud(),
+            reference_entry_hash: false,
+            link_from_original_to_each_update: true,
+            paginated_revisions: false,
+            visibility: Visibility::Public,
+            permission: None,
+            fields: fields.iter().map(|f| f.to_string()).collect(),
+        };
+        let entry_types = vec![
+            entry_type("comment", &["post_hash:ActionHash::Post"]),
+            entry_type("post", &["title:String"]),
+        ];
+
+        let sorted: Vec<&str> = sort_entry_types_by_dependencies(&entry_types)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.name.as_str())
+            .collect();
+
+        assert_eq!(sorted, vec!["post", "comment"]);
+    }
+
+    #[test]
+    fn applying_the_same_spec_twice_scaffolds_nothing_the_second_time() {
+        let interactivity = Interactivity::NonInteractive;
+        let template_file_tree = TemplateType::Vanilla.file_tree().unwrap();
+        let ScaffoldedTemplate { file_tree, .. } = scaffold_web_app(
+            "forum",
+            None,
+            PackageManager::Npm,
+            true,
+            &template_file_tree,
+            false,
+            interactivity,
+        )
+        .unwrap();
+        let spec = r#"
+dnas:
+  - name: forum
+    zomes:
+      - name: posts
+        entry_types:
+          - name: post
+            fields: ["title:String:TextField"]
+          - name: comment
+            fields: ["post_hash:ActionHash::Post"]
+        link_types:
+          - from: post
+            to: agent:creator
+        collections:
+          - name: all_posts
+            type: global
+            entry_type: post
+"#;
+        let spec = HappSpec::from_file_content(Path::new("happ.yaml"), spec).unwrap();
+
+        let applied = apply_happ_spec(
+            file_tree,
+            &template_file_tree,
+            &spec,
+            false,
+            false,
+            interactivity,
+        )
+        .unwrap();
+        assert_eq!(applied.scaffolded.len(), 7);
+        assert!(applied.skipped.is_empty());
+
+        let reapplied = apply_happ_spec(
+            applied.file_tree.clone(),
+            &template_file_tree,
+            &spec,
+            false,
+            false,
+            interactivity,
+        )
+        .unwrap();
+        assert!(reapplied.scaffolded.is_empty());
+        assert_eq!(reapplied.skipped, applied.scaffolded);
+        assert_eq!(
+            flatten_file_tree(&reapplied.file_tree),
+            flatten_file_tree(&applied.file_tree)
+        );
+    }
+}
@@ -0,0 +1,625 @@
+//! Report of the structure of a scaffolded project, as printed by `hc-scaffold inspect`
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use convert_case::{Case, Casing};
+use holochain_types::prelude::{AppManifest, DnaManifest, ZomeManifest};
+use mr_bundle::Location;
+use path_clean::PathClean;
+use quote::ToTokens;
+use serde::Serialize;
+
+use crate::{
+    error::{ScaffoldError, ScaffoldResult},
+    file_tree::{file_content, find_map_rust_files, FileTree},
+};
+
+use super::{
+    app::{cargo::get_workspace_members, find_app_manifests},
+    collection::TimeGranularity,
+    dna::{find_dna_manifests, DnaFileTree},
+    entry_type::integrity::get_all_entry_types,
+    link_type::integrity::get_all_link_types,
+    remove::{mentions_ident, mentions_path},
+    zome::{coordinator::find_all_extern_functions_by_file, ZomeFileTree},
+};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ProjectReport {
+    pub apps: Vec<AppReport>,
+    pub dnas: Vec<DnaReport>,
+    pub workspace_members: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct AppReport {
+    pub name: String,
+    pub manifest_path: PathBuf,
+    pub roles: Vec<RoleReport>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct RoleReport {
+    pub name: String,
+    /// Name of the DNA of this role, if its bundle is built from a DNA in this project
+    pub dna: Option<String>,
+    pub dna_bundle_path: Option<PathBuf>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DnaReport {
+    pub name: String,
+    pub manifest_path: PathBuf,
+    pub integrity_zomes: Vec<IntegrityZomeReport>,
+    pub coordinator_zomes: Vec<CoordinatorZomeReport>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct IntegrityZomeReport {
+    pub name: String,
+    /// Path to the crate of the zome, if it could be found in the workspace
+    pub crate_path: Option<PathBuf>,
+    pub entry_types: Vec<EntryTypeReport>,
+    pub link_types: Vec<LinkTypeReport>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EntryTypeReport {
+    pub name: String,
+    pub reference_entry_hash: bool,
+    pub fields: Vec<FieldReport>,
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FieldReport {
+    pub name: String,
+    pub rust_type: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LinkTypeReport {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CoordinatorZomeReport {
+    pub name: String,
+    /// Path to the crate of the zome, if it could be found in the workspace
+    pub crate_path: Option<PathBuf>,
+    pub dependencies: Vec<String>,
+    pub collections: Vec<CollectionReport>,
+    pub extern_functions: Vec<ExternFunctionReport>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CollectionReport {
+    pub name: String,
+    /// Same syntax as the `<collection-type>` argument, "global", "by-author" or "by-time:<granularity>"
+    pub collection_type: String,
+    /// The entry type that is added to the collection when it's created
+    pub entry_type: Option<String>,
+    pub path: PathBuf,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ExternFunctionReport {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Builds the report of all the apps, DNAs and zomes found in the given project
+pub fn inspect_project(file_tree: &FileTree) -> ScaffoldResult<ProjectReport> {
+    let dna_manifests = find_dna_manifests(file_tree)?;
+
+    let apps = find_app_manifests(file_tree)?
+        .into_iter()
+        .map(|(manifest_path, app_manifest)| {
+            inspect_app(manifest_path, &app_manifest, &dna_manifests)
+        })
+        .collect();
+
+    let dnas = dna_manifests
+        .into_keys()
+        .map(|manifest_path| {
+            let dna_file_tree =
+                DnaFileTree::from_dna_manifest_path(file_tree.clone(), &manifest_path)?;
+            inspect_dna(dna_file_tree)
+        })
+        .collect::<ScaffoldResult<Vec<DnaReport>>>()?;
+
+    Ok(ProjectReport {
+        apps,
+        dnas,
+        workspace_members: get_workspace_members(file_tree).unwrap_or_default(),
+    })
+}
+
+fn inspect_app(
+    manifest_path: PathBuf,
+    app_manifest: &AppManifest,
+    dna_manifests: &std::collections::BTreeMap<PathBuf, DnaManifest>,
+) -> AppReport {
+    let mut workdir = manifest_path.clone();
+    workdir.pop();
+
+    let roles = app_manifest
+        .app_roles()
+        .into_iter()
+        .map(|role| {
+            let dna_bundle_path = match role.dna.location {
+                Some(Location::Bundled(path)) => Some(workdir.join(path).clean()),
+                _ => None,
+            };
+            let dna = dna_bundle_path.as_ref().and_then(|bundle_path| {
+                dna_manifests
+                    .iter()
+                    .find(|(dna_manifest_path, _)| {
+                        dna_manifest_path.parent() == bundle_path.parent()
+                    })
+                    .map(|(_, m)| m.name())
+            });
+
+            RoleReport {
+                name: role.name,
+                dna,
+                dna_bundle_path,
+            }
+        })
+        .collect();
+
+    AppReport {
+        name: app_manifest.app_name().to_string(),
+        manifest_path,
+        roles,
+    }
+}
+
+fn inspect_dna(dna_file_tree: DnaFileTree) -> ScaffoldResult<DnaReport> {
+    let DnaManifest::V1(v1) = dna_file_tree.dna_manifest.clone();
+
+    let mut integrity_zomes = vec![];
+    let mut all_link_types = vec![];
+    for zome_manifest in v1.integrity.zomes {
+        let report = inspect_integrity_zome(&dna_file_tree, zome_manifest)?;
+        all_link_types.extend(report.link_types.iter().map(|l| l.name.clone()));
+        integrity_zomes.push(report);
+    }
+
+    let coordinator_zomes = v1
+        .coordinator
+        .zomes
+        .into_iter()
+        .map(|zome_manifest| {
+            inspect_coordinator_zome(&dna_file_tree, zome_manifest, &all_link_types)
+        })
+        .collect::<ScaffoldResult<Vec<CoordinatorZomeReport>>>()?;
+
+    Ok(DnaReport {
+        name: dna_file_tree.dna_manifest.name(),
+        manifest_path: dna_file_tree.dna_manifest_path,
+        integrity_zomes,
+        coordinator_zomes,
+    })
+}
+
+fn zome_file_tree(
+    dna_file_tree: &DnaFileTree,
+    zome_manifest: ZomeManifest,
+) -> Option<ZomeFileTree> {
+    let dna_file_tree = DnaFileTree::from_dna_manifest_path(
+        dna_file_tree.file_tree_ref().clone(),
+        &dna_file_tree.dna_manifest_path,
+    )
+    .ok()?;
+    ZomeFileTree::from_zome_manifest(dna_file_tree, zome_manifest).ok()
+}
+
+pub fn crate_src_file_tree(zome_file_tree: &ZomeFileTree) -> ScaffoldResult<(PathBuf, FileTree)> {
+    let crate_src_path = zome_file_tree.zome_crate_path.join("src");
+    let v: Vec<OsString> = crate_src_path.iter().map(|s| s.to_os_string()).collect();
+    let file_tree = zome_file_tree
+        .dna_file_tree
+        .file_tree_ref()
+        .path(&mut v.iter())
+        .cloned()
+        .ok_or(ScaffoldError::PathNotFound(crate_src_path.clone()))?;
+    Ok((crate_src_path, file_tree))
+}
+
+fn inspect_integrity_zome(
+    dna_file_tree: &DnaFileTree,
+    zome_manifest: ZomeManifest,
+) -> ScaffoldResult<IntegrityZomeReport> {
+    let name = zome_manifest.name.to_string();
+    let Some(zome_file_tree) = zome_file_tree(dna_file_tree, zome_manifest) else {
+        return Ok(IntegrityZomeReport {
+            name,
+            crate_path: None,
+            entry_types: vec![],
+            link_types: vec![],
+        });
+    };
+    let (crate_src_path, src_file_tree) = crate_src_file_tree(&zome_file_tree)?;
+
+    let entry_structs = find_map_rust_files(&src_file_tree, &|_path, file| {
+        let structs: Vec<syn::ItemStruct> = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Struct(item_struct)
+                    if item_struct
+                        .attrs
+                        .iter()
+                        .any(|a| a.path().is_ident("hdk_entry_helper")) =>
+                {
+                    Some(item_struct.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        (!structs.is_empty()).then_some(structs)
+    });
+
+    let entry_types = get_all_entry_types(&zome_file_tree)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry_type_reference| {
+            let entry_struct = entry_structs.iter().find_map(|(path, structs)| {
+                structs
+                    .iter()
+                    .find(|s| s.ident == entry_type_reference.entry_type)
+                    .map(|s| (crate_src_path.join(path), s))
+            });
+            let fields = entry_struct
+                .as_ref()
+                .map(|(_, s)| {
+                    s.fields
+                        .iter()
+                        .filter_map(|f| {
+                            f.ident.as_ref().map(|ident| FieldReport {
+                                name: ident.to_string(),
+                                rust_type: f.ty.to_token_stream().to_string().replace(' ', ""),
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            EntryTypeReport {
+                name: entry_type_reference.entry_type,
+                reference_entry_hash: entry_type_reference.reference_entry_hash,
+                fields,
+                path: entry_struct.map(|(path, _)| path),
+            }
+        })
+        .collect();
+
+    let link_types_paths = find_map_rust_files(&src_file_tree, &|_path, file| {
+        file.items
+            .iter()
+            .any(|item| {
+                matches!(item, syn::Item::Enum(item_enum) if item_enum
+                    .attrs
+                    .iter()
+                    .any(|a| a.path().is_ident("hdk_link_types")))
+            })
+            .then_some(())
+    });
+    let link_types = match link_types_paths.keys().next() {
+        Some(path) => get_all_link_types(&zome_file_tree)?
+            .into_iter()
+            .map(|name| LinkTypeReport {
+                name,
+                path: crate_src_path.join(path),
+            })
+            .collect(),
+        None => vec![],
+    };
+
+    Ok(IntegrityZomeReport {
+        name,
+        crate_path: Some(zome_file_tree.zome_crate_path),
+        entry_types,
+        link_types,
+    })
+}
+
+fn inspect_coordinator_zome(
+    dna_file_tree: &DnaFileTree,
+    zome_manifest: ZomeManifest,
+    link_types: &[String],
+) -> ScaffoldResult<CoordinatorZomeReport> {
+    let name = zome_manifest.name.to_string();
+    let dependencies = zome_manifest
+        .dependencies
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|d| d.name.to_string())
+        .collect();
+    let Some(zome_file_tree) = zome_file_tree(dna_file_tree, zome_manifest) else {
+        return Ok(CoordinatorZomeReport {
+            name,
+            crate_path: None,
+            dependencies,
+            collections: vec![],
+            extern_functions: vec![],
+        });
+    };
+
+    let functions_by_file = find_all_extern_functions_by_file(&zome_file_tree)?;
+    let functions: Vec<(&PathBuf, &syn::ItemFn)> = functions_by_file
+        .iter()
+        .flat_map(|(path, functions)| functions.iter().map(move |f| (path, f)))
+        .collect();
+
+    // Collections are link types with a getter named after them, that walks the time buckets
+    // of the collection if it's by time, or gets the links of the author if it's by author
+    let collections = link_types
+        .iter()
+        .filter_map(|link_type| {
+            let getter_name = format!("get_{}", link_type.to_case(Case::Snake));
+            let (path, getter) = functions.iter().find(|(_, f)| f.sig.ident == getter_name)?;
+            let getter_body = getter.block.to_token_stream();
+            let collection_type = if mentions_ident(getter_body.clone(), "buckets_in_range") {
+                match time_granularity(&zome_file_tree, path) {
+                    Some(granularity) => format!("by-time:{granularity}"),
+                    None => "by-time".to_string(),
+                }
+            } else if mentions_ident(getter_body, "author") {
+                "by-author".to_string()
+            } else {
+                "global".to_string()
+            };
+            let entry_type = functions.iter().find_map(|(_, f)| {
+                let entry_type = f.sig.ident.to_string().strip_prefix("create_")?.to_owned();
+                mentions_path(f.block.to_token_stream(), "LinkTypes", link_type)
+                    .then(|| entry_type.to_case(Case::Pascal))
+            });
+
+            Some(CollectionReport {
+                name: link_type.to_case(Case::Snake),
+                collection_type,
+                entry_type,
+                path: (*path).clone(),
+            })
+        })
+        .collect();
+
+    let extern_functions = functions
+        .iter()
+        .map(|(path, f)| ExternFunctionReport {
+            name: f.sig.ident.to_string(),
+            path: (*path).clone(),
+        })
+        .collect();
+
+    Ok(CoordinatorZomeReport {
+        name,
+        crate_path: Some(zome_file_tree.zome_crate_path),
+        dependencies,
+        collections,
+        extern_functions,
+    })
+}
+
+struct TreeNode {
+    label: String,
+    children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    fn new(label: String, children: Vec<TreeNode>) -> Self {
+        TreeNode { label, children }
+    }
+
+    fn leaf(label: String) -> Self {
+        TreeNode::new(label, vec![])
+    }
+
+    fn render(&self, prefix: &str, output: &mut String) {
+        for (i, child) in self.children.iter().enumerate() {
+            let last = i == self.children.len() - 1;
+            let (branch, indent) = match last {
+                true => ("└── ", "    "),
+                false => ("├── ", "│   "),
+            };
+            output.push_str(&format!("{prefix}{branch}{}\n", child.label));
+            child.render(&format!("{prefix}{indent}"), output);
+        }
+    }
+}
+
+fn path_label(path: &std::path::Path) -> String {
+    format!("({})", path.display()).dimmed().to_string()
+}
+
+impl ProjectReport {
+    /// Renders the report as a tree, one line per item
+    pub fn tree(&self) -> String {
+        let apps = self.apps.iter().map(|app| {
+            let roles = app
+                .roles
+                .iter()
+                .map(|role| {
+                    TreeNode::leaf(format!(
+                        "{} {} → {}",
+                        "role".dimmed(),
+                        role.name.bold(),
+                        role.dna.as_deref().unwrap_or("?")
+                    ))
+                })
+                .collect();
+            TreeNode::new(
+                format!(
+                    "{} {} {}",
+                    "app".dimmed(),
+                    app.name.bold(),
+                    path_label(&app.manifest_path)
+                ),
+                roles,
+            )
+        });
+
+        let dnas = self.dnas.iter().map(|dna| {
+            let integrity_zomes = dna.integrity_zomes.iter().map(|zome| {
+                let entry_types = zome.entry_types.iter().map(|entry_type| {
+                    let fields = entry_type
+                        .fields
+                        .iter()
+                        .map(|f| TreeNode::leaf(format!("{}: {}", f.name, f.rust_type)))
+                        .collect();
+                    let reference = match entry_type.reference_entry_hash {
+                        true => "EntryHash",
+                        false => "ActionHash",
+                    };
+                    TreeNode::new(
+                        format!(
+                            "{} {} [{}] {}",
+                            "entry type".dimmed(),
+                            entry_type.name.bold(),
+                            reference,
+                            entry_type
+                                .path
+                                .as_deref()
+                                .map(path_label)
+                                .unwrap_or_default()
+                        ),
+                        fields,
+                    )
+                });
+                let link_types = zome.link_types.iter().map(|link_type| {
+                    TreeNode::leaf(format!(
+                        "{} {} {}",
+                        "link type".dimmed(),
+                        link_type.name.bold(),
+                        path_label(&link_type.path)
+                    ))
+                });
+                TreeNode::new(
+                    format!(
+                        "{} {} {}",
+                        "integrity zome".dimmed(),
+                        zome.name.bold(),
+                        zome.crate_path
+                            .as_deref()
+                            .map(path_label)
+                            .unwrap_or_default()
+                    ),
+                    entry_types.chain(link_types).collect(),
+                )
+            });
+
+            let coordinator_zomes = dna.coordinator_zomes.iter().map(|zome| {
+                let collections = zome.collections.iter().map(|collection| {
+                    TreeNode::leaf(format!(
+                        "{} {} [{}{}] {}",
+                        "collection".dimmed(),
+                        collection.name.bold(),
+                        collection.collection_type,
+                        collection
+                            .entry_type
+                            .as_ref()
+                            .map(|e| format!(" of {e}"))
+                            .unwrap_or_default(),
+                        path_label(&collection.path)
+                    ))
+                });
+                let functions = zome.extern_functions.iter().map(|f| {
+                    TreeNode::leaf(format!(
+                        "{} {} {}",
+                        "fn".dimmed(),
+                        f.name,
+                        path_label(&f.path)
+                    ))
+                });
+                let dependencies = match zome.dependencies.is_empty() {
+                    true => String::new(),
+                    false => format!(" → {}", zome.dependencies.join(", ")),
+                };
+                TreeNode::new(
+                    format!(
+                        "{} {}{} {}",
+                        "coordinator zome".dimmed(),
+                        zome.name.bold(),
+                        dependencies,
+                        zome.crate_path
+                            .as_deref()
+                            .map(path_label)
+                            .unwrap_or_default()
+                    ),
+                    collections.chain(functions).collect(),
+                )
+            });
+
+            TreeNode::new(
+                format!(
+                    "{} {} {}",
+                    "dna".dimmed(),
+                    dna.name.bold(),
+                    path_label(&dna.manifest_path)
+                ),
+                integrity_zomes.chain(coordinator_zomes).collect(),
+            )
+        });
+
+        let root = TreeNode::new(String::new(), apps.chain(dnas).collect());
+        let mut output = String::from(".\n");
+        root.render("", &mut output);
+        output
+    }
+}
+
+/// The granularity of a by-time collection, from the `BUCKET_DEPTH` constant of the file of its getter
+fn time_granularity(zome_file_tree: &ZomeFileTree, path: &Path) -> Option<TimeGranularity> {
+    let contents = file_content(zome_file_tree.dna_file_tree.file_tree_ref(), path).ok()?;
+    let file = syn::parse_file(&contents).ok()?;
+
+    file.items.iter().find_map(|item| match item {
+        syn::Item::Const(item_const) if item_const.ident == "BUCKET_DEPTH" => {
+            match item_const.expr.as_ref() {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(depth),
+                    ..
+                }) => TimeGranularity::from_depth(depth.base10_parse().ok()?),
+                _ => None,
+            }
+        }
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_nested_nodes_as_a_tree() {
+        let root = TreeNode::new(
+            String::new(),
+            vec![
+                TreeNode::new(
+                    "dna".into(),
+                    vec![
+                        TreeNode::leaf("zome_a".into()),
+                        TreeNode::leaf("zome_b".into()),
+                    ],
+                ),
+                TreeNode::new("app".into(), vec![TreeNode::leaf("role".into())]),
+            ],
+        );
+        let mut output = String::new();
+        root.render("", &mut output);
+
+        assert_eq!(
+            output,
+            "├── dna\n│   ├── zome_a\n│   └── zome_b\n└── app\n    └── role\n"
+        );
+    }
+}
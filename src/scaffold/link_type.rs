@@ -1,17 +1,22 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use convert_case::{Case, Casing};
 use dialoguer::{theme::ColorfulTheme, Confirm, Select};
+use regex::Regex;
 
 use crate::{
     error::{ScaffoldError, ScaffoldResult},
     file_tree::{insert_file, map_file, FileTree},
     templates::{link_type::scaffold_link_type_templates, ScaffoldedTemplate},
-    utils::input_with_case,
+    utils::{input_with_case, Interactivity},
 };
 
 use self::{
-    coordinator::add_link_type_functions_to_coordinator, integrity::add_link_type_to_integrity_zome,
+    coordinator::add_link_type_functions_to_coordinator,
+    integrity::{
+        add_link_type_to_integrity_zome, get_all_link_types, get_link_types_validated_in_file,
+        remove_link_type_from_integrity_zome,
+    },
 };
 
 use super::{
@@ -19,9 +24,14 @@ use super::{
     dna::DnaFileTree,
     entry_type::{
         definitions::{Cardinality, Referenceable},
-        integrity::get_all_entry_types,
+        integrity::{get_all_entry_types, is_private_entry_type},
         utils::{get_or_choose_optional_reference_type, get_or_choose_referenceable},
     },
+    remove::{
+        any_case_reference, find_leftover_references, map_crate_rust_files, pascal_case_reference,
+        remove_generated_files, remove_link_type_statements, remove_module, ui_and_tests_folders,
+        Removal,
+    },
     zome::{utils::get_coordinator_zomes_for_integrity, ZomeFileTree},
 };
 
@@ -38,43 +48,69 @@ pub fn scaffold_link_type(
     bidirectional: Option<bool>,
     no_ui: bool,
     no_spec: bool,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     let dna_manifest_path = zome_file_tree.dna_file_tree.dna_manifest_path.clone();
     let zome_manifest = zome_file_tree.zome_manifest.clone();
     let all_entry_types = get_all_entry_types(&zome_file_tree)?.unwrap_or_default();
 
+    if from_referenceable.is_none() {
+        interactivity.ensure_interactive("<from-referenceable>")?;
+    }
     let from_referenceable = get_or_choose_referenceable(
         "Link from which entry type?",
         &zome_file_tree,
         from_referenceable,
         &all_entry_types,
+        interactivity,
     )?;
 
+    if to_referenceable.is_none() {
+        interactivity.ensure_interactive("<to-referenceable>")?;
+    }
     let to_referenceable = get_or_choose_optional_reference_type(
         "Link to which entry type?",
         &zome_file_tree,
         to_referenceable,
         &all_entry_types,
+        interactivity,
     )?;
 
+    for referenceable in std::iter::once(&from_referenceable).chain(to_referenceable.as_ref()) {
+        if let Referenceable::EntryType(entry_type) = referenceable {
+            if is_private_entry_type(&zome_file_tree, &entry_type.entry_type)? {
+                return Err(ScaffoldError::PrivateEntryType(
+                    entry_type.entry_type.to_case(Case::Pascal),
+                    "linked".to_string(),
+                ));
+            }
+        }
+    }
+
     let link_type = match &to_referenceable {
         Some(to_referenceable) => link_type_name(&from_referenceable, to_referenceable),
-        None => input_with_case("Enter link type name:", None, Case::Pascal)?,
+        None => input_with_case("Enter link type name:", None, Case::Pascal, interactivity)?,
     };
 
     let bidirectional = match (&to_referenceable, bidirectional) {
         (None, _) => false,
         (_, Some(b)) => b,
-        _ => Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Should the link be bidirectional?")
-            .interact()?,
+        _ => {
+            interactivity.ensure_interactive("--bidirectional")?;
+            Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Should the link be bidirectional?")
+                .interact()?
+        }
     };
 
     let delete = match delete {
         Some(d) => d,
-        None => Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Can the link be deleted?")
-            .interact()?,
+        None => {
+            interactivity.ensure_interactive("--delete")?;
+            Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Can the link be deleted?")
+                .interact()?
+        }
     };
 
     // 1. Create an LINK_TYPE_NAME.rs in "src/", with the link type validation
@@ -151,14 +187,15 @@ pub use {}::*;
         )),
         1 => Ok(coordinator_zomes_for_integrity[0].clone()),
         _ => {
+            let prompt = "Which coordinator zome should the link type functions be scaffolded in?";
+            interactivity.ensure_can_prompt(prompt)?;
+
             let names: Vec<String> = coordinator_zomes_for_integrity
                 .iter()
                 .map(|z| z.name.to_string())
                 .collect();
             let selection = Select::with_theme(&ColorfulTheme::default())
-                .with_prompt(
-                    "Which coordinator zome should the link type functions be scaffolded in?",
-                )
+                .with_prompt(prompt)
                 .default(0)
                 .items(&names[..])
                 .interact()?;
@@ -182,7 +219,11 @@ pub use {}::*;
         bidirectional,
     )?;
 
-    let app_file_tree = AppFileTree::get_or_choose(zome_file_tree.dna_file_tree.file_tree(), None)?;
+    let app_file_tree = AppFileTree::get_or_choose(
+        zome_file_tree.dna_file_tree.file_tree(),
+        None,
+        interactivity,
+    )?;
 
     let app_name = app_file_tree.app_manifest.app_name().to_string();
 
@@ -199,6 +240,7 @@ pub use {}::*;
         inverse_link_type.as_deref(),
         no_ui,
         no_spec,
+        interactivity,
     )
 }
 
@@ -222,3 +264,196 @@ pub fn link_type_name(
         .to_case(Case::Pascal),
     )
 }
+
+/// Splits a link type name like "PostToComments" into its "Post" and "Comments" parts
+fn split_link_type_name(link_type: &str) -> Option<(String, String)> {
+    let re = Regex::new(r"\A(?P<from>.+?)To(?P<to>[A-Z].*)\z").expect("Failed to create regex");
+    let captures = re.captures(link_type)?;
+    Some((captures["from"].to_string(), captures["to"].to_string()))
+}
+
+/// Whether the given UI or test file name, without extension, was scaffolded for the given link type
+fn is_link_type_file(file_stem: &str, link_type: &str) -> bool {
+    let mut names = vec![link_type.to_string(), link_type.to_case(Case::Kebab)];
+
+    if let Some((from, to)) = split_link_type_name(link_type) {
+        let to_for_from = format!("{to}For{from}");
+        names.push(to_for_from.to_case(Case::Kebab));
+        names.push(to_for_from);
+    }
+
+    names.iter().any(|n| n == file_stem)
+}
+
+/// The names of the zome functions the scaffolder generates to get or change the links of the given link type,
+/// like `get_all_posts` for a collection, `get_comments_for_post` or `get_all_revisions_for_post`
+fn generated_function_names(link_type: &str) -> Vec<String> {
+    let snake_link_type = link_type.to_case(Case::Snake);
+    let mut names = vec![format!("get_{snake_link_type}")];
+
+    if let Some(entry_type) = link_type.strip_suffix("Updates") {
+        let snake_entry_type = entry_type.to_case(Case::Snake);
+        names.push(format!("get_latest_{snake_entry_type}"));
+        names.push(format!("get_all_revisions_for_{snake_entry_type}"));
+    }
+
+    if let Some((from, to)) = split_link_type_name(link_type) {
+        let plural_link_type = pluralizer::pluralize(&snake_link_type, 2, false);
+        let froms = [from.clone(), pluralizer::pluralize(&from, 1, false)];
+        let tos = [to.clone(), pluralizer::pluralize(&to, 1, false)];
+        for from in froms.iter().map(|f| f.to_case(Case::Snake)) {
+            names.push(format!("add_{snake_link_type}_for_{from}"));
+            names.push(format!("get_{plural_link_type}_for_{from}"));
+            for to in tos.iter().map(|t| t.to_case(Case::Snake)) {
+                for prefix in ["add", "get", "get_deleted", "delete"] {
+                    names.push(format!("{prefix}_{to}_for_{from}"));
+                }
+            }
+        }
+    }
+
+    names
+}
+
+/// The identifiers the scaffolder generates for the given link type: its name, the names of its validation
+/// and zome functions, and the names of the UI components listing its targets, like `CommentsForPost`
+pub fn link_type_identifiers(link_type: &str) -> Vec<String> {
+    let snake_link_type = link_type.to_case(Case::Snake);
+    let mut identifiers = vec![
+        link_type.to_string(),
+        format!("validate_create_link_{snake_link_type}"),
+        format!("validate_delete_link_{snake_link_type}"),
+    ];
+
+    if let Some((from, to)) = split_link_type_name(link_type) {
+        identifiers.push(format!("{to}For{from}"));
+    }
+    identifiers.extend(generated_function_names(link_type));
+
+    identifiers
+}
+
+/// Patterns matching the references to the given link type, or to its zome functions
+pub fn link_type_reference_patterns(link_type: &str) -> Vec<Regex> {
+    let mut patterns = vec![pascal_case_reference(link_type)];
+
+    if let Some((from, to)) = split_link_type_name(link_type) {
+        let singular_to = pluralizer::pluralize(&to, 1, false);
+        patterns.push(any_case_reference(&format!("{to}For{from}")));
+        patterns.push(any_case_reference(&format!("{singular_to}For{from}")));
+    }
+
+    patterns
+}
+
+/// Removes the given link type, with its validation, its zome functions, and its UI and tests
+///
+/// The other link types validated in the file of this link type, like the inverse link type of
+/// a bidirectional link, are removed as well
+pub fn remove_link_type(
+    zome_file_tree: ZomeFileTree,
+    link_type_name: &str,
+) -> ScaffoldResult<Removal> {
+    let link_type = link_type_name.to_case(Case::Pascal);
+
+    if !get_all_link_types(&zome_file_tree)?.contains(&link_type) {
+        return Err(ScaffoldError::LinkTypeNotFound(
+            link_type,
+            zome_file_tree.dna_file_tree.dna_manifest.name(),
+            zome_file_tree.zome_manifest.name.to_string(),
+        ));
+    }
+
+    let mut link_types = vec![link_type.clone()];
+    for l in get_link_types_validated_in_file(
+        &zome_file_tree,
+        Path::new(&format!("{}.rs", link_type.to_case(Case::Snake))),
+    )? {
+        if !link_types.contains(&l) {
+            link_types.push(l);
+        }
+    }
+
+    let (zome_file_tree, removed_files) = remove_link_types(zome_file_tree, &link_types)?;
+    let file_tree = zome_file_tree.dna_file_tree.file_tree();
+
+    let patterns: Vec<Regex> = link_types
+        .iter()
+        .flat_map(|l| link_type_reference_patterns(l))
+        .collect();
+    let leftover_references = find_leftover_references(&file_tree, &patterns);
+
+    Ok(Removal {
+        file_tree,
+        removed_files,
+        leftover_references,
+    })
+}
+
+/// Removes the given link types from the integrity zome and from all its coordinator zomes,
+/// returning the files that were removed
+pub fn remove_link_types(
+    mut zome_file_tree: ZomeFileTree,
+    link_types: &[String],
+) -> ScaffoldResult<(ZomeFileTree, Vec<PathBuf>)> {
+    let dna_manifest_path = zome_file_tree.dna_file_tree.dna_manifest_path.clone();
+    let dna_name = zome_file_tree.dna_file_tree.dna_manifest.name();
+    let zome_manifest = zome_file_tree.zome_manifest.clone();
+    let coordinator_zomes = get_coordinator_zomes_for_integrity(
+        &zome_file_tree.dna_file_tree.dna_manifest,
+        zome_manifest.name.0.as_ref(),
+    );
+
+    let mut removed_files = Vec::new();
+
+    for link_type in link_types {
+        let module_name = link_type.to_case(Case::Snake);
+
+        zome_file_tree = remove_link_type_from_integrity_zome(zome_file_tree, link_type)?;
+        let integrity_src_path = zome_file_tree.zome_crate_path.join("src");
+        let mut file_tree = zome_file_tree.dna_file_tree.file_tree();
+
+        removed_files.extend(remove_module(
+            &mut file_tree,
+            &integrity_src_path,
+            &module_name,
+        )?);
+
+        for coordinator_zome in &coordinator_zomes {
+            let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+            let coordinator_file_tree =
+                ZomeFileTree::from_zome_manifest(dna_file_tree, coordinator_zome.clone())?;
+            let coordinator_src_path = coordinator_file_tree.zome_crate_path.join("src");
+            file_tree = coordinator_file_tree.dna_file_tree.file_tree();
+
+            map_crate_rust_files(
+                &mut file_tree,
+                &coordinator_src_path,
+                |_file_path, mut file| {
+                    remove_link_type_statements(
+                        &mut file,
+                        link_type,
+                        &generated_function_names(link_type),
+                    );
+                    Ok(file)
+                },
+            )?;
+            removed_files.extend(remove_module(
+                &mut file_tree,
+                &coordinator_src_path,
+                &module_name,
+            )?);
+
+            for folder in ui_and_tests_folders(&dna_name, &coordinator_zome.name.0) {
+                removed_files.extend(remove_generated_files(&mut file_tree, &folder, |stem| {
+                    is_link_type_file(stem, link_type)
+                })?);
+            }
+        }
+
+        let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+        zome_file_tree = ZomeFileTree::from_zome_manifest(dna_file_tree, zome_manifest.clone())?;
+    }
+
+    Ok((zome_file_tree, removed_files))
+}
@@ -10,13 +10,14 @@ use quote::{format_ident, quote};
 
 use crate::{
     error::{ScaffoldError, ScaffoldResult},
-    file_tree::{find_map_rust_files, map_rust_files, FileTree},
+    file_tree::{file_content, find_map_rust_files, map_rust_files, FileTree},
     scaffold::{
         dna::DnaFileTree,
         entry_type::{
             definitions::Referenceable,
             integrity::{find_ending_match_expr, find_ending_match_expr_in_block},
         },
+        remove::{map_crate_rust_files, remove_enum_variant, remove_functions, remove_match_arms},
         zome::{utils::get_coordinator_zomes_for_integrity, ZomeFileTree},
     },
 };
@@ -288,6 +289,106 @@ pub fn add_link_type_to_integrity_zome(
     Ok(zome_file_tree)
 }
 
+/// Returns the names of all the variants of the `#[hdk_link_types]` enum of the given integrity zome
+pub fn get_all_link_types(zome_file_tree: &ZomeFileTree) -> ScaffoldResult<Vec<String>> {
+    let crate_src_path = zome_file_tree.zome_crate_path.join("src");
+    let v: Vec<OsString> = crate_src_path.iter().map(|s| s.to_os_string()).collect();
+
+    let link_types_instances = find_map_rust_files(
+        zome_file_tree
+            .dna_file_tree
+            .file_tree_ref()
+            .path(&mut v.iter())
+            .ok_or(ScaffoldError::PathNotFound(crate_src_path.clone()))?,
+        &|_path, file| {
+            file.items.iter().find_map(|i| match i {
+                syn::Item::Enum(item_enum)
+                    if item_enum.attrs.iter().any(|a| {
+                        a.path()
+                            .segments
+                            .iter()
+                            .any(|s| s.ident == "hdk_link_types")
+                    }) =>
+                {
+                    Some(item_enum.clone())
+                }
+                _ => None,
+            })
+        },
+    );
+
+    Ok(link_types_instances
+        .values()
+        .flat_map(|item_enum| item_enum.variants.iter().map(|v| v.ident.to_string()))
+        .collect())
+}
+
+/// Removes the given link type from the `#[hdk_link_types]` enum, from the validation match arms and its validation functions
+pub fn remove_link_type_from_integrity_zome(
+    zome_file_tree: ZomeFileTree,
+    link_type_name: &str,
+) -> ScaffoldResult<ZomeFileTree> {
+    let crate_src_path = zome_file_tree.zome_crate_path.join("src");
+    let dna_manifest_path = zome_file_tree.dna_file_tree.dna_manifest_path.clone();
+    let zome_manifest = zome_file_tree.zome_manifest.clone();
+
+    let validation_functions = vec![
+        format!(
+            "validate_create_link_{}",
+            link_type_name.to_case(Case::Snake)
+        ),
+        format!(
+            "validate_delete_link_{}",
+            link_type_name.to_case(Case::Snake)
+        ),
+    ];
+
+    let mut file_tree = zome_file_tree.dna_file_tree.file_tree();
+
+    map_crate_rust_files(&mut file_tree, &crate_src_path, |_file_path, mut file| {
+        remove_enum_variant(&mut file, "hdk_link_types", link_type_name);
+        remove_match_arms(&mut file, "LinkTypes", link_type_name);
+        remove_functions(&mut file, &validation_functions);
+        Ok(file)
+    })?;
+
+    let dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
+    ZomeFileTree::from_zome_manifest(dna_file_tree, zome_manifest)
+}
+
+/// Returns the link types whose validation functions are defined in the given file of the integrity zome
+///
+/// Link types are validated in the file of the item they were scaffolded with, eg. the link types of
+/// an entry type are validated in the file of the entry type
+pub fn get_link_types_validated_in_file(
+    zome_file_tree: &ZomeFileTree,
+    file_path: &Path,
+) -> ScaffoldResult<Vec<String>> {
+    let path = zome_file_tree.zome_crate_path.join("src").join(file_path);
+    let Ok(content) = file_content(zome_file_tree.dna_file_tree.file_tree_ref(), &path) else {
+        return Ok(vec![]);
+    };
+    let file: syn::File =
+        syn::parse_str(&content).map_err(|e| ScaffoldError::MalformedFile(path, e.to_string()))?;
+
+    let validated_functions: Vec<String> = file
+        .items
+        .iter()
+        .filter_map(|i| match i {
+            syn::Item::Fn(item_fn) => Some(item_fn.sig.ident.to_string()),
+            _ => None,
+        })
+        .collect();
+
+    Ok(get_all_link_types(zome_file_tree)?
+        .into_iter()
+        .filter(|l| {
+            validated_functions
+                .contains(&format!("validate_create_link_{}", l.to_case(Case::Snake)))
+        })
+        .collect())
+}
+
 fn validate_referenceable(
     referenceable: &Referenceable,
     address_ident: &syn::Ident,
@@ -0,0 +1,654 @@
+//! Helpers to remove code that was previously scaffolded
+//!
+//! The removal of each item (entry types, link types and collections) lives next to its scaffolding,
+//! this module only holds the syn and file tree manipulations they share.
+
+use std::{
+    collections::BTreeSet,
+    ffi::OsString,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use convert_case::{Case, Casing};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use regex::Regex;
+use syn::visit_mut::{self, VisitMut};
+
+use crate::{
+    error::{ScaffoldError, ScaffoldResult},
+    file_tree::{
+        dir_content, file_content, file_exists, flatten_file_tree, insert_file, map_rust_files,
+        remove_file, FileTree,
+    },
+};
+
+pub struct Removal {
+    pub file_tree: FileTree,
+    /// Files that have to be deleted from disk, relative to the root of the project
+    pub removed_files: Vec<PathBuf>,
+    /// References to the removed item in code that was not generated by the scaffolding tool,
+    /// which were left in place
+    pub leftover_references: Vec<LeftoverReference>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeftoverReference {
+    pub path: PathBuf,
+    pub line_number: usize,
+    pub line: String,
+}
+
+impl fmt::Display for LeftoverReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}",
+            self.path.display(),
+            self.line_number,
+            self.line
+        )
+    }
+}
+
+/// Applies the given function to every rust file in the given crate source folder
+pub fn map_crate_rust_files<F: Fn(PathBuf, syn::File) -> ScaffoldResult<syn::File> + Copy>(
+    file_tree: &mut FileTree,
+    crate_src_path: &Path,
+    map_fn: F,
+) -> ScaffoldResult<()> {
+    let v: Vec<OsString> = crate_src_path.iter().map(|s| s.to_os_string()).collect();
+    map_rust_files(
+        file_tree
+            .path_mut(&mut v.iter())
+            .ok_or(ScaffoldError::PathNotFound(crate_src_path.to_path_buf()))?,
+        map_fn,
+    )
+    .map_err(|e| match e {
+        ScaffoldError::MalformedFile(path, error) => {
+            ScaffoldError::MalformedFile(crate_src_path.join(path), error)
+        }
+        _ => e,
+    })
+}
+
+/// Removes the `{module_name}.rs` file from the given crate source folder, and its `pub mod` and
+/// `pub use` declarations from the `lib.rs` file
+///
+/// Returns the path of the removed file, if it existed
+pub fn remove_module(
+    file_tree: &mut FileTree,
+    crate_src_path: &Path,
+    module_name: &str,
+) -> ScaffoldResult<Option<PathBuf>> {
+    let module_path = crate_src_path.join(format!("{module_name}.rs"));
+
+    if !file_exists(file_tree, &module_path) {
+        return Ok(None);
+    }
+
+    remove_file(file_tree, &module_path)?;
+
+    map_crate_rust_files(file_tree, crate_src_path, |file_path, mut file| {
+        if file_path == Path::new("lib.rs") {
+            file.items.retain(|item| match item {
+                syn::Item::Mod(item_mod) => item_mod.ident != module_name,
+                syn::Item::Use(item_use) => !matches!(
+                    &item_use.tree,
+                    syn::UseTree::Path(use_path) if use_path.ident == module_name
+                ),
+                _ => true,
+            });
+        }
+        Ok(file)
+    })?;
+
+    Ok(Some(module_path))
+}
+
+/// Removes the given variant from the enums annotated with the given attribute, eg. `hdk_entry_types`
+pub fn remove_enum_variant(file: &mut syn::File, enum_attribute: &str, variant: &str) {
+    for item in &mut file.items {
+        if let syn::Item::Enum(item_enum) = item {
+            if item_enum
+                .attrs
+                .iter()
+                .any(|a| a.path().segments.iter().any(|s| s.ident == enum_attribute))
+            {
+                let trailing_punct = item_enum.variants.trailing_punct();
+                item_enum.variants = item_enum
+                    .variants
+                    .clone()
+                    .into_iter()
+                    .filter(|v| v.ident != variant)
+                    .collect();
+                if trailing_punct && !item_enum.variants.empty_or_trailing() {
+                    item_enum.variants.push_punct(Default::default());
+                }
+            }
+        }
+    }
+}
+
+/// Removes every match arm in the file whose pattern refers to `{enum_name}::{variant}`
+pub fn remove_match_arms(file: &mut syn::File, enum_name: &str, variant: &str) {
+    struct ArmRemover<'a> {
+        enum_name: &'a str,
+        variant: &'a str,
+    }
+
+    impl VisitMut for ArmRemover<'_> {
+        fn visit_expr_match_mut(&mut self, expr_match: &mut syn::ExprMatch) {
+            expr_match.arms.retain(|arm| {
+                !mentions_path(arm.pat.to_token_stream(), self.enum_name, self.variant)
+            });
+            visit_mut::visit_expr_match_mut(self, expr_match);
+        }
+    }
+
+    ArmRemover { enum_name, variant }.visit_file_mut(file);
+}
+
+/// Removes the top level functions with the given names
+pub fn remove_functions(file: &mut syn::File, function_names: &[String]) {
+    file.items.retain(|item| match item {
+        syn::Item::Fn(item_fn) => !function_names.contains(&item_fn.sig.ident.to_string()),
+        _ => true,
+    });
+}
+
+/// Removes from every function in the file the statements that refer to `LinkTypes::{link_type}`,
+/// together with the statements that depend on them, and the variables that are only used by them
+///
+/// If the value returned by a function depends on the link type, the whole function is removed if it's
+/// one of the given generated functions, and left untouched otherwise so that it's reported as a reference
+pub fn remove_link_type_statements(
+    file: &mut syn::File,
+    link_type: &str,
+    generated_functions: &[String],
+) {
+    file.items.retain_mut(|item| {
+        let syn::Item::Fn(item_fn) = item else {
+            return true;
+        };
+
+        let stmts = &item_fn.block.stmts;
+        let removed = statements_to_remove(stmts, |stmt| {
+            mentions_path(stmt.to_token_stream(), "LinkTypes", link_type)
+        });
+
+        if removed.is_empty() {
+            return true;
+        }
+
+        let returns_removed_value = matches!(stmts.last(), Some(syn::Stmt::Expr(_, None)))
+            && removed.contains(&(stmts.len() - 1));
+        if returns_removed_value || removed.len() == stmts.len() {
+            return !generated_functions.contains(&item_fn.sig.ident.to_string());
+        }
+
+        item_fn.block.stmts = stmts
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !removed.contains(i))
+            .map(|(_, s)| s.clone())
+            .collect();
+        true
+    });
+}
+
+/// Finds the statements that satisfy the given predicate, together with the statements that depend on them,
+/// and the variables that are only used by them
+pub fn statements_to_remove<F: Fn(&syn::Stmt) -> bool>(
+    stmts: &[syn::Stmt],
+    is_reference: F,
+) -> BTreeSet<usize> {
+    let mut removed: BTreeSet<usize> = stmts
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| is_reference(s))
+        .map(|(i, _)| i)
+        .collect();
+
+    if removed.is_empty() {
+        return removed;
+    }
+
+    let bindings: Vec<Vec<String>> = stmts.iter().map(bound_variables).collect();
+    let idents: Vec<BTreeSet<String>> = stmts
+        .iter()
+        .map(|s| flatten_tokens(s.to_token_stream()).into_iter().collect())
+        .collect();
+
+    // Whether statement `j` reads the variable `v` as it was bound by statement `i`, and not shadowed afterwards
+    let reads_binding = |i: usize, j: usize, v: &String| {
+        j > i && idents[j].contains(v) && !bindings[i + 1..j].iter().any(|b| b.contains(v))
+    };
+    let is_used_by = |i: usize, removed: &BTreeSet<usize>| {
+        bindings[i]
+            .iter()
+            .any(|v| (0..stmts.len()).any(|j| !removed.contains(&j) && reads_binding(i, j, v)))
+    };
+    let was_used: Vec<bool> = (0..stmts.len())
+        .map(|i| is_used_by(i, &BTreeSet::new()))
+        .collect();
+
+    loop {
+        // Statements that use the variables bound by removed statements can't stay either
+        let dependents: Vec<usize> = (0..stmts.len())
+            .filter(|i| !removed.contains(i))
+            .filter(|i| {
+                removed
+                    .iter()
+                    .any(|r| bindings[*r].iter().any(|v| reads_binding(*r, *i, v)))
+            })
+            .collect();
+
+        // Variables that were only used by removed statements are not needed anymore
+        let unused: Vec<usize> = (0..stmts.len())
+            .filter(|i| !removed.contains(i) && !dependents.contains(i))
+            .filter(|i| was_used[*i] && !bindings[*i].is_empty() && !is_used_by(*i, &removed))
+            .collect();
+
+        if dependents.is_empty() && unused.is_empty() {
+            return removed;
+        }
+
+        removed.extend(dependents);
+        removed.extend(unused);
+    }
+}
+
+fn bound_variables(stmt: &syn::Stmt) -> Vec<String> {
+    struct BindingsCollector(Vec<String>);
+
+    impl<'ast> syn::visit::Visit<'ast> for BindingsCollector {
+        fn visit_pat_ident(&mut self, pat_ident: &'ast syn::PatIdent) {
+            // Variables starting with an underscore are meant to be unused
+            if !pat_ident.ident.to_string().starts_with('_') {
+                self.0.push(pat_ident.ident.to_string());
+            }
+            syn::visit::visit_pat_ident(self, pat_ident);
+        }
+    }
+
+    let mut collector = BindingsCollector(vec![]);
+    if let syn::Stmt::Local(local) = stmt {
+        syn::visit::Visit::visit_pat(&mut collector, &local.pat);
+    }
+    collector.0
+}
+
+fn flatten_tokens(tokens: TokenStream) -> Vec<String> {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Group(group) => flatten_tokens(group.stream()),
+            other => vec![other.to_string()],
+        })
+        .collect()
+}
+
+/// Whether the given tokens contain the identifier
+pub fn mentions_ident(tokens: TokenStream, ident: &str) -> bool {
+    flatten_tokens(tokens).iter().any(|t| t == ident)
+}
+
+/// Whether the given tokens access the field `{variable}.{field}`
+pub fn mentions_field(tokens: TokenStream, variable: &str, field: &str) -> bool {
+    flatten_tokens(tokens)
+        .windows(3)
+        .any(|w| w[0] == variable && w[1] == "." && w[2] == field)
+}
+
+/// Whether the given tokens contain the path `{enum_name}::{variant}`
+pub fn mentions_path(tokens: TokenStream, enum_name: &str, variant: &str) -> bool {
+    flatten_tokens(tokens)
+        .windows(4)
+        .any(|w| w[0] == enum_name && w[1] == ":" && w[2] == ":" && w[3] == variant)
+}
+
+/// Removes the files in the given folder whose name, up to its first dot, matches the given predicate
+pub fn remove_generated_files<F: Fn(&str) -> bool>(
+    file_tree: &mut FileTree,
+    folder_path: &Path,
+    is_generated: F,
+) -> ScaffoldResult<Vec<PathBuf>> {
+    let Ok(content) = dir_content(file_tree, folder_path) else {
+        return Ok(vec![]);
+    };
+
+    let mut removed_files = Vec::new();
+
+    for (name, tree) in content {
+        let name = name.to_string_lossy().to_string();
+        let stem = name.split('.').next().unwrap_or_default();
+
+        if tree.file_content().is_some() && is_generated(stem) {
+            let file_path = folder_path.join(&name);
+            remove_file(file_tree, &file_path)?;
+            removed_files.push(file_path);
+        }
+    }
+
+    Ok(removed_files)
+}
+
+/// Removes the given top level `export async function` declarations from a typescript file, if it exists
+pub fn remove_typescript_functions(
+    file_tree: &mut FileTree,
+    file_path: &Path,
+    function_names: &[String],
+) -> ScaffoldResult<()> {
+    let Ok(content) = file_content(file_tree, file_path) else {
+        return Ok(());
+    };
+
+    let mut lines: Vec<&str> = Vec::new();
+    let mut skipping = false;
+
+    for line in content.lines() {
+        if function_names
+            .iter()
+            .any(|f| line.starts_with(&format!("export async function {f}(")))
+        {
+            skipping = true;
+        }
+
+        if skipping {
+            if line == "}" {
+                skipping = false;
+            }
+            continue;
+        }
+
+        if line.is_empty() && lines.last().is_some_and(|l| l.is_empty()) {
+            continue;
+        }
+
+        lines.push(line);
+    }
+
+    let mut new_content = lines.join("\n").trim_end().to_string();
+    new_content.push('\n');
+
+    if new_content != content {
+        insert_file(file_tree, file_path, &new_content)?;
+    }
+
+    Ok(())
+}
+
+/// The folders in which the UI and the tests for the given coordinator zome are scaffolded
+pub fn ui_and_tests_folders(dna_name: &str, coordinator_zome_name: &str) -> Vec<PathBuf> {
+    ["ui", "tests"]
+        .iter()
+        .map(|folder| {
+            PathBuf::from(folder)
+                .join("src")
+                .join(dna_name)
+                .join(coordinator_zome_name)
+        })
+        .collect()
+}
+
+/// Words the scaffolder puts around the names of the items in the identifiers it generates,
+/// like `createPost`, `PostDetail` or `get_all_revisions_for_post`, to report the references left to a removed item
+const GENERATED_WORDS: [&str; 53] = [
+    "add",
+    "address",
+    "alice",
+    "all",
+    "author",
+    "base",
+    "bob",
+    "by",
+    "client",
+    "create",
+    "created",
+    "current",
+    "delete",
+    "deleted",
+    "deletes",
+    "detail",
+    "edit",
+    "entry",
+    "error",
+    "fetch",
+    "for",
+    "get",
+    "hash",
+    "hashes",
+    "input",
+    "into",
+    "is",
+    "latest",
+    "link",
+    "links",
+    "load",
+    "more",
+    "my",
+    "oldest",
+    "on",
+    "original",
+    "partial",
+    "path",
+    "previous",
+    "props",
+    "query",
+    "render",
+    "revisions",
+    "sample",
+    "set",
+    "target",
+    "time",
+    "to",
+    "update",
+    "updated",
+    "updates",
+    "valid",
+    "validate",
+];
+
+/// Matches the given name in PascalCase as a whole identifier, or as part of the identifiers the scaffolder
+/// generates for it like `getAllPosts` or `AllPostsForAuthor`, but not `createEventDispatcher` for `Event`
+pub fn pascal_case_reference(name: &str) -> Regex {
+    let camel_words = GENERATED_WORDS.join("|");
+    let pascal_words = GENERATED_WORDS
+        .iter()
+        .map(|w| w.to_case(Case::Pascal))
+        .collect::<Vec<String>>()
+        .join("|");
+    Regex::new(&format!(
+        "(?:^|[^A-Za-z0-9_])(?:{camel_words})?(?:{pascal_words})*{}(?:{pascal_words})*(?:[^A-Za-z0-9_]|$)",
+        regex::escape(&name.to_case(Case::Pascal))
+    ))
+    .expect("Failed to create regex")
+}
+
+/// Matches the given words in any case, eg. `comments_for_post`, `commentsForPost` or `CommentsForPost`
+pub fn any_case_reference(words: &str) -> Regex {
+    let words: Vec<String> = words
+        .to_case(Case::Snake)
+        .split('_')
+        .map(regex::escape)
+        .collect();
+    Regex::new(&format!("(?i){}", words.join("[_-]?"))).expect("Failed to create regex")
+}
+
+/// Whether the file is Rust code or UI and tests code, based on its extension
+pub fn is_code_file(path: &Path) -> bool {
+    let code_extensions = ["rs", "ts", "tsx", "js", "jsx", "svelte", "vue"];
+
+    path.extension()
+        .is_some_and(|e| code_extensions.iter().any(|c| e == *c))
+}
+
+/// Finds the lines of code in the project that match any of the given patterns, skipping comments
+pub fn find_leftover_references(
+    file_tree: &FileTree,
+    patterns: &[Regex],
+) -> Vec<LeftoverReference> {
+    flatten_file_tree(file_tree)
+        .into_iter()
+        .filter(|(path, _)| is_code_file(path))
+        .filter_map(|(path, content)| content.map(|c| (path, c)))
+        .flat_map(|(path, content)| {
+            content
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| {
+                    let trimmed = line.trim_start();
+                    !trimmed.starts_with("//")
+                        && !trimmed.starts_with('*')
+                        && patterns.iter().any(|p| p.is_match(line))
+                })
+                .map(|(i, line)| LeftoverReference {
+                    path: path.clone(),
+                    line_number: i + 1,
+                    line: line.trim().to_string(),
+                })
+                .collect::<Vec<LeftoverReference>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_link_type_statements_and_their_dependencies() {
+        let mut file: syn::File = syn::parse_quote! {
+            pub fn create_post(post: Post) -> ExternResult<Record> {
+                let post_hash = create_entry(&EntryTypes::Post(post.clone()))?;
+                let record = get(post_hash.clone(), GetOptions::default())?;
+                let path = Path::from("all_posts");
+                create_link(path.path_entry_hash()?, post_hash.clone(), LinkTypes::AllPosts, ())?;
+                Ok(record)
+            }
+
+            pub fn delete_post(original_post_hash: ActionHash) -> ExternResult<ActionHash> {
+                let path = Path::from("all_posts");
+                let links = get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllPosts)?.build())?;
+                for link in links {
+                    delete_link(link.create_link_hash)?;
+                }
+                delete_entry(original_post_hash)
+            }
+
+            pub fn get_all_posts() -> ExternResult<Vec<Link>> {
+                let path = Path::from("all_posts");
+                get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllPosts)?.build())
+            }
+
+            pub fn count_all_posts() -> ExternResult<usize> {
+                let path = Path::from("all_posts");
+                let links = get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllPosts)?.build())?;
+                Ok(links.len())
+            }
+        };
+
+        remove_link_type_statements(&mut file, "AllPosts", &[String::from("get_all_posts")]);
+
+        let expected: syn::File = syn::parse_quote! {
+            pub fn create_post(post: Post) -> ExternResult<Record> {
+                let post_hash = create_entry(&EntryTypes::Post(post.clone()))?;
+                let record = get(post_hash.clone(), GetOptions::default())?;
+                Ok(record)
+            }
+
+            pub fn delete_post(original_post_hash: ActionHash) -> ExternResult<ActionHash> {
+                delete_entry(original_post_hash)
+            }
+
+            pub fn count_all_posts() -> ExternResult<usize> {
+                let path = Path::from("all_posts");
+                let links = get_links(GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllPosts)?.build())?;
+                Ok(links.len())
+            }
+        };
+        assert_eq!(file, expected);
+    }
+
+    #[test]
+    fn finds_pascal_case_references_on_identifier_boundaries() {
+        let reference = pascal_case_reference("event");
+
+        assert!(reference.is_match("import EventDetail from './EventDetail.svelte';"));
+        assert!(reference.is_match("await createEvent(alice.cells[0]);"));
+        assert!(reference.is_match("const event: Event = decode(record);"));
+        assert!(!reference.is_match("const dispatch = createEventDispatcher();"));
+        assert!(!reference.is_match("this.dispatchEvent(new CustomEvent('event-created'));"));
+    }
+
+    #[test]
+    fn keeps_the_statements_of_shadowed_variables() {
+        let item_fn: syn::ItemFn = syn::parse_quote! {
+            pub fn validate_create_comment(
+                _action: EntryCreationAction,
+                comment: Comment
+            ) -> ExternResult<ValidateCallbackResult> {
+                let record = must_get_valid_record(comment.post_hash.clone())?;
+                let _post: crate::Post = record.entry().to_app_option().map_err(|e| wasm_error!(e))?;
+                if let Some(action_hash) = comment.author_post.clone() {
+                    let record = must_get_valid_record(action_hash)?;
+                    let _post: crate::Post = record.entry().to_app_option().map_err(|e| wasm_error!(e))?;
+                }
+                let record = must_get_valid_record(comment.reply_to.clone())?;
+                let _comment: crate::Comment = record.entry().to_app_option().map_err(|e| wasm_error!(e))?;
+                Ok(ValidateCallbackResult::Valid)
+            }
+        };
+
+        let removed = statements_to_remove(&item_fn.block.stmts, |stmt| {
+            ["author_post", "reply_to"]
+                .iter()
+                .any(|f| mentions_field(stmt.to_token_stream(), "comment", f))
+        });
+
+        assert_eq!(removed, BTreeSet::from([2, 3, 4]));
+    }
+
+    #[test]
+    fn removes_match_arms_and_enum_variants() {
+        let mut file: syn::File = syn::parse_quote! {
+            #[hdk_entry_types]
+            pub enum EntryTypes {
+                Post(Post),
+                Comment(Comment),
+            }
+
+            pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+                match op {
+                    FlatOp::StoreEntry(store_entry) => match store_entry {
+                        EntryTypes::Post(post) => validate_create_post(post),
+                        EntryTypes::Comment(comment) => validate_create_comment(comment),
+                    },
+                    _ => Ok(ValidateCallbackResult::Valid),
+                }
+            }
+        };
+
+        remove_enum_variant(&mut file, "hdk_entry_types", "Post");
+        remove_match_arms(&mut file, "EntryTypes", "Post");
+
+        let expected: syn::File = syn::parse_quote! {
+            #[hdk_entry_types]
+            pub enum EntryTypes {
+                Comment(Comment),
+            }
+
+            pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+                match op {
+                    FlatOp::StoreEntry(store_entry) => match store_entry {
+                        EntryTypes::Comment(comment) => validate_create_comment(comment),
+                    },
+                    _ => Ok(ValidateCallbackResult::Valid),
+                }
+            }
+        };
+        assert_eq!(file, expected);
+    }
+}
@@ -0,0 +1,524 @@
+//! Helpers to rename items that were previously scaffolded
+//!
+//! The renaming of each item (entry types and zomes) lives next to its scaffolding,
+//! this module only holds the name replacement and file tree manipulations they share.
+
+use std::path::{Path, PathBuf};
+
+use convert_case::{Case, Casing};
+use regex::Regex;
+
+use crate::{
+    error::ScaffoldResult,
+    file_tree::{create_dir_all, flatten_file_tree, insert_file, remove_file, FileTree},
+};
+
+pub struct Renaming {
+    pub file_tree: FileTree,
+    /// Files that were moved, from their old path to their new path, relative to the root of the project
+    pub renamed_files: Vec<(PathBuf, PathBuf)>,
+    /// Files whose contents were changed, without being moved
+    pub modified_files: Vec<PathBuf>,
+}
+
+impl Renaming {
+    pub fn new(file_tree: FileTree) -> Self {
+        Renaming {
+            file_tree,
+            renamed_files: vec![],
+            modified_files: vec![],
+        }
+    }
+
+    /// The old paths of the renamed files, which have to be deleted from disk
+    pub fn removed_files(&self) -> Vec<PathBuf> {
+        self.renamed_files
+            .iter()
+            .map(|(old, _)| old.clone())
+            .collect()
+    }
+
+    /// Human readable summary of every file this renaming touches
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+
+        if !self.renamed_files.is_empty() {
+            summary.push_str("Files to rename:\n");
+            for (old, new) in &self.renamed_files {
+                summary.push_str(&format!("  {} -> {}\n", old.display(), new.display()));
+            }
+        }
+
+        if !self.modified_files.is_empty() {
+            summary.push_str("Files to modify:\n");
+            for path in &self.modified_files {
+                summary.push_str(&format!("  {}\n", path.display()));
+            }
+        }
+
+        summary
+    }
+
+    /// Replaces the contents of every file accepted by `in_scope` with `map_content`, and moves it to `map_path`
+    pub fn map_files<S, C, P>(
+        mut self,
+        in_scope: S,
+        map_content: C,
+        map_path: P,
+    ) -> ScaffoldResult<Renaming>
+    where
+        S: Fn(&Path) -> bool,
+        C: Fn(&Path, &str) -> ScaffoldResult<String>,
+        P: Fn(&Path) -> PathBuf,
+    {
+        for (path, content) in flatten_file_tree(&self.file_tree) {
+            let Some(content) = content else {
+                continue;
+            };
+            if !in_scope(&path) {
+                continue;
+            }
+
+            let new_content = map_content(&path, &content)?;
+            let new_path = map_path(&path);
+
+            if new_path != path {
+                remove_file(&mut self.file_tree, &path)?;
+                if let Some(parent) = new_path.parent() {
+                    create_dir_all(&mut self.file_tree, parent)?;
+                }
+                insert_file(&mut self.file_tree, &new_path, &new_content)?;
+                self.track_rename(path, new_path);
+            } else if new_content != content {
+                insert_file(&mut self.file_tree, &path, &new_content)?;
+                if !self.modified_files.contains(&path) {
+                    self.modified_files.push(path);
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    fn track_rename(&mut self, old_path: PathBuf, new_path: PathBuf) {
+        self.modified_files.retain(|p| *p != old_path);
+
+        // A file that was already moved by a previous step keeps its original path
+        match self.renamed_files.iter_mut().find(|(_, n)| *n == old_path) {
+            Some(renamed) => renamed.1 = new_path,
+            None => self.renamed_files.push((old_path, new_path)),
+        }
+    }
+}
+
+/// Identifiers of the frameworks that look like identifiers generated for an item, eg. `create_link` for `Link`
+const FRAMEWORK_IDENTIFIERS: [&str; 9] = [
+    "post_commit",
+    "create_link",
+    "create_link_hash",
+    "delete_link",
+    "get_links",
+    "get_link_details",
+    "count_links",
+    "validate_create_link",
+    "validate_delete_link",
+];
+
+/// Replaces a name in all the case conventions the templates use for it,
+/// in its singular and plural forms: `BlogPost`, `blogPost`, `blog_post`, `blog-post`, `Blog Post`, `blog post`...
+///
+/// Inside longer identifiers the name is only replaced if the whole identifier is one the scaffolder
+/// generated for it, so `createPost` is renamed but `createEventDispatcher` is not
+pub struct NameReplacer {
+    /// Longest first
+    replacements: Vec<Replacement>,
+    /// Whole identifiers that must never be changed, like the names of the zomes
+    protected_identifiers: Vec<String>,
+    /// The words of the identifiers the scaffolder generated from the old name, like `get_all_posts`
+    generated_identifiers: Vec<Vec<String>>,
+}
+
+/// The replacements of one form of the old name, by the case convention it was found in
+///
+/// Single word names look the same in many conventions (`post` is camelCase, snake_case and kebab-case),
+/// in which case the convention is guessed from the surrounding characters
+struct Replacement {
+    old: String,
+    new_by_case: Vec<(Case, String)>,
+}
+
+impl NameReplacer {
+    pub fn new(old_name: &str, new_name: &str) -> Self {
+        NameReplacer {
+            replacements: vec![],
+            protected_identifiers: FRAMEWORK_IDENTIFIERS.map(String::from).to_vec(),
+            generated_identifiers: vec![],
+        }
+        .with_replacements(case_forms(old_name).into_iter().zip(case_forms(new_name)))
+    }
+
+    /// Leaves the given whole identifiers untouched, like the names of the zomes
+    pub fn protect(mut self, identifiers: impl IntoIterator<Item = String>) -> Self {
+        self.protected_identifiers.extend(identifiers);
+        self
+    }
+
+    /// Leaves every case form of the given names untouched, also inside other identifiers
+    ///
+    /// Needed for names that start with the renamed one, eg. `PostComment` when renaming `Post`
+    pub fn keep<'a>(self, names: impl IntoIterator<Item = &'a str>) -> Self {
+        let forms: Vec<((Case, String), (Case, String))> = names
+            .into_iter()
+            .flat_map(case_forms)
+            .map(|form| (form.clone(), form))
+            .collect();
+        self.with_replacements(forms)
+    }
+
+    /// Renames the old name inside the given identifiers, in any case convention,
+    /// like the zome functions and UI components the scaffolder generated for it
+    pub fn generated_identifiers<S: AsRef<str>>(
+        mut self,
+        identifiers: impl IntoIterator<Item = S>,
+    ) -> Self {
+        for identifier in identifiers {
+            let identifier_words = words(identifier.as_ref());
+            if !self.generated_identifiers.contains(&identifier_words) {
+                self.generated_identifiers.push(identifier_words);
+            }
+        }
+        self
+    }
+
+    fn with_replacements(
+        mut self,
+        forms: impl IntoIterator<Item = ((Case, String), (Case, String))>,
+    ) -> Self {
+        for ((case, old), (_, new)) in forms {
+            match self.replacements.iter_mut().find(|r| r.old == old) {
+                Some(replacement) => {
+                    if !replacement.new_by_case.iter().any(|(c, _)| *c == case) {
+                        replacement.new_by_case.push((case, new));
+                    }
+                }
+                None => self.replacements.push(Replacement {
+                    old,
+                    new_by_case: vec![(case, new)],
+                }),
+            }
+        }
+        self.replacements
+            .sort_by_key(|r| std::cmp::Reverse(r.old.chars().count()));
+        self
+    }
+
+    /// Replaces every occurrence of the old name that isn't part of a longer word
+    ///
+    /// Single word lowercase names that are not next to a `_`, a `-` or an uppercase letter
+    /// are replaced in the given `default_case`, eg. snake_case for Rust and camelCase for TypeScript
+    pub fn replace(&self, text: &str, default_case: Case) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut result = String::with_capacity(text.len());
+        let mut i = 0;
+
+        while i < chars.len() {
+            match self.replacement_at(&chars, i) {
+                Some(replacement) => {
+                    let end = i + replacement.old.chars().count();
+                    result.push_str(replacement.new_in_context(&chars, i, end, default_case));
+                    i = end;
+                }
+                None => {
+                    result.push(chars[i]);
+                    i += 1;
+                }
+            }
+        }
+
+        result
+    }
+
+    fn replacement_at(&self, chars: &[char], i: usize) -> Option<&Replacement> {
+        self.replacements.iter().find(|replacement| {
+            let old_chars: Vec<char> = replacement.old.chars().collect();
+            let end = i + old_chars.len();
+
+            if end > chars.len() || chars[i..end] != old_chars[..] {
+                return false;
+            }
+
+            let starts_lowercase = old_chars[0].is_lowercase();
+            // A lowercase name can't continue a previous word, eg. "repost"
+            if starts_lowercase && i > 0 && chars[i - 1].is_ascii_alphanumeric() {
+                return false;
+            }
+            // No name can be followed by more lowercase letters, eg. "Postgres" or "posted"
+            if end < chars.len() && (chars[end].is_lowercase() || chars[end].is_ascii_digit()) {
+                return false;
+            }
+
+            if self
+                .protected_identifiers
+                .contains(&identifier_at(chars, i, end))
+            {
+                return false;
+            }
+
+            // Inside a longer identifier, only the ones the scaffolder generated are renamed
+            let (word_start, word_end) = word_bounds(chars, i, end, |c| c == '-');
+            let identifier_words = words(&chars[word_start..word_end].iter().collect::<String>());
+            identifier_words == words(&replacement.old)
+                || self.generated_identifiers.contains(&identifier_words)
+        })
+    }
+}
+
+impl Replacement {
+    fn new_in_context(&self, chars: &[char], start: usize, end: usize, default_case: Case) -> &str {
+        let previous = start.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(end).copied();
+        let next_to = |c: char| previous == Some(c) || next == Some(c);
+
+        let case = if next_to('-') {
+            Case::Kebab
+        } else if next_to('_') {
+            Case::Snake
+        } else if next.is_some_and(|c| c.is_uppercase()) {
+            Case::Camel
+        } else {
+            default_case
+        };
+
+        // PascalCase is preferred over Title Case since code has to keep compiling
+        [case, Case::Pascal]
+            .iter()
+            .find_map(|case| {
+                self.new_by_case
+                    .iter()
+                    .find(|(c, _)| c == case)
+                    .map(|(_, new)| new.as_str())
+            })
+            .unwrap_or(&self.new_by_case[0].1)
+    }
+}
+
+/// The singular and plural forms of the name in every case convention the templates use
+fn case_forms(name: &str) -> Vec<(Case, String)> {
+    let cases = [
+        Case::Pascal,
+        Case::Camel,
+        Case::Snake,
+        Case::Kebab,
+        Case::Title,
+        Case::Lower,
+    ];
+    let plural = pluralizer::pluralize(&name.to_case(Case::Snake), 2, false);
+
+    [name.to_string(), plural]
+        .iter()
+        .flat_map(|n| cases.iter().map(|case| (*case, n.to_case(*case))))
+        .collect()
+}
+
+/// The whole identifier that contains the given range of characters
+fn identifier_at(chars: &[char], start: usize, end: usize) -> String {
+    let (start, end) = word_bounds(chars, start, end, |_| false);
+    chars[start..end].iter().collect()
+}
+
+/// The bounds of the identifier that contains the given range of characters,
+/// which can also contain the given separators, eg. `-` for kebab-case names
+fn word_bounds(
+    chars: &[char],
+    start: usize,
+    end: usize,
+    is_separator: impl Fn(char) -> bool,
+) -> (usize, usize) {
+    let is_identifier_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || is_separator(c);
+
+    let mut start = start;
+    while start > 0 && is_identifier_char(chars[start - 1]) {
+        start -= 1;
+    }
+    let mut end = end;
+    while end < chars.len() && is_identifier_char(chars[end]) {
+        end += 1;
+    }
+
+    (start, end)
+}
+
+/// The lowercase words of an identifier in any case convention, eg. `["create", "event"]` for `createEvent`
+fn words(identifier: &str) -> Vec<String> {
+    identifier
+        .to_case(Case::Snake)
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Replaces the given identifier only where it's not part of a longer one, eg. `posts` but not `posts_integrity`
+pub fn replace_identifier(text: &str, old_identifier: &str, new_identifier: &str) -> String {
+    let regex = Regex::new(&format!(r"\b{}\b", regex::escape(old_identifier)))
+        .expect("Failed to create regex");
+    regex
+        .replace_all(text, regex::NoExpand(new_identifier))
+        .to_string()
+}
+
+/// Replaces the name in the file name of the given path, leaving its folders untouched
+///
+/// Rust modules are named in snake_case, and UI and tests files in kebab-case
+pub fn rename_file_name(path: &Path, replacer: &NameReplacer) -> PathBuf {
+    let default_case = match is_rust_file(path) {
+        true => Case::Snake,
+        false => Case::Kebab,
+    };
+    match path.file_name() {
+        Some(file_name) => {
+            path.with_file_name(replacer.replace(&file_name.to_string_lossy(), default_case))
+        }
+        None => path.to_path_buf(),
+    }
+}
+
+/// The case convention for single word names in the code of the given file
+pub fn identifier_case(path: &Path) -> Case {
+    match is_rust_file(path) {
+        true => Case::Snake,
+        false => Case::Camel,
+    }
+}
+
+fn is_rust_file(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "rs")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_all_case_forms_of_a_name() {
+        let replacer = NameReplacer::new("blog_post", "article")
+            .protect(vec![String::from("posts")])
+            .generated_identifiers([
+                "validate_create_blog_post",
+                "BlogPostUpdates",
+                "get_all_blog_posts",
+                "blog-post-detail",
+            ]);
+
+        assert_eq!(
+            replacer.replace(
+                "pub fn validate_create_blog_post(blog_post: BlogPost)",
+                Case::Snake
+            ),
+            "pub fn validate_create_article(article: Article)"
+        );
+        assert_eq!(
+            replacer.replace(
+                "LinkTypes::BlogPostUpdates, getAllBlogPosts, <blog-post-detail>",
+                Case::Camel
+            ),
+            "LinkTypes::ArticleUpdates, getAllArticles, <article-detail>"
+        );
+        assert_eq!(
+            replacer.replace("No blog posts found, create a Blog Post", Case::Camel),
+            "No articles found, create a Article"
+        );
+    }
+
+    #[test]
+    fn guesses_the_case_of_single_word_names_from_their_context() {
+        let replacer = NameReplacer::new("post", "blog_post").generated_identifiers([
+            "get_post",
+            "post_hash",
+            "post-detail",
+        ]);
+
+        assert_eq!(
+            replacer.replace("let post = get_post(post_hash)?; Post", Case::Snake),
+            "let blog_post = get_blog_post(blog_post_hash)?; BlogPost"
+        );
+        assert_eq!(
+            replacer.replace("const post = postHash; <post-detail>", Case::Camel),
+            "const blogPost = blogPostHash; <blog-post-detail>"
+        );
+        assert_eq!(
+            rename_file_name(Path::new("tests/src/forum/posts/post.test.ts"), &replacer),
+            PathBuf::from("tests/src/forum/posts/blog-post.test.ts")
+        );
+    }
+
+    #[test]
+    fn leaves_longer_words_and_protected_identifiers_untouched() {
+        let replacer = NameReplacer::new("post", "article")
+            .protect(vec![String::from("posts"), String::from("posts_integrity")])
+            .generated_identifiers(["create_post", "post_hash", "PostToComments"]);
+
+        assert_eq!(
+            replacer.replace(
+                "use posts_integrity::*; fn post_commit() {} repost Postgres zome_name: \"posts\"",
+                Case::Snake
+            ),
+            "use posts_integrity::*; fn post_commit() {} repost Postgres zome_name: \"posts\""
+        );
+        assert_eq!(
+            replacer.replace("createPost(post_hash) -> PostToComments", Case::Camel),
+            "createArticle(article_hash) -> ArticleToComments"
+        );
+        assert_eq!(
+            replacer.replace("getPostAuthor(alicePosts)", Case::Camel),
+            "getPostAuthor(alicePosts)"
+        );
+
+        let replacer = NameReplacer::new("post", "article").keep(["post_comment"]);
+        assert_eq!(
+            replacer.replace(
+                "Post, PostComment, createPostComment, post_comments",
+                Case::Camel
+            ),
+            "Article, PostComment, createPostComment, post_comments"
+        );
+    }
+
+    #[test]
+    fn leaves_framework_identifiers_that_contain_the_name_untouched() {
+        let replacer = NameReplacer::new("event", "meetup").generated_identifiers([
+            "create_event",
+            "event-created",
+            "EventDetail",
+            "get_all_events",
+        ]);
+
+        assert_eq!(
+            replacer.replace(
+                "import { createEventDispatcher } from 'svelte'; new CustomEvent('event'); createEvent(event)",
+                Case::Camel
+            ),
+            "import { createEventDispatcher } from 'svelte'; new CustomEvent('meetup'); createMeetup(meetup)"
+        );
+        assert_eq!(
+            replacer.replace(
+                "<CreateEvent on:event-created />, EventDetail, get_all_events, EventToAttendees",
+                Case::Camel
+            ),
+            "<CreateMeetup on:meetup-created />, MeetupDetail, get_all_meetups, EventToAttendees"
+        );
+
+        let replacer = NameReplacer::new("link", "bookmark").generated_identifiers([
+            "AllLinks",
+            "link_hash",
+            "create_link",
+        ]);
+        assert_eq!(
+            replacer.replace(
+                "LinkTypes::AllLinks, create_link(path, link_hash, ()), get_links(input), Link",
+                Case::Snake
+            ),
+            "LinkTypes::AllBookmarks, create_link(path, bookmark_hash, ()), get_links(input), Bookmark"
+        );
+    }
+}
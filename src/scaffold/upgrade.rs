@@ -0,0 +1,267 @@
+//! Migrations of a scaffolded project to the dependency versions and code patterns
+//! generated by this version of the scaffolding tool, as run by `hc-scaffold upgrade`
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use holochain_types::prelude::DnaManifest;
+use serde_json::Value;
+use syn::visit_mut::VisitMut;
+
+use crate::{
+    error::{ScaffoldError, ScaffoldResult},
+    file_tree::{file_content, insert_file, map_rust_files, FileTree},
+    versions,
+};
+
+use super::{
+    app::cargo::{add_workspace_external_dependency, get_workspace_cargo_toml},
+    dna::{find_dna_manifests, DnaFileTree},
+    zome::ZomeFileTree,
+};
+
+/// A migration of the project, which leaves the project untouched if it's already migrated
+pub struct MigrationStep {
+    /// The holochain version the step migrates the project to
+    pub holochain_version: &'static str,
+    pub description: &'static str,
+    /// Migrates the given project, returning a description of each change that was made
+    pub migrate: fn(&mut FileTree) -> ScaffoldResult<Vec<String>>,
+}
+
+/// The migration steps, in the order they have to be applied
+pub fn migration_steps() -> Vec<MigrationStep> {
+    vec![
+        MigrationStep {
+            holochain_version: versions::HOLOCHAIN_VERSION,
+            description: "Upgrade hdk and hdi in the workspace Cargo.toml",
+            migrate: upgrade_cargo_dependencies,
+        },
+        MigrationStep {
+            holochain_version: versions::HOLOCHAIN_VERSION,
+            description: "Upgrade the holochain npm packages in the package.json files",
+            migrate: upgrade_npm_dependencies,
+        },
+        MigrationStep {
+            holochain_version: versions::HOLOCHAIN_VERSION,
+            description:
+                "Add the CreateLink action to the LinkDeleted signal of the coordinator zomes",
+            migrate: add_create_link_action_to_link_deleted_signal,
+        },
+    ]
+}
+
+fn upgrade_cargo_dependencies(file_tree: &mut FileTree) -> ScaffoldResult<Vec<String>> {
+    let cargo_toml = get_workspace_cargo_toml(file_tree)?;
+    let mut changes = vec![];
+
+    for (crate_name, version) in [
+        ("hdk", versions::HDK_VERSION),
+        ("hdi", versions::HDI_VERSION),
+    ] {
+        let Some(current) = cargo_toml
+            .get("workspace")
+            .and_then(|w| w.get("dependencies"))
+            .and_then(|d| d.get(crate_name))
+        else {
+            continue;
+        };
+        let version = format!("={version}");
+
+        if current.as_str() != Some(version.as_str()) {
+            *file_tree =
+                add_workspace_external_dependency(file_tree.clone(), crate_name, &version)?;
+            changes.push(format!(
+                "Cargo.toml: {crate_name} {current} -> \"{version}\""
+            ));
+        }
+    }
+
+    Ok(changes)
+}
+
+fn upgrade_npm_dependencies(file_tree: &mut FileTree) -> ScaffoldResult<Vec<String>> {
+    let packages = [
+        ("@holochain/client", versions::HOLOCHAIN_CLIENT_VERSION),
+        ("@holochain/tryorama", versions::TRYORAMA_VERSION),
+        ("@holochain/hc-spin", versions::HC_SPIN_VERSION),
+    ];
+    let mut changes = vec![];
+
+    for package_json_path in ["package.json", "ui/package.json", "tests/package.json"] {
+        let package_json_path = Path::new(package_json_path);
+        let Ok(content) = file_content(file_tree, package_json_path) else {
+            continue;
+        };
+        let mut package_json: Value = serde_json::from_str(&content)?;
+        let mut changed = false;
+
+        for field in ["dependencies", "devDependencies"] {
+            let Some(dependencies) = package_json.get_mut(field).and_then(|d| d.as_object_mut())
+            else {
+                continue;
+            };
+            for (package, version) in packages {
+                if let Some(current) = dependencies.get_mut(package) {
+                    if current.as_str() != Some(version) {
+                        changes.push(format!(
+                            "{}: {package} {current} -> \"{version}\"",
+                            package_json_path.display()
+                        ));
+                        *current = Value::String(version.to_string());
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        if changed {
+            insert_file(
+                file_tree,
+                package_json_path,
+                &format!("{}\n", serde_json::to_string_pretty(&package_json)?),
+            )?;
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Older versions of the scaffolding tool emitted the LinkDeleted signal without the action
+/// that created the deleted link
+fn add_create_link_action_to_link_deleted_signal(
+    file_tree: &mut FileTree,
+) -> ScaffoldResult<Vec<String>> {
+    let mut changes = vec![];
+
+    for crate_src_path in coordinator_src_paths(file_tree)? {
+        let lib_rs_path = crate_src_path.join("lib.rs");
+        let original_content = file_content(file_tree, &lib_rs_path)?;
+
+        let v: Vec<OsString> = crate_src_path.iter().map(|s| s.to_os_string()).collect();
+        map_rust_files(
+            file_tree
+                .path_mut(&mut v.iter())
+                .ok_or(ScaffoldError::PathNotFound(crate_src_path.clone()))?,
+            |file_path, mut file| {
+                if file_path == Path::new("lib.rs") {
+                    for item in &mut file.items {
+                        match item {
+                            syn::Item::Enum(item_enum) if item_enum.ident == "Signal" => {
+                                add_create_link_action_to_signal_enum(item_enum);
+                            }
+                            syn::Item::Fn(item_fn) if item_fn.sig.ident == "signal_action" => {
+                                AddCreateLinkAction::default().visit_item_fn_mut(item_fn);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Ok(file)
+            },
+        )?;
+
+        if file_content(file_tree, &lib_rs_path)? != original_content {
+            changes.push(format!(
+                "{}: added create_link_action to Signal::LinkDeleted",
+                lib_rs_path.display()
+            ));
+        }
+    }
+
+    Ok(changes)
+}
+
+fn add_create_link_action_to_signal_enum(signal_enum: &mut syn::ItemEnum) {
+    for variant in &mut signal_enum.variants {
+        let syn::Fields::Named(fields) = &mut variant.fields else {
+            continue;
+        };
+        let has_create_link_action = fields
+            .named
+            .iter()
+            .any(|f| f.ident.as_ref().is_some_and(|i| i == "create_link_action"));
+
+        if variant.ident == "LinkDeleted" && !has_create_link_action {
+            // Right after the action, as the scaffolding tool generates it
+            let position = fields.named.len().min(1);
+            fields.named.insert(
+                position,
+                syn::parse_quote! { create_link_action: SignedActionHashed },
+            );
+        }
+    }
+}
+
+#[derive(Default)]
+struct AddCreateLinkAction {
+    changed: bool,
+}
+
+impl VisitMut for AddCreateLinkAction {
+    fn visit_expr_struct_mut(&mut self, expr_struct: &mut syn::ExprStruct) {
+        let is_link_deleted = expr_struct
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "LinkDeleted");
+        let has_create_link_action = expr_struct.fields.iter().any(
+            |f| matches!(&f.member, syn::Member::Named(ident) if ident == "create_link_action"),
+        );
+
+        if is_link_deleted && !has_create_link_action {
+            expr_struct.fields.push(syn::parse_quote! {
+                create_link_action: record.signed_action.clone()
+            });
+            self.changed = true;
+        }
+
+        syn::visit_mut::visit_expr_struct_mut(self, expr_struct);
+    }
+}
+
+fn coordinator_src_paths(file_tree: &FileTree) -> ScaffoldResult<Vec<PathBuf>> {
+    let mut paths = vec![];
+
+    for dna_manifest_path in find_dna_manifests(file_tree)?.into_keys() {
+        let dna_file_tree =
+            DnaFileTree::from_dna_manifest_path(file_tree.clone(), &dna_manifest_path)?;
+        let DnaManifest::V1(v1) = dna_file_tree.dna_manifest.clone();
+
+        for zome_manifest in v1.coordinator.zomes {
+            // Zomes that are not built from a crate in the workspace can't be migrated
+            if let Ok(zome_file_tree) =
+                ZomeFileTree::from_zome_manifest(dna_file_tree.clone(), zome_manifest)
+            {
+                paths.push(zome_file_tree.zome_crate_path.join("src"));
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_create_link_action_to_link_deleted_signal() {
+        let mut signal_action: syn::ItemFn = syn::parse_quote! {
+            fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
+                emit_signal(Signal::LinkDeleted { action, link_type })?;
+                Ok(())
+            }
+        };
+
+        let mut visitor = AddCreateLinkAction::default();
+        visitor.visit_item_fn_mut(&mut signal_action);
+        assert!(visitor.changed);
+
+        let mut visitor = AddCreateLinkAction::default();
+        visitor.visit_item_fn_mut(&mut signal_action);
+        assert!(!visitor.changed);
+    }
+}
@@ -6,6 +6,7 @@ use crate::error::ScaffoldResult;
 use crate::reserved_words::check_for_reserved_keywords;
 use crate::templates::web_app::scaffold_web_app_template;
 use crate::templates::ScaffoldedTemplate;
+use crate::utils::Interactivity;
 use crate::{error::ScaffoldError, file_tree::FileTree};
 
 use super::app::{
@@ -25,6 +26,7 @@ pub fn scaffold_web_app(
     skip_nix: bool,
     template_file_tree: &FileTree,
     holo_enabled: bool,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     check_for_reserved_keywords(app_name)?;
 
@@ -64,6 +66,7 @@ pub fn scaffold_web_app(
         app_name,
         package_manager,
         holo_enabled,
+        interactivity,
     )?;
 
     Ok(scaffold_template_result)
@@ -7,6 +7,7 @@ use serde::Serialize;
 use crate::{
     error::{ScaffoldError, ScaffoldResult},
     file_tree::FileTree,
+    utils::Interactivity,
 };
 
 /// Represents different package managers that can be used.
@@ -31,7 +32,9 @@ pub enum SubCommand {
 }
 
 impl PackageManager {
-    pub fn choose() -> ScaffoldResult<PackageManager> {
+    pub fn choose(interactivity: Interactivity) -> ScaffoldResult<PackageManager> {
+        interactivity.ensure_interactive("--package-manager")?;
+
         let managers = [
             PackageManager::Bun,
             PackageManager::Npm,
@@ -130,10 +133,13 @@ impl std::str::FromStr for PackageManager {
     }
 }
 
-impl TryFrom<&FileTree> for PackageManager {
-    type Error = ScaffoldError;
-
-    fn try_from(app_file_tree: &FileTree) -> ScaffoldResult<PackageManager> {
+impl PackageManager {
+    /// Gets the package manager from the lockfile in the app file tree,
+    /// or prompts to choose one if there is none
+    pub fn from_file_tree(
+        app_file_tree: &FileTree,
+        interactivity: Interactivity,
+    ) -> ScaffoldResult<PackageManager> {
         if PackageManager::lockfile_exists(app_file_tree, Path::new("bun.lockb")) {
             Ok(PackageManager::Bun)
         } else if PackageManager::lockfile_exists(app_file_tree, Path::new("package-lock.json")) {
@@ -143,11 +149,19 @@ impl TryFrom<&FileTree> for PackageManager {
         } else if PackageManager::lockfile_exists(app_file_tree, Path::new("yarn.lock")) {
             Ok(PackageManager::Yarn)
         } else {
-            PackageManager::choose()
+            PackageManager::choose(interactivity)
         }
     }
 }
 
+impl TryFrom<&FileTree> for PackageManager {
+    type Error = ScaffoldError;
+
+    fn try_from(app_file_tree: &FileTree) -> ScaffoldResult<PackageManager> {
+        PackageManager::from_file_tree(app_file_tree, Interactivity::Interactive)
+    }
+}
+
 impl From<&str> for SubCommand {
     fn from(s: &str) -> Self {
         match s {
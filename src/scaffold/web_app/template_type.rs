@@ -10,6 +10,7 @@ use crate::{
         dir_exists, file_content, file_exists, load_directory_into_memory,
         template_dirs_to_file_tree, FileTree,
     },
+    utils::Interactivity,
 };
 
 static LIT_TEMPLATES: Dir<'static> =
@@ -76,7 +77,9 @@ impl TemplateType {
         template_dirs_to_file_tree(ui_framework_dir, &GENERIC_TEMPLATES)
     }
 
-    pub fn choose() -> ScaffoldResult<TemplateType> {
+    pub fn choose(interactivity: Interactivity) -> ScaffoldResult<TemplateType> {
+        interactivity.ensure_interactive("--template")?;
+
         let frameworks = [
             TemplateType::Lit,
             TemplateType::Svelte,
@@ -93,7 +96,9 @@ impl TemplateType {
         Ok(frameworks[selection].clone())
     }
 
-    pub fn choose_non_vanilla() -> ScaffoldResult<TemplateType> {
+    pub fn choose_non_vanilla(interactivity: Interactivity) -> ScaffoldResult<TemplateType> {
+        interactivity.ensure_interactive("--template")?;
+
         let frameworks = [
             TemplateType::Lit,
             TemplateType::Svelte,
@@ -108,7 +113,9 @@ impl TemplateType {
         Ok(frameworks[selection].clone())
     }
 
-    pub fn choose_non_headless() -> ScaffoldResult<TemplateType> {
+    pub fn choose_non_headless(interactivity: Interactivity) -> ScaffoldResult<TemplateType> {
+        interactivity.ensure_interactive("--template")?;
+
         let frameworks = [
             TemplateType::Lit,
             TemplateType::Svelte,
@@ -139,12 +146,13 @@ impl From<PathBuf> for TemplateType {
     }
 }
 
-impl TryFrom<&FileTree> for TemplateType {
-    type Error = ScaffoldError;
-
+impl TemplateType {
     /// Try to get ui framework from app file tree, if the ui framework cannot be inferred, then
     /// the user will be prompted to choose one via `TemplateType::choose`
-    fn try_from(app_file_tree: &FileTree) -> Result<Self, Self::Error> {
+    pub fn from_file_tree(
+        app_file_tree: &FileTree,
+        interactivity: Interactivity,
+    ) -> ScaffoldResult<Self> {
         let ui_package_json_path = PathBuf::from("ui/package.json");
         if file_exists(app_file_tree, &ui_package_json_path) {
             let v: Vec<OsString> = ui_package_json_path
@@ -169,7 +177,15 @@ impl TryFrom<&FileTree> for TemplateType {
                 return Ok(TemplateType::Vanilla);
             }
         }
-        TemplateType::choose()
+        TemplateType::choose(interactivity)
+    }
+}
+
+impl TryFrom<&FileTree> for TemplateType {
+    type Error = ScaffoldError;
+
+    fn try_from(app_file_tree: &FileTree) -> Result<Self, Self::Error> {
+        TemplateType::from_file_tree(app_file_tree, Interactivity::Interactive)
     }
 }
 
@@ -8,13 +8,13 @@ use std::{
 };
 
 use crate::{
-    file_tree::{build_file_tree, file_exists, insert_file_tree_in_dir, FileTree},
+    file_tree::{file_exists, insert_file, insert_file_tree_in_dir, FileTree},
     reserved_words::check_for_reserved_keywords,
     templates::{
         coordinator::scaffold_coordinator_zome_templates,
         integrity::scaffold_integrity_zome_templates, ScaffoldedTemplate,
     },
-    utils::{input_with_case, unparse_pretty},
+    utils::{input_with_case, unparse_pretty, Interactivity},
     versions,
 };
 use build_fs_tree::{dir, file};
@@ -32,13 +32,16 @@ pub mod utils;
 use super::{
     app::cargo::{
         add_workspace_external_dependency, add_workspace_path_dependency, get_workspace_members,
-        get_workspace_packages_locations, workspace_package_path,
+        get_workspace_packages, get_workspace_packages_locations, rename_workspace_path_dependency,
+        workspace_package_path,
     },
     dna::{
         coordinator::{add_coordinator_zome_to_manifest, new_coordinator_zome_manifest},
         integrity::{add_integrity_zome_to_manifest, new_integrity_zome_manifest},
         DnaFileTree,
     },
+    remove::{is_code_file, ui_and_tests_folders},
+    rename::{replace_identifier, Renaming},
 };
 
 pub struct ZomeFileTree {
@@ -51,6 +54,7 @@ impl ZomeFileTree {
     pub fn get_or_choose_integrity(
         dna_file_tree: DnaFileTree,
         integrity_zome_name: Option<&str>,
+        interactivity: Interactivity,
     ) -> ScaffoldResult<ZomeFileTree> {
         let integrity_zomes = match dna_file_tree.dna_manifest.clone() {
             DnaManifest::V1(v1) => v1.integrity.zomes.clone(),
@@ -68,9 +72,11 @@ impl ZomeFileTree {
                         dna_file_tree.dna_manifest.name(),
                     ))
             }
-            (_, None) => {
-                choose_integrity_zome(&dna_file_tree.dna_manifest.name(), &integrity_zomes)
-            }
+            (_, None) => choose_integrity_zome(
+                &dna_file_tree.dna_manifest.name(),
+                &integrity_zomes,
+                interactivity,
+            ),
             (_, Some(name)) => integrity_zomes
                 .into_iter()
                 .find(|zome| zome.name.0.to_string().eq(name))
@@ -149,7 +155,10 @@ pub fn iter_all_eq<T: PartialEq>(iter: impl IntoIterator<Item = T>) -> Option<T>
 fn choose_integrity_zome(
     dna_name: &str,
     integrity_zomes: &[ZomeManifest],
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ZomeManifest> {
+    interactivity.ensure_interactive("--zome")?;
+
     let integrity_zome_names: Vec<String> = integrity_zomes
         .iter()
         .map(|z| z.name.0.to_string())
@@ -353,6 +362,7 @@ pub fn scaffold_integrity_zome_with_path(
     template_file_tree: &FileTree,
     zome_name: &str,
     path: &Path,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     check_for_reserved_keywords(zome_name)?;
 
@@ -393,6 +403,7 @@ pub fn scaffold_integrity_zome_with_path(
         template_file_tree,
         &dna_manifest.name(),
         &zome_manifest,
+        interactivity,
     )
 }
 
@@ -401,27 +412,34 @@ pub fn scaffold_integrity_zome(
     template_file_tree: &FileTree,
     zome_name: &str,
     path: &Option<PathBuf>,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     let path_to_scaffold_in = match path {
         Some(p) => p.clone(),
         None => match try_to_guess_integrity_zomes_location(&dna_file_tree)? {
             Some(p) => {
-                if Confirm::with_theme(&ColorfulTheme::default())
-                    .with_prompt(format!("Scaffold integrity zome in folder {:?}?", p))
-                    .interact()?
+                if !interactivity.is_interactive()
+                    || Confirm::with_theme(&ColorfulTheme::default())
+                        .with_prompt(format!("Scaffold integrity zome in folder {:?}?", p))
+                        .interact()?
                 {
                     p
                 } else {
                     choose_directory_path(
                         &String::from("Where should the integrity zome be scaffolded instead?"),
                         dna_file_tree.file_tree_ref(),
+                        interactivity,
                     )?
                 }
             }
-            None => choose_directory_path(
-                &String::from("Where should the integrity zome be scaffolded?"),
-                dna_file_tree.file_tree_ref(),
-            )?,
+            None => {
+                interactivity.ensure_interactive("--integrity")?;
+                choose_directory_path(
+                    &String::from("Where should the integrity zome be scaffolded?"),
+                    dna_file_tree.file_tree_ref(),
+                    interactivity,
+                )?
+            }
         },
     };
 
@@ -430,6 +448,7 @@ pub fn scaffold_integrity_zome(
         template_file_tree,
         zome_name,
         &path_to_scaffold_in,
+        interactivity,
     )
 }
 
@@ -439,6 +458,7 @@ pub fn scaffold_coordinator_zome_in_path(
     zome_name: &str,
     dependencies: Option<&Vec<String>>,
     path: &Path,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     check_for_reserved_keywords(zome_name)?;
 
@@ -472,6 +492,7 @@ pub fn scaffold_coordinator_zome_in_path(
         template_file_tree,
         &dna_manifest.name(),
         &coordinator_zome_manifest,
+        interactivity,
     )
 }
 
@@ -481,6 +502,7 @@ pub fn scaffold_coordinator_zome(
     zome_name: &str,
     dependencies: Option<&Vec<String>>,
     path: &Option<PathBuf>,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     let prompt = String::from("Where should the coordinator zome be scaffolded?");
 
@@ -488,16 +510,20 @@ pub fn scaffold_coordinator_zome(
         Some(p) => p.clone(),
         None => match try_to_guess_coordinator_zomes_location(&dna_file_tree)? {
             Some(p) => {
-                if Confirm::with_theme(&ColorfulTheme::default())
-                    .with_prompt(format!("Scaffold coordinator zome in {:?}?", p))
-                    .interact()?
+                if !interactivity.is_interactive()
+                    || Confirm::with_theme(&ColorfulTheme::default())
+                        .with_prompt(format!("Scaffold coordinator zome in {:?}?", p))
+                        .interact()?
                 {
                     p
                 } else {
-                    choose_directory_path(&prompt, dna_file_tree.file_tree_ref())?
+                    choose_directory_path(&prompt, dna_file_tree.file_tree_ref(), interactivity)?
                 }
             }
-            None => choose_directory_path(&prompt, dna_file_tree.file_tree_ref())?,
+            None => {
+                interactivity.ensure_interactive("--coordinator")?;
+                choose_directory_path(&prompt, dna_file_tree.file_tree_ref(), interactivity)?
+            }
         },
     };
 
@@ -507,6 +533,7 @@ pub fn scaffold_coordinator_zome(
         zome_name,
         dependencies,
         &path_to_scaffold_in,
+        interactivity,
     )
 }
 
@@ -514,14 +541,17 @@ pub fn scaffold_zome_pair(
     app_file_tree: FileTree,
     template_file_tree: FileTree,
     dna_name: &str,
-) -> Result<(), ScaffoldError> {
-    let mut dna_file_tree = DnaFileTree::get_or_choose(app_file_tree, Some(dna_name))?;
+    interactivity: Interactivity,
+) -> ScaffoldResult<FileTree> {
+    let mut dna_file_tree =
+        DnaFileTree::get_or_choose(app_file_tree, Some(dna_name), interactivity)?;
     let dna_manifest_path = dna_file_tree.dna_manifest_path.clone();
 
     let zome_name = input_with_case(
             "Enter coordinator zome name (snake_case):\n(The integrity zome will automatically be named '{name of coordinator zome}_integrity')\n",
             Some( dna_name ),
             Case::Snake,
+            interactivity,
         )?;
 
     let integrity_zome_name = integrity_zome_name(&zome_name);
@@ -530,6 +560,7 @@ pub fn scaffold_zome_pair(
         &template_file_tree,
         &integrity_zome_name,
         &None,
+        interactivity,
     )?;
     dna_file_tree = DnaFileTree::from_dna_manifest_path(file_tree, &dna_manifest_path)?;
 
@@ -539,8 +570,231 @@ pub fn scaffold_zome_pair(
         &zome_name,
         Some(&vec![integrity_zome_name]),
         &None,
+        interactivity,
+    )?;
+
+    Ok(file_tree)
+}
+
+/// Renames a zome in the DNA manifest, in its crate and in the workspace, and moves its UI and tests
+///
+/// Renaming a coordinator zome also renames its `<name>_integrity` zome, if there is one
+pub fn rename_zome(
+    dna_file_tree: DnaFileTree,
+    old_name: &str,
+    new_name: &str,
+) -> ScaffoldResult<Renaming> {
+    check_for_reserved_keywords(new_name)?;
+
+    let dna_name = dna_file_tree.dna_manifest.name();
+    let DnaManifest::V1(mut manifest) = dna_file_tree.dna_manifest.clone();
+
+    let find_zome = |name: &str| {
+        manifest
+            .integrity
+            .zomes
+            .iter()
+            .chain(manifest.coordinator.zomes.iter())
+            .find(|z| z.name.0.eq(name))
+            .cloned()
+    };
+
+    let Some(zome_manifest) = find_zome(old_name) else {
+        return Err(ScaffoldError::ZomeNotFound(old_name.to_owned(), dna_name));
+    };
+
+    let mut renamed_zomes = vec![(zome_manifest, new_name.to_owned())];
+    let is_coordinator = manifest
+        .coordinator
+        .zomes
+        .iter()
+        .any(|z| z.name.0.eq(old_name));
+    if is_coordinator {
+        if let Some(integrity_manifest) = find_zome(&integrity_zome_name(old_name)) {
+            renamed_zomes.push((integrity_manifest, integrity_zome_name(new_name)));
+        }
+    }
+
+    for (_, new_zome_name) in &renamed_zomes {
+        if find_zome(new_zome_name).is_some() {
+            return Err(ScaffoldError::ZomeAlreadyExists(
+                new_zome_name.clone(),
+                dna_name,
+            ));
+        }
+    }
+
+    // (old crate name, new crate name, old crate path, new crate path)
+    let mut renamed_crates = Vec::new();
+    for (zome_manifest, new_zome_name) in &renamed_zomes {
+        let old_crate_path = zome_crate_path(&dna_file_tree, zome_manifest)?;
+        let old_crate_name = wasm_crate_name(&zome_manifest.location).unwrap_or_default();
+        let new_crate_name = match old_crate_name.eq(zome_manifest.name.0.as_ref()) {
+            true => new_zome_name.clone(),
+            false => old_crate_name.clone(),
+        };
+
+        let folder_name = old_crate_path
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let new_folder_name = if folder_name.eq(&old_crate_name) {
+            new_crate_name.clone()
+        } else if old_crate_name.strip_suffix("_integrity") == Some(folder_name.as_str()) {
+            new_crate_name
+                .strip_suffix("_integrity")
+                .unwrap_or(&new_crate_name)
+                .to_string()
+        } else {
+            folder_name
+        };
+
+        let new_crate_path = old_crate_path.with_file_name(new_folder_name);
+        renamed_crates.push((
+            old_crate_name,
+            new_crate_name,
+            old_crate_path,
+            new_crate_path,
+        ));
+    }
+
+    let renamed_zome_names: Vec<(String, String)> = renamed_zomes
+        .iter()
+        .map(|(z, new_zome_name)| (z.name.0.to_string(), new_zome_name.clone()))
+        .collect();
+    let rename_zome_name = |name: &str| {
+        renamed_zome_names
+            .iter()
+            .find(|(old, _)| old.eq(name))
+            .map(|(_, new)| new.clone())
+    };
+
+    for zome in manifest
+        .integrity
+        .zomes
+        .iter_mut()
+        .chain(manifest.coordinator.zomes.iter_mut())
+    {
+        if let Some(new_zome_name) = rename_zome_name(&zome.name.0) {
+            zome.name = new_zome_name.into();
+        }
+        if let Location::Bundled(wasm_path) = &zome.location {
+            let crate_name = wasm_crate_name(&zome.location).unwrap_or_default();
+            if let Some((_, new_crate_name, _, _)) =
+                renamed_crates.iter().find(|(old, ..)| old.eq(&crate_name))
+            {
+                zome.location =
+                    Location::Bundled(wasm_path.with_file_name(format!("{new_crate_name}.wasm")));
+            }
+        }
+        for dependency in zome.dependencies.iter_mut().flatten() {
+            if let Some(new_zome_name) = rename_zome_name(&dependency.name.0) {
+                dependency.name = new_zome_name.into();
+            }
+        }
+    }
+
+    let dna_manifest_path = dna_file_tree.dna_manifest_path.clone();
+    let mut file_tree = dna_file_tree.file_tree();
+    insert_file(
+        &mut file_tree,
+        &dna_manifest_path,
+        &serde_yaml::to_string(&DnaManifest::V1(manifest))?,
     )?;
 
-    build_file_tree(file_tree, ".")?;
-    Ok(())
+    let crate_paths: Vec<PathBuf> = get_workspace_packages(&file_tree)?
+        .into_values()
+        .filter_map(|cargo_toml_path| cargo_toml_path.parent().map(|p| p.to_path_buf()))
+        .collect();
+
+    for (old_crate_name, new_crate_name, _, new_crate_path) in &renamed_crates {
+        file_tree = rename_workspace_path_dependency(
+            file_tree,
+            old_crate_name,
+            new_crate_name,
+            new_crate_path,
+        )?;
+    }
+
+    let renaming = Renaming::new(file_tree).map_files(
+        |path| {
+            crate_paths.iter().any(|p| path.starts_with(p))
+                && (path.ends_with("Cargo.toml") || path.extension().is_some_and(|e| e.eq("rs")))
+        },
+        |_path, content| {
+            Ok(renamed_crates
+                .iter()
+                .fold(content.to_string(), |content, (old, new, _, _)| {
+                    replace_identifier(&content, old, new)
+                }))
+        },
+        |path| {
+            renamed_crates
+                .iter()
+                .find_map(|(_, _, old_crate_path, new_crate_path)| {
+                    path.strip_prefix(old_crate_path)
+                        .ok()
+                        .map(|relative_path| new_crate_path.join(relative_path))
+                })
+                .unwrap_or(path.to_path_buf())
+        },
+    )?;
+
+    // (old folder, new folder) of the UI and tests of each zome
+    let renamed_folders: Vec<(PathBuf, PathBuf)> = renamed_zome_names
+        .iter()
+        .flat_map(|(old, new)| {
+            ui_and_tests_folders(&dna_name, old)
+                .into_iter()
+                .zip(ui_and_tests_folders(&dna_name, new))
+        })
+        .collect();
+
+    renaming.map_files(
+        |path| {
+            ["ui", "tests"]
+                .iter()
+                .any(|folder| path.starts_with(PathBuf::from(folder).join("src")))
+                && is_code_file(path)
+        },
+        |path, content| {
+            let mut content = content.to_string();
+            for (old, new) in &renamed_zome_names {
+                content =
+                    content.replace(&format!("{dna_name}/{old}/"), &format!("{dna_name}/{new}/"));
+                if renamed_folders
+                    .iter()
+                    .any(|(old_folder, _)| path.starts_with(old_folder))
+                {
+                    for quote in ["\"", "'", "`"] {
+                        content = content.replace(
+                            &format!("{quote}{old}{quote}"),
+                            &format!("{quote}{new}{quote}"),
+                        );
+                    }
+                }
+            }
+            Ok(content)
+        },
+        |path| {
+            renamed_folders
+                .iter()
+                .find_map(|(old_folder, new_folder)| {
+                    path.strip_prefix(old_folder)
+                        .ok()
+                        .map(|relative_path| new_folder.join(relative_path))
+                })
+                .unwrap_or(path.to_path_buf())
+        },
+    )
+}
+
+/// The name of the crate that builds the wasm of the zome
+fn wasm_crate_name(location: &Location) -> Option<String> {
+    match location {
+        Location::Bundled(wasm_path) => wasm_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string()),
+        _ => None,
+    }
 }
@@ -1,4 +1,4 @@
-use std::{collections::BTreeMap, ffi::OsString};
+use std::{collections::BTreeMap, ffi::OsString, path::PathBuf};
 
 use dialoguer::{theme::ColorfulTheme, Select};
 use holochain_types::prelude::ZomeManifest;
@@ -10,6 +10,7 @@ use crate::{
     error::{ScaffoldError, ScaffoldResult},
     file_tree::find_map_rust_files,
     scaffold::dna::DnaFileTree,
+    utils::Interactivity,
 };
 
 use super::ZomeFileTree;
@@ -92,6 +93,7 @@ pub fn initial_lib_rs(dependencies: Option<&Vec<String>>) -> TokenStream {
 fn choose_extern_function(
     functions_by_zome: &BTreeMap<String, Vec<ItemFn>>,
     prompt: &str,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<(String, ItemFn)> {
     let all_functions: Vec<(String, ItemFn)> = functions_by_zome
         .iter()
@@ -102,6 +104,8 @@ fn choose_extern_function(
         })
         .collect();
 
+    interactivity.ensure_can_prompt(prompt)?;
+
     let all_fns_str: Vec<String> = all_functions
         .iter()
         .map(|(z, f)| format!(r#""{}", in zome "{}""#, f.sig.ident, z))
@@ -121,6 +125,7 @@ pub fn find_extern_function_or_choose(
     coordinator_zomes: &Vec<ZomeManifest>,
     fn_name_to_find: &str,
     prompt: &str,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<(ZomeManifest, ItemFn)> {
     let mut functions_by_zome: BTreeMap<String, Vec<ItemFn>> = BTreeMap::new();
 
@@ -143,7 +148,7 @@ pub fn find_extern_function_or_choose(
         functions_by_zome.insert(coordinator_zome.name.to_string(), all_extern_functions);
     }
 
-    let (zome_name, fn_name) = choose_extern_function(&functions_by_zome, prompt)?;
+    let (zome_name, fn_name) = choose_extern_function(&functions_by_zome, prompt, interactivity)?;
 
     let chosen_zome = coordinator_zomes
         .iter()
@@ -194,6 +199,16 @@ pub fn find_extern_function_in_zomes(
 }
 
 pub fn find_all_extern_functions(zome_file_tree: &ZomeFileTree) -> ScaffoldResult<Vec<ItemFn>> {
+    Ok(find_all_extern_functions_by_file(zome_file_tree)?
+        .into_values()
+        .flatten()
+        .collect())
+}
+
+/// Returns the `#[hdk_extern]` functions of the given zome, indexed by the path of the file they are defined in
+pub fn find_all_extern_functions_by_file(
+    zome_file_tree: &ZomeFileTree,
+) -> ScaffoldResult<BTreeMap<PathBuf, Vec<ItemFn>>> {
     let crate_src_path = zome_file_tree.zome_crate_path.join("src");
     let v: Vec<OsString> = crate_src_path
         .clone()
@@ -227,5 +242,9 @@ pub fn find_all_extern_functions(zome_file_tree: &ZomeFileTree) -> ScaffoldResul
         },
     );
 
-    Ok(hdk_extern_instances.values().flatten().cloned().collect())
+    Ok(hdk_extern_instances
+        .into_iter()
+        .filter(|(_, functions)| !functions.is_empty())
+        .map(|(path, functions)| (crate_src_path.join(path), functions))
+        .collect())
 }
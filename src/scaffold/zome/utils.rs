@@ -1,7 +1,7 @@
 use dialoguer::{theme::ColorfulTheme, MultiSelect, Select};
 use holochain_types::prelude::{DnaManifest, ZomeManifest};
 
-use crate::error::ScaffoldResult;
+use crate::{error::ScaffoldResult, utils::Interactivity};
 
 /// Prompts a MultiSelect dialog to select one or multiple integrity zomes
 ///
@@ -9,6 +9,7 @@ use crate::error::ScaffoldResult;
 pub fn select_integrity_zomes(
     dna_manifest: &DnaManifest,
     prompt: Option<&str>,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<Vec<String>> {
     let integrity_zomes: Vec<String> = match dna_manifest {
         DnaManifest::V1(v1) => v1
@@ -25,6 +26,7 @@ pub fn select_integrity_zomes(
     }
 
     let prompt = prompt.unwrap_or("Select integrity zome (SPACE to select/unselect):");
+    interactivity.ensure_can_prompt(prompt)?;
 
     let selected_options = MultiSelect::with_theme(&ColorfulTheme::default())
         .with_prompt(prompt)
@@ -63,9 +65,13 @@ pub fn get_coordinator_zomes_for_integrity(
 ///
 /// # Example
 /// ```rs,no_run
-/// let (scaffold_integrity, scaffold_coordintor) = select_scaffold_zome_options().unwrap();
+/// let (scaffold_integrity, scaffold_coordintor) = select_scaffold_zome_options(Interactivity::Interactive).unwrap();
 /// ```
-pub fn select_scaffold_zome_options() -> ScaffoldResult<(bool, bool)> {
+pub fn select_scaffold_zome_options(interactivity: Interactivity) -> ScaffoldResult<(bool, bool)> {
+    if !interactivity.is_interactive() {
+        return Ok((true, true));
+    }
+
     let option = Select::with_theme(&ColorfulTheme::default())
         .with_prompt("What do you want to scaffold?")
         .default(0)
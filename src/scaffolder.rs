@@ -0,0 +1,459 @@
+//! Library API to scaffold code into a project at an explicit path,
+//! without depending on nor changing the working directory of the process
+
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use convert_case::Case;
+
+use crate::{
+    error::ScaffoldResult,
+    file_tree::{build_file_tree_with_mode, flatten_file_tree, load_directory_into_memory},
+    file_tree::{BuildMode, BuiltFileTree, FileTree},
+    scaffold::{
+        app::AppFileTree,
+        collection::{scaffold_collection, CollectionType},
+        dna::{scaffold_dna, DnaFileTree},
+        entry_type::{
+            crud::Crud,
+            definitions::{
+                EntryTypeReference, FieldDefinition, Permission, Referenceable, Visibility,
+            },
+            scaffold_entry_type,
+        },
+        link_type::scaffold_link_type,
+        web_app::template_type::TemplateType,
+        zome::{
+            integrity_zome_name, scaffold_coordinator_zome, scaffold_integrity_zome,
+            utils::select_integrity_zomes, ZomeFileTree,
+        },
+    },
+    templates::ScaffoldedTemplate,
+    utils::{check_case, run_cargo_fmt_if_available, Interactivity},
+};
+
+/// Scaffolds code into the project at the given root folder, with the given template
+///
+/// Each scaffolding method reads the project from disk and returns the scaffolded file tree of the whole project,
+/// which is only written to disk by [`Scaffolder::write`].
+/// Answers left as `None` are prompted for, unless the scaffolder is [`Interactivity::NonInteractive`].
+///
+/// A scaffolder keeps no state besides its settings, so several of them can work on different projects in the same process.
+pub struct Scaffolder {
+    project_dir: PathBuf,
+    template_file_tree: FileTree,
+    interactivity: Interactivity,
+}
+
+/// Answers for [`Scaffolder::dna`]
+#[derive(Debug, Clone, Default)]
+pub struct DnaAnswers {
+    /// The app to add the DNA to, which can be left out if the project has only one app
+    pub app: Option<String>,
+    pub name: String,
+}
+
+/// Answers for [`Scaffolder::zome`]
+#[derive(Debug, Clone, Default)]
+pub struct ZomeAnswers {
+    /// The DNA to add the zome to, which can be left out if the project has only one DNA
+    pub dna: Option<String>,
+    /// Name of the zome, or of the coordinator zome if both zomes are scaffolded,
+    /// in which case the integrity zome is named "<NAME>_integrity"
+    pub name: String,
+    pub integrity: bool,
+    /// Folder to scaffold the integrity zome in
+    pub integrity_path: Option<PathBuf>,
+    pub coordinator: bool,
+    /// Folder to scaffold the coordinator zome in
+    pub coordinator_path: Option<PathBuf>,
+    /// The integrity zomes that the coordinator zome depends on, when it's scaffolded without an integrity zome
+    pub dependencies: Option<Vec<String>>,
+}
+
+/// Answers for [`Scaffolder::entry_type`]
+#[derive(Debug, Clone, Default)]
+pub struct EntryTypeAnswers {
+    pub dna: Option<String>,
+    /// The integrity zome to add the entry type to, which can be left out if the DNA has only one
+    pub zome: Option<String>,
+    pub name: String,
+    pub crud: Option<Crud>,
+    pub reference_entry_hash: Option<bool>,
+    pub link_from_original_to_each_update: Option<bool>,
+    /// Whether the revisions of the entries are got one page at a time
+    pub paginated_revisions: bool,
+    pub visibility: Option<Visibility>,
+    pub permission: Option<Permission>,
+    pub fields: Option<Vec<FieldDefinition>>,
+    pub no_ui: bool,
+    pub no_spec: bool,
+}
+
+/// Answers for [`Scaffolder::link_type`]
+#[derive(Debug, Clone, Default)]
+pub struct LinkTypeAnswers {
+    pub dna: Option<String>,
+    pub zome: Option<String>,
+    pub from_referenceable: Option<Referenceable>,
+    pub to_referenceable: Option<Referenceable>,
+    pub delete: Option<bool>,
+    pub bidirectional: Option<bool>,
+    pub no_ui: bool,
+    pub no_spec: bool,
+}
+
+/// Answers for [`Scaffolder::collection`]
+#[derive(Debug, Clone, Default)]
+pub struct CollectionAnswers {
+    pub dna: Option<String>,
+    pub zome: Option<String>,
+    pub name: String,
+    pub collection_type: Option<CollectionType>,
+    pub entry_type: Option<EntryTypeReference>,
+    /// Whether the getter of the collection returns its links one page at a time
+    pub paginated: bool,
+    pub no_ui: bool,
+    pub no_spec: bool,
+}
+
+impl Scaffolder {
+    pub fn new(project_dir: impl Into<PathBuf>, template_file_tree: FileTree) -> Self {
+        Scaffolder {
+            project_dir: project_dir.into(),
+            template_file_tree,
+            interactivity: Interactivity::default(),
+        }
+    }
+
+    /// Sets whether the answers left as `None` can be prompted for
+    pub fn with_interactivity(mut self, interactivity: Interactivity) -> Self {
+        self.interactivity = interactivity;
+        self
+    }
+
+    pub fn with_template_type(
+        project_dir: impl Into<PathBuf>,
+        template_type: &TemplateType,
+    ) -> ScaffoldResult<Self> {
+        Ok(Scaffolder::new(project_dir, template_type.file_tree()?))
+    }
+
+    pub fn project_dir(&self) -> &Path {
+        &self.project_dir
+    }
+
+    pub fn template_file_tree(&self) -> &FileTree {
+        &self.template_file_tree
+    }
+
+    pub fn interactivity(&self) -> Interactivity {
+        self.interactivity
+    }
+
+    /// Reads the files of the project into memory
+    pub fn load_project(&self) -> ScaffoldResult<FileTree> {
+        load_directory_into_memory(&self.project_dir)
+    }
+
+    /// Name of the app that the scaffolded code goes in, which is prompted for
+    /// if it's left out and the project has several apps
+    pub fn choose_app(&self, app: Option<&str>) -> ScaffoldResult<String> {
+        let app_file_tree =
+            AppFileTree::get_or_choose(self.load_project()?, app, self.interactivity)?;
+
+        Ok(app_file_tree.app_manifest.app_name().to_string())
+    }
+
+    /// Name of the DNA that the scaffolded code goes in, which is prompted for
+    /// if it's left out and the project has several DNAs
+    pub fn choose_dna(&self, dna: Option<&str>) -> ScaffoldResult<String> {
+        let dna_file_tree =
+            DnaFileTree::get_or_choose(self.load_project()?, dna, self.interactivity)?;
+
+        Ok(dna_file_tree.dna_manifest.name())
+    }
+
+    /// Names of the DNA and of the integrity zome that the scaffolded code goes in,
+    /// which are prompted for if they are left out and there are several of them
+    pub fn choose_integrity_zome(
+        &self,
+        dna: Option<&str>,
+        zome: Option<&str>,
+    ) -> ScaffoldResult<(String, String)> {
+        let zome_file_tree = self.integrity_zome(dna, zome)?;
+
+        Ok((
+            zome_file_tree.dna_file_tree.dna_manifest.name(),
+            zome_file_tree.zome_manifest.name.0.to_string(),
+        ))
+    }
+
+    pub fn dna(&self, answers: DnaAnswers) -> ScaffoldResult<ScaffoldedTemplate> {
+        check_case(&answers.name, "dna name", Case::Snake)?;
+
+        let app_file_tree = AppFileTree::get_or_choose(
+            self.load_project()?,
+            answers.app.as_deref(),
+            self.interactivity,
+        )?;
+
+        scaffold_dna(
+            app_file_tree,
+            &self.template_file_tree,
+            &answers.name,
+            self.interactivity,
+        )
+    }
+
+    /// Scaffolds an integrity zome, a coordinator zome or both, as set in the answers
+    pub fn zome(&self, answers: ZomeAnswers) -> ScaffoldResult<ScaffoldedTemplate> {
+        check_case(&answers.name, "zome name", Case::Snake)?;
+
+        let mut dna_file_tree = DnaFileTree::get_or_choose(
+            self.load_project()?,
+            answers.dna.as_deref(),
+            self.interactivity,
+        )?;
+        let dna_manifest_path = dna_file_tree.dna_manifest_path.clone();
+        let mut next_instructions: Vec<String> = vec![];
+
+        if answers.integrity {
+            let integrity_zome_name = if answers.coordinator {
+                integrity_zome_name(&answers.name)
+            } else {
+                answers.name.clone()
+            };
+            let scaffolded = scaffold_integrity_zome(
+                dna_file_tree,
+                &self.template_file_tree,
+                &integrity_zome_name,
+                &answers.integrity_path,
+                self.interactivity,
+            )?;
+            next_instructions.extend(scaffolded.next_instructions);
+
+            dna_file_tree =
+                DnaFileTree::from_dna_manifest_path(scaffolded.file_tree, &dna_manifest_path)?;
+        }
+
+        if answers.coordinator {
+            let dependencies = match (answers.integrity, answers.dependencies) {
+                (true, _) => vec![integrity_zome_name(&answers.name)],
+                (false, Some(dependencies)) => dependencies,
+                (false, None) => select_integrity_zomes(&dna_file_tree.dna_manifest, Some(
+                    "Select integrity zome(s) this coordinator zome depends on (SPACE to select/unselect, ENTER to continue):"
+                ), self.interactivity)?,
+            };
+            let scaffolded = scaffold_coordinator_zome(
+                dna_file_tree,
+                &self.template_file_tree,
+                &answers.name,
+                Some(&dependencies),
+                &answers.coordinator_path,
+                self.interactivity,
+            )?;
+            next_instructions.extend(scaffolded.next_instructions);
+
+            dna_file_tree =
+                DnaFileTree::from_dna_manifest_path(scaffolded.file_tree, &dna_manifest_path)?;
+        }
+
+        Ok(ScaffoldedTemplate {
+            file_tree: dna_file_tree.file_tree(),
+            next_instructions: (!next_instructions.is_empty())
+                .then(|| next_instructions.join("\n\n")),
+        })
+    }
+
+    pub fn entry_type(&self, answers: EntryTypeAnswers) -> ScaffoldResult<ScaffoldedTemplate> {
+        check_case(&answers.name, "entry type name", Case::Snake)?;
+
+        let zome_file_tree =
+            self.integrity_zome(answers.dna.as_deref(), answers.zome.as_deref())?;
+
+        scaffold_entry_type(
+            zome_file_tree,
+            &self.template_file_tree,
+            &answers.name,
+            answers.crud,
+            answers.reference_entry_hash,
+            answers.link_from_original_to_each_update,
+            answers.paginated_revisions,
+            answers.visibility,
+            answers.permission,
+            answers.fields.as_ref(),
+            answers.no_ui,
+            answers.no_spec,
+            self.interactivity,
+        )
+    }
+
+    pub fn link_type(&self, answers: LinkTypeAnswers) -> ScaffoldResult<ScaffoldedTemplate> {
+        let zome_file_tree =
+            self.integrity_zome(answers.dna.as_deref(), answers.zome.as_deref())?;
+
+        scaffold_link_type(
+            zome_file_tree,
+            &self.template_file_tree,
+            answers.from_referenceable.as_ref(),
+            answers.to_referenceable.as_ref(),
+            answers.delete,
+            answers.bidirectional,
+            answers.no_ui,
+            answers.no_spec,
+            self.interactivity,
+        )
+    }
+
+    pub fn collection(&self, answers: CollectionAnswers) -> ScaffoldResult<ScaffoldedTemplate> {
+        check_case(&answers.name, "collection name", Case::Snake)?;
+
+        let zome_file_tree =
+            self.integrity_zome(answers.dna.as_deref(), answers.zome.as_deref())?;
+
+        scaffold_collection(
+            zome_file_tree,
+            &self.template_file_tree,
+            &answers.name,
+            answers.collection_type,
+            answers.entry_type,
+            answers.paginated,
+            answers.no_ui,
+            answers.no_spec,
+            self.interactivity,
+        )
+    }
+
+    /// Writes the scaffolded project to its root folder with the given build mode,
+    /// and formats its Rust code if any Rust file was written
+    ///
+    /// Returns the changes made to the project, with the content of the written files before the write
+    pub fn write(
+        &self,
+        file_tree: FileTree,
+        build_mode: BuildMode,
+    ) -> ScaffoldResult<BuiltFileTree> {
+        let has_rust_files = flatten_file_tree(&file_tree)
+            .keys()
+            .any(|path| path.extension().is_some_and(|e| e == "rs"));
+
+        let built = build_file_tree_with_mode(file_tree, &self.project_dir, build_mode)?;
+
+        if built.written && has_rust_files {
+            if let Err(e) = run_cargo_fmt_if_available(&self.project_dir) {
+                eprintln!(
+                    "{}: {}",
+                    "rustfmt exec failed: ".yellow(),
+                    e.to_string().yellow()
+                );
+            }
+        }
+
+        Ok(built)
+    }
+
+    fn integrity_zome(
+        &self,
+        dna: Option<&str>,
+        zome: Option<&str>,
+    ) -> ScaffoldResult<ZomeFileTree> {
+        let dna_file_tree =
+            DnaFileTree::get_or_choose(self.load_project()?, dna, self.interactivity)?;
+
+        ZomeFileTree::get_or_choose_integrity(dna_file_tree, zome, self.interactivity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use build_fs_tree::{dir, file};
+
+    use super::*;
+    use crate::error::ScaffoldError;
+
+    #[test]
+    fn scaffolds_a_dna_in_the_given_project_dir() {
+        let project_dir = std::env::temp_dir().join(format!(
+            "hc-scaffold-scaffolder-test-{}",
+            std::process::id()
+        ));
+        build_file_tree_with_mode(
+            dir! {
+                "dnas" => dir! {},
+                "workdir" => dir! {
+                    "happ.yaml" => file!(r#"
+manifest_version: '1'
+name: forum
+description: null
+roles: []
+"#)
+                }
+            },
+            &project_dir,
+            BuildMode::Write,
+        )
+        .unwrap();
+
+        let happ_manifest = std::fs::read_to_string(project_dir.join("workdir/happ.yaml")).unwrap();
+
+        let scaffolder = Scaffolder::new(&project_dir, dir! {});
+        let ScaffoldedTemplate { file_tree, .. } = scaffolder
+            .dna(DnaAnswers {
+                app: None,
+                name: String::from("forum"),
+            })
+            .unwrap();
+        let built = scaffolder.write(file_tree, BuildMode::Write).unwrap();
+
+        assert!(built.written);
+        assert!(project_dir.join("dnas/forum/workdir/dna.yaml").is_file());
+        assert_eq!(
+            built.files_before.get(Path::new("workdir/happ.yaml")),
+            Some(&Some(happ_manifest))
+        );
+        assert_eq!(
+            built
+                .files_before
+                .get(Path::new("dnas/forum/workdir/dna.yaml")),
+            Some(&None)
+        );
+
+        std::fs::remove_dir_all(&project_dir).unwrap();
+    }
+
+    #[test]
+    fn fails_instead_of_prompting_when_non_interactive() {
+        let project_dir = std::env::temp_dir().join(format!(
+            "hc-scaffold-scaffolder-non-interactive-test-{}",
+            std::process::id()
+        ));
+        let happ_manifest = |name: &str| {
+            file!(format!(
+                "manifest_version: '1'\nname: {name}\ndescription: null\nroles: []\n"
+            ))
+        };
+        build_file_tree_with_mode(
+            dir! {
+                "forum" => dir! { "happ.yaml" => happ_manifest("forum") },
+                "blog" => dir! { "happ.yaml" => happ_manifest("blog") },
+            },
+            &project_dir,
+            BuildMode::Write,
+        )
+        .unwrap();
+
+        let interactive = Scaffolder::new(&project_dir, dir! {});
+        let non_interactive = Scaffolder::new(&project_dir, dir! {})
+            .with_interactivity(Interactivity::NonInteractive);
+
+        assert_eq!(interactive.interactivity(), Interactivity::Interactive);
+        assert!(matches!(
+            non_interactive.choose_app(None),
+            Err(ScaffoldError::MissingArgument(argument)) if argument == "--app"
+        ));
+        assert_eq!(non_interactive.choose_app(Some("blog")).unwrap(), "blog");
+
+        std::fs::remove_dir_all(&project_dir).unwrap();
+    }
+}
@@ -1,5 +1,7 @@
 use anyhow::Context;
 use build_fs_tree::serde::Serialize;
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Select};
 use handlebars::Handlebars;
 use regex::Regex;
 use std::collections::BTreeMap;
@@ -9,9 +11,10 @@ use std::sync::LazyLock;
 
 use crate::error::{ScaffoldError, ScaffoldResult};
 use crate::file_tree::{
-    file_content, find_files, flatten_file_tree, unflatten_file_tree, FileTree,
+    file_content, find_files, flatten_file_tree, pristine::PristineStore, unflatten_file_tree,
+    FileTree,
 };
-use crate::utils::format_code;
+use crate::utils::{format_code, format_rust_code_if_available, Interactivity};
 
 pub mod helpers;
 
@@ -20,6 +23,7 @@ pub mod coordinator;
 pub mod dna;
 pub mod entry_type;
 pub mod example;
+pub mod field;
 pub mod integrity;
 pub mod link_type;
 pub mod web_app;
@@ -280,19 +284,234 @@ fn handle_if_template_regex<T: Serialize>(
     Ok(())
 }
 
+/// Renders the templates into the app, merging each rendered file with the changes made to it
+/// since it was last generated, as remembered in the pristine store of the app
 pub fn render_template_file_tree_and_merge_with_existing<T: Serialize>(
     app_file_tree: FileTree,
     h: &Handlebars,
     template_file_tree: &FileTree,
     data: &T,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<FileTree> {
+    // Merge helper templates already render on top of the current content of their files
+    let (merge_templates, templates): (BTreeMap<_, _>, BTreeMap<_, _>) = flatten_file_tree(
+        template_file_tree,
+    )
+    .into_iter()
+    .partition(
+        |(_, contents)| matches!(contents, Some(c) if c.contains("{{#merge previous_file_content")),
+    );
     let rendered_templates =
-        render_template_file_tree(&app_file_tree, h, template_file_tree, data)?;
+        render_template_file_tree(&app_file_tree, h, &unflatten_file_tree(&templates)?, data)?;
+    let rendered_merge_templates = render_template_file_tree(
+        &app_file_tree,
+        h,
+        &unflatten_file_tree(&merge_templates)?,
+        data,
+    )?;
 
+    let mut pristine_store = PristineStore::load(&app_file_tree)?;
     let mut flattened_app_file_tree = flatten_file_tree(&app_file_tree);
-    let flattened_templates = flatten_file_tree(&rendered_templates);
 
-    flattened_app_file_tree.extend(flattened_templates);
+    for (path, rendered) in flatten_file_tree(&rendered_templates) {
+        let Some(new_content) = rendered else {
+            flattened_app_file_tree.insert(path, None);
+            continue;
+        };
+        // Rust files are formatted by `cargo fmt` once written, so they are stored as formatted
+        let new_content = match path.extension().and_then(|e| e.to_str()) {
+            Some("rs") => format_rust_code_if_available(&new_content),
+            _ => new_content,
+        };
+
+        // Files generated before the pristine store existed are overwritten, as they used to be
+        let content = match (
+            flattened_app_file_tree.get(&path).cloned().flatten(),
+            pristine_store.files.get(&path),
+        ) {
+            (Some(current), Some(pristine)) => merge_with_changes_since_generated(
+                &path,
+                pristine,
+                &current,
+                &new_content,
+                interactivity,
+            )?,
+            _ => new_content.clone(),
+        };
+
+        pristine_store.files.insert(path.clone(), new_content);
+        flattened_app_file_tree.insert(path, Some(content));
+    }
+    flattened_app_file_tree.extend(flatten_file_tree(&rendered_merge_templates));
+
+    let mut file_tree = unflatten_file_tree(&flattened_app_file_tree)?;
+    pristine_store.save(&mut file_tree)?;
+
+    Ok(file_tree)
+}
+
+/// Three-way merge of the changes made to a file since it was generated with the `pristine` content,
+/// and of the changes made by the template to the newly generated content
+///
+/// Conflicts are left as conflict markers in non-interactive mode, otherwise the user chooses how to resolve them
+pub fn merge_with_changes_since_generated(
+    path: &Path,
+    pristine: &str,
+    current: &str,
+    new: &str,
+    interactivity: Interactivity,
+) -> ScaffoldResult<String> {
+    if current == pristine || current == new {
+        return Ok(new.to_owned());
+    }
+    if new == pristine {
+        return Ok(current.to_owned());
+    }
+
+    let conflicted = match diffy::merge(pristine, current, new) {
+        Ok(merged) => return Ok(merged),
+        Err(conflicted) => conflicted,
+    };
+
+    let choices = [
+        "Merge them with conflict markers, to resolve by hand",
+        "Keep my version",
+        "Use the version of the template",
+    ];
+    let choice = if !interactivity.is_interactive() {
+        0
+    } else {
+        Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "{} was changed since it was generated, and the changes conflict with the template:",
+                path.display()
+            ))
+            .default(0)
+            .items(&choices)
+            .interact()?
+    };
+
+    match choice {
+        0 => {
+            eprintln!(
+                "{}",
+                format!(
+                    "Conflicts in {}: resolve the conflict markers between your version (ours) and the template (theirs)",
+                    path.display()
+                )
+                .yellow()
+            );
+            Ok(conflicted)
+        }
+        1 => Ok(current.to_owned()),
+        _ => Ok(new.to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_the_template_changes_with_the_changes_since_generated() {
+        let pristine = "<h1>Posts</h1>\n<AllPosts />\n";
+        let current = "<h1 class=\"title\">My posts</h1>\n<AllPosts />\n";
+        let new = "<h1>Posts</h1>\n<AllPosts />\n<AllComments />\n";
+        let path = Path::new("ui/src/App.svelte");
+
+        assert_eq!(
+            merge_with_changes_since_generated(
+                path,
+                pristine,
+                current,
+                new,
+                Interactivity::NonInteractive
+            )
+            .unwrap(),
+            "<h1 class=\"title\">My posts</h1>\n<AllPosts />\n<AllComments />\n"
+        );
+        assert_eq!(
+            merge_with_changes_since_generated(
+                path,
+                pristine,
+                current,
+                pristine,
+                Interactivity::NonInteractive
+            )
+            .unwrap(),
+            current
+        );
+    }
+
+    #[test]
+    fn regenerating_merges_the_changes_made_since_the_files_were_stored() {
+        let templates: FileTree = build_fs_tree::dir! {
+            "README.md.hbs" => build_fs_tree::file!("# {{title}}\n\nfirst\nsecond\nthird\n"),
+            "src" => build_fs_tree::dir! {
+                "lib.rs.hbs" => build_fs_tree::file!("pub fn {{fn_name}}() -> u32 { 1 }\n\npub fn other() -> u32 { 2 }\n")
+            },
+            "notes.txt.hbs" => build_fs_tree::file!(
+                "{{#merge previous_file_content}}{{#match_scope \"notes {\"}}{{previous_scope_content}}\n  {{title}}\n{{/match_scope}}{{/merge}}"
+            )
+        };
+        let h = build_handlebars(&templates).unwrap();
+        let app_file_tree: FileTree = build_fs_tree::dir! {
+            "notes.txt" => build_fs_tree::file!("notes {\n}\n")
+        };
+
+        let app_file_tree = render_template_file_tree_and_merge_with_existing(
+            app_file_tree,
+            &h,
+            &templates,
+            &serde_json::json!({ "title": "Forum", "fn_name": "get" }),
+            Interactivity::NonInteractive,
+        )
+        .unwrap();
+        let pristine_store = PristineStore::load(&app_file_tree).unwrap();
+        let lib_rs = Path::new("src/lib.rs");
+        assert_eq!(
+            pristine_store.files.get(lib_rs),
+            Some(&format_rust_code_if_available(
+                "pub fn get() -> u32 { 1 }\n\npub fn other() -> u32 { 2 }\n"
+            ))
+        );
+        assert!(!pristine_store.files.contains_key(Path::new("notes.txt")));
+
+        // Edit the files as formatted by `cargo fmt`
+        let mut flattened = flatten_file_tree(&app_file_tree);
+        let readme = Path::new("README.md");
+        let edited_readme = file_content(&app_file_tree, readme)
+            .unwrap()
+            .replace("third", "my third");
+        flattened.insert(readme.to_path_buf(), Some(edited_readme));
+        let edited_lib_rs = pristine_store.files[lib_rs]
+            .replace("{ 2 }", "{ 3 }")
+            .replace("    2", "    3");
+        flattened.insert(lib_rs.to_path_buf(), Some(edited_lib_rs));
+        let app_file_tree = unflatten_file_tree(&flattened).unwrap();
+
+        let app_file_tree = render_template_file_tree_and_merge_with_existing(
+            app_file_tree,
+            &h,
+            &templates,
+            &serde_json::json!({ "title": "Blog", "fn_name": "fetch" }),
+            Interactivity::NonInteractive,
+        )
+        .unwrap();
 
-    unflatten_file_tree(&flattened_app_file_tree)
+        assert_eq!(
+            file_content(&app_file_tree, readme).unwrap(),
+            "# Blog\n\nfirst\nsecond\nmy third\n"
+        );
+        assert_eq!(
+            file_content(&app_file_tree, lib_rs).unwrap(),
+            format_rust_code_if_available(
+                "pub fn fetch() -> u32 { 1 }\n\npub fn other() -> u32 { 3 }\n"
+            )
+        );
+        assert_eq!(
+            file_content(&app_file_tree, Path::new("notes.txt")).unwrap(),
+            "notes {\nForum\n  Blog\n}\n"
+        );
+    }
 }
@@ -3,6 +3,7 @@ use std::{ffi::OsString, path::PathBuf};
 use holochain_types::prelude::ZomeManifest;
 use serde::Serialize;
 
+use crate::utils::Interactivity;
 use crate::{
     error::ScaffoldResult,
     file_tree::{file_content, FileTree},
@@ -25,6 +26,7 @@ pub struct ScaffoldCollectionData {
     pub collection_name: String,
     pub referenceable: Referenceable,
     pub deletable: bool,
+    pub paginated: bool,
 }
 
 // TODO: group some params into a new-type or prefer builder pattern
@@ -39,8 +41,10 @@ pub fn scaffold_collection_templates(
     collection_name: &str,
     entry_type_reference: &EntryTypeReference,
     deletable: bool,
+    paginated: bool,
     no_ui: bool,
     no_spec: bool,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     let data = ScaffoldCollectionData {
         app_name: app_name.to_owned(),
@@ -50,6 +54,7 @@ pub fn scaffold_collection_templates(
         collection_type: *collection_type,
         referenceable: Referenceable::EntryType(entry_type_reference.clone()),
         deletable,
+        paginated,
     };
 
     let h = build_handlebars(template_file_tree)?;
@@ -76,6 +81,7 @@ pub fn scaffold_collection_templates(
             &h,
             &web_app_template,
             &data,
+            interactivity,
         )?;
     }
 
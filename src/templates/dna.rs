@@ -2,6 +2,7 @@ use std::{ffi::OsString, path::PathBuf};
 
 use serde::Serialize;
 
+use crate::utils::Interactivity;
 use crate::{
     error::ScaffoldResult,
     file_tree::{file_content, FileTree},
@@ -21,6 +22,7 @@ pub fn scaffold_dna_templates(
     template_file_tree: &FileTree,
     app_name: &str,
     dna_name: &str,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     let data = ScaffoldDnaData {
         app_name: app_name.to_owned(),
@@ -38,6 +40,7 @@ pub fn scaffold_dna_templates(
             &h,
             web_app_template,
             &data,
+            interactivity,
         )?;
     }
 
@@ -3,6 +3,7 @@ use std::{ffi::OsString, path::PathBuf};
 use holochain_types::prelude::ZomeManifest;
 use serde::Serialize;
 
+use crate::utils::Interactivity;
 use crate::{
     error::ScaffoldResult,
     file_tree::{file_content, FileTree},
@@ -22,6 +23,7 @@ pub struct ScaffoldEntryTypeData<'a> {
     pub entry_type_ts_types: &'a str,
     pub crud: Crud,
     pub link_from_original_to_each_update: bool,
+    pub paginated_revisions: bool,
 }
 
 // TODO: group some params into a new-type or prefer builder pattern
@@ -36,8 +38,10 @@ pub fn scaffold_entry_type_templates(
     entry_type_ts_types: &str,
     crud: &Crud,
     link_from_original_to_each_update: bool,
+    paginated_revisions: bool,
     no_ui: bool,
     no_spec: bool,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     let data = ScaffoldEntryTypeData {
         app_name: app_name.to_owned(),
@@ -47,6 +51,7 @@ pub fn scaffold_entry_type_templates(
         entry_type_ts_types,
         crud: *crud,
         link_from_original_to_each_update,
+        paginated_revisions,
     };
     let h = build_handlebars(template_file_tree)?;
 
@@ -72,6 +77,7 @@ pub fn scaffold_entry_type_templates(
             &h,
             &web_app_template,
             &data,
+            interactivity,
         )?;
     }
 
@@ -88,3 +94,74 @@ pub fn scaffold_entry_type_templates(
         next_instructions,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use mr_bundle::Location;
+
+    use super::*;
+    use crate::scaffold::{
+        entry_type::definitions::{FieldDefinition, Visibility},
+        web_app::template_type::TemplateType,
+    };
+
+    fn render_spec(visibility: Visibility) -> String {
+        let entry_type = EntryDefinition {
+            name: "draft".to_string(),
+            fields: vec![FieldDefinition::from_str("title:String:TextField").unwrap()],
+            reference_entry_hash: false,
+            visibility,
+            permission: None,
+        };
+        let coordinator_zome = ZomeManifest {
+            name: "posts".into(),
+            hash: None,
+            location: Location::Bundled(PathBuf::from(
+                "../../../target/wasm32-unknown-unknown/release/posts.wasm",
+            )),
+            dependencies: None,
+            dylib: None,
+        };
+        let scaffolded = scaffold_entry_type_templates(
+            build_fs_tree::dir! {},
+            &TemplateType::Svelte.file_tree().unwrap(),
+            "forum",
+            "forum",
+            &coordinator_zome,
+            &entry_type,
+            "",
+            &Crud {
+                update: true,
+                delete: true,
+            },
+            false,
+            false,
+            true,
+            false,
+            Interactivity::NonInteractive,
+        )
+        .unwrap();
+
+        file_content(
+            &scaffolded.file_tree,
+            &PathBuf::from("tests/src/forum/posts/draft.test.ts"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn private_entry_type_specs_check_that_a_second_agent_cannot_read_them() {
+        let private_spec = render_spec(Visibility::Private);
+        assert!(private_spec.contains("a second agent cannot read a private Draft"));
+        assert!(private_spec.contains("assert.notOk(bobReadOutput);"));
+        assert!(private_spec.contains("assert.equal(bobDrafts.length, 0);"));
+        // The author reads their private entries back
+        assert!(private_spec.contains("// Alice gets the created Draft"));
+
+        let public_spec = render_spec(Visibility::Public);
+        assert!(!public_spec.contains("a second agent cannot read"));
+        assert!(public_spec.contains("// Bob gets the created Draft"));
+    }
+}
@@ -1,6 +1,7 @@
 use serde::Serialize;
 use std::{ffi::OsString, path::PathBuf};
 
+use crate::utils::Interactivity;
 use crate::{
     error::ScaffoldResult,
     file_tree::{file_content, FileTree},
@@ -26,6 +27,7 @@ pub fn scaffold_example(
     package_manager: PackageManager,
     template_file_tree: &FileTree,
     example: &ExampleType,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     let data = ScaffoldExampleData {
         example: &example.to_string(),
@@ -45,6 +47,7 @@ pub fn scaffold_example(
             &h,
             example_template,
             &data,
+            interactivity,
         )?;
     }
 
@@ -0,0 +1,100 @@
+use std::{ffi::OsString, path::PathBuf};
+
+use holochain_types::prelude::ZomeManifest;
+use serde::Serialize;
+
+use crate::{
+    error::ScaffoldResult,
+    file_tree::{flatten_file_tree, unflatten_file_tree, FileTree},
+    scaffold::entry_type::definitions::EntryDefinition,
+    utils::format_code,
+};
+
+use super::{build_handlebars, render_template_file};
+
+#[derive(Serialize, Debug)]
+pub struct ScaffoldFieldsData {
+    pub dna_role_name: String,
+    pub coordinator_zome_manifest: ZomeManifest,
+    /// The entry type the fields are added to, holding only the new fields
+    pub entry_type: EntryDefinition,
+}
+
+pub struct ScaffoldedFields {
+    pub file_tree: FileTree,
+    /// Files that exist in the app but couldn't be merged with the new fields, with the reason why
+    pub skipped_files: Vec<(PathBuf, String)>,
+}
+
+/// Renders the templates in the "field" folder, which merge the new fields into the files
+/// that were generated for the entry type
+///
+/// Templates whose target file doesn't exist in the app are ignored
+pub fn scaffold_field_templates(
+    app_file_tree: FileTree,
+    template_file_tree: &FileTree,
+    dna_role_name: &str,
+    coordinator_zome: &ZomeManifest,
+    entry_type: &EntryDefinition,
+    no_ui: bool,
+) -> ScaffoldResult<ScaffoldedFields> {
+    let data = ScaffoldFieldsData {
+        dna_role_name: dna_role_name.to_owned(),
+        coordinator_zome_manifest: coordinator_zome.clone(),
+        entry_type: entry_type.clone(),
+    };
+    let h = build_handlebars(template_file_tree)?;
+
+    let field_path = PathBuf::from("field");
+    let v: Vec<OsString> = field_path.iter().map(|s| s.to_os_string()).collect();
+
+    let Some(mut field_templates) = template_file_tree.path(&mut v.iter()).cloned() else {
+        return Ok(ScaffoldedFields {
+            file_tree: app_file_tree,
+            skipped_files: vec![],
+        });
+    };
+    if no_ui {
+        if let Some(v) = field_templates.dir_content_mut() {
+            v.retain(|k, _| k != "ui");
+        }
+    }
+
+    let mut flattened_app_file_tree = flatten_file_tree(&app_file_tree);
+    let mut skipped_files = vec![];
+
+    for (path, maybe_contents) in flatten_file_tree(&field_templates) {
+        let (Some(contents), Some("hbs")) =
+            (maybe_contents, path.extension().and_then(|e| e.to_str()))
+        else {
+            continue;
+        };
+        let new_path = h.render_template(&path.to_string_lossy(), &data)?;
+        let target_path = PathBuf::from(new_path).with_extension("");
+
+        if !matches!(flattened_app_file_tree.get(&target_path), Some(Some(_))) {
+            continue;
+        }
+
+        let new_contents = render_template_file(
+            &h,
+            &app_file_tree,
+            &target_path,
+            &contents,
+            &serde_json::json!(data),
+        )
+        .and_then(|c| format_code(&c, &target_path));
+
+        match new_contents {
+            Ok(new_contents) => {
+                flattened_app_file_tree.insert(target_path, Some(new_contents));
+            }
+            Err(e) => skipped_files.push((target_path, e.to_string())),
+        }
+    }
+
+    Ok(ScaffoldedFields {
+        file_tree: unflatten_file_tree(&flattened_app_file_tree)?,
+        skipped_files,
+    })
+}
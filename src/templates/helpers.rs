@@ -20,6 +20,7 @@ pub fn register_helpers(h: Handlebars) -> Handlebars {
     let h = register_includes_helper(h);
     let h = register_case_helpers(h);
     let h = register_replace_helper(h);
+    let h = register_json_helper(h);
     let h = register_pluralize_helpers(h);
     let h = register_merge(h);
     let h = register_uniq_lines(h);
@@ -76,6 +77,14 @@ pub fn register_replace_helper(mut h: Handlebars) -> Handlebars {
     h
 }
 
+/// Writes the given value as JSON, eg. to write a string as a javascript string literal
+pub fn register_json_helper(mut h: Handlebars) -> Handlebars {
+    handlebars_helper!(json: |v: Value| v.to_string());
+    h.register_helper("json", Box::new(json));
+
+    h
+}
+
 pub fn register_pluralize_helpers(mut h: Handlebars) -> Handlebars {
     handlebars_helper!(singular: |s: String| pluralizer::pluralize(s.as_str(), 1, false));
     h.register_helper("singular", Box::new(singular));
@@ -99,6 +99,7 @@ impl HelperDef for Merge {
                     .collect();
 
                 matched_scopes.sort_by(|a, b| b.__starting_index.cmp(&a.__starting_index));
+                let first_is_insertion = matched_scopes.last().is_some_and(|s| s.__insertion);
 
                 let mut full_merge_content = String::from("");
                 for matched_scope in matched_scopes {
@@ -111,7 +112,11 @@ impl HelperDef for Merge {
                     previous_index = start_index + 1;
                     full_merge_content.insert_str(0, full_scope_content.as_str());
                 }
-                full_merge_content.insert_str(0, &s[0..=previous_index]);
+                // Scopes keep the character that follows their opener, insertions keep the text as is
+                match first_is_insertion {
+                    true => full_merge_content.insert_str(0, &s[0..previous_index]),
+                    false => full_merge_content.insert_str(0, &s[0..=previous_index]),
+                }
                 out.write(&full_merge_content)?;
 
                 data.remove(MATCHED_SCOPES);
@@ -130,6 +135,8 @@ struct MatchedScopedData {
     __starting_index: usize,
     __new_scope_content: String,
     __old_scope_length: usize,
+    #[serde(default)]
+    __insertion: bool,
 }
 
 const MATCHED_SCOPES: &str = "__matched_scopes";
@@ -137,6 +144,7 @@ const SCOPE_CONTENT: &str = "__scope_content";
 const STARTING_INDEX: &str = "__starting_index";
 const NEW_SCOPE_CONTENT: &str = "__new_scope_content";
 const OLD_SCOPE_LENGTH: &str = "__old_scope_length";
+const INSERTION: &str = "__insertion";
 
 #[derive(Clone, Copy)]
 pub struct MatchScope;
@@ -225,9 +233,97 @@ impl HelperDef for MatchScope {
     }
 }
 
+/// Inserts its content just before (or after) the first occurrence of the given text,
+/// leaving the rest of the content of the enclosing merge helper untouched
+#[derive(Clone, Copy)]
+pub struct Insert {
+    after: bool,
+}
+
+impl HelperDef for Insert {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'reg, 'rc>,
+        r: &'reg Handlebars<'reg>,
+        ctx: &'rc Context,
+        rc: &mut RenderContext<'reg, 'rc>,
+        _out: &mut dyn Output,
+    ) -> HelperResult {
+        let t = h
+            .template()
+            .ok_or(RenderError::new("insert helpers cannot have empty content"))?;
+
+        let mut data = rc
+            .context()
+            .ok_or(RenderError::new("Context must be an object"))?
+            .data()
+            .as_object()
+            .ok_or(RenderError::new("Context must be an object"))?
+            .clone();
+
+        let Some(Value::String(scope_content)) = data.get(SCOPE_CONTENT) else {
+            return Err(RenderError::new(
+                "insert helpers need to be placed inside a merge helper",
+            ));
+        };
+
+        let anchor = h
+            .param(0)
+            .ok_or(RenderError::new("insert helpers need 1 parameter"))?
+            .value()
+            .as_str()
+            .ok_or(RenderError::new(
+                "insert helpers' first parameter must be a string",
+            ))?
+            .to_string();
+
+        let anchor_index = scope_content.find(&anchor).ok_or(RenderError::new(format!(
+            "\"{anchor}\" was not found in the content to merge"
+        )))?;
+        let insertion_index = match self.after {
+            true => anchor_index + anchor.len(),
+            false => anchor_index,
+        };
+        if insertion_index == 0 {
+            return Err(RenderError::new(
+                "insert helpers can't insert at the very start of the content",
+            ));
+        }
+
+        let mut inner_output = StringOutput::new();
+        t.render(r, ctx, rc, &mut inner_output)?;
+        let out_string = inner_output.into_string()?;
+
+        let mut map = Map::new();
+        map.insert(
+            String::from(STARTING_INDEX),
+            Value::Number(Number::from(insertion_index - 1)),
+        );
+        map.insert(String::from(NEW_SCOPE_CONTENT), Value::String(out_string));
+        map.insert(
+            String::from(OLD_SCOPE_LENGTH),
+            Value::Number(Number::from(1)),
+        );
+        map.insert(String::from(INSERTION), Value::Bool(true));
+
+        let mut matched_scopes = match data.get(MATCHED_SCOPES) {
+            Some(Value::Array(array)) => array.clone(),
+            _ => vec![],
+        };
+        matched_scopes.push(Value::Object(map));
+        data.insert(MATCHED_SCOPES.to_string(), Value::Array(matched_scopes));
+
+        rc.set_context(Context::wraps(data)?);
+
+        Ok(())
+    }
+}
+
 pub fn register_merge(mut h: Handlebars) -> Handlebars {
     h.register_helper("merge", Box::new(Merge));
     h.register_helper("match_scope", Box::new(MatchScope));
+    h.register_helper("insert_before", Box::new(Insert { after: false }));
+    h.register_helper("insert_after", Box::new(Insert { after: true }));
 
     h
 }
@@ -375,6 +471,46 @@ export class B {
         // New line
     }
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn test_merge_insert_before_and_after() {
+        let h = Handlebars::new();
+
+        let h = register_merge(h);
+
+        let code = r#"<script>
+let title = '';
+</script>
+
+<div>
+  <button>Create</button>
+</div>
+"#;
+        let value = json!({"previous_file_content": code});
+        let context = Context::from(value);
+        let template = r#"{{#merge previous_file_content}}
+{{#insert_after "let title = '';\n"}}
+let tags = [];
+{{/insert_after}}
+{{#insert_before "  <button>"}}
+  <input />
+{{/insert_before}}
+{{/merge}}"#;
+
+        assert_eq!(
+            h.render_template_with_context(template, &context).unwrap(),
+            r#"<script>
+let title = '';
+let tags = [];
+</script>
+
+<div>
+  <input />
+  <button>Create</button>
+</div>
 "#,
         );
     }
@@ -3,6 +3,7 @@ use std::{ffi::OsString, path::PathBuf};
 use holochain_types::prelude::ZomeManifest;
 use serde::Serialize;
 
+use crate::utils::Interactivity;
 use crate::{
     error::ScaffoldResult,
     file_tree::{file_content, FileTree},
@@ -23,6 +24,7 @@ pub fn scaffold_integrity_zome_templates(
     template_file_tree: &FileTree,
     dna_role_name: &str,
     zome_manifest: &ZomeManifest,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     let data = ScaffoldIntegrityZomeData {
         dna_role_name: dna_role_name.to_owned(),
@@ -40,6 +42,7 @@ pub fn scaffold_integrity_zome_templates(
             &h,
             web_app_template,
             &data,
+            interactivity,
         )?;
     }
 
@@ -3,6 +3,7 @@ use std::{ffi::OsString, path::PathBuf};
 use holochain_types::prelude::ZomeManifest;
 use serde::Serialize;
 
+use crate::utils::Interactivity;
 use crate::{
     error::ScaffoldResult,
     file_tree::{file_content, FileTree},
@@ -40,6 +41,7 @@ pub fn scaffold_link_type_templates(
     bidirectional: Option<&str>,
     no_ui: bool,
     no_spec: bool,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     let data = ScaffoldLinkTypeData {
         app_name,
@@ -85,6 +87,7 @@ pub fn scaffold_link_type_templates(
             &h,
             &link_type_template,
             &data,
+            interactivity,
         )?;
     }
 
@@ -2,6 +2,7 @@ use std::{ffi::OsString, path::PathBuf};
 
 use serde::Serialize;
 
+use crate::utils::Interactivity;
 use crate::{
     error::ScaffoldResult,
     file_tree::{file_content, FileTree},
@@ -34,6 +35,7 @@ pub fn scaffold_web_app_template(
     app_name: &str,
     package_manager: PackageManager,
     holo_enabled: bool,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<ScaffoldedTemplate> {
     let data = ScaffoldWebAppData {
         app_name,
@@ -60,6 +62,7 @@ pub fn scaffold_web_app_template(
             &h,
             web_app_template,
             &data,
+            interactivity,
         )?;
     }
 
@@ -1,7 +1,8 @@
 use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::io::Write;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::{ffi::OsString, path::PathBuf};
 
 use anyhow::Context;
@@ -13,7 +14,47 @@ use dprint_plugin_typescript::configuration::ConfigurationBuilder;
 use crate::error::{ScaffoldError, ScaffoldResult};
 use crate::file_tree::{dir_content, FileTree};
 
-pub fn choose_directory_path(prompt: &str, app_file_tree: &FileTree) -> ScaffoldResult<PathBuf> {
+/// Whether the user can be prompted for the values that were not given
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Interactivity {
+    #[default]
+    Interactive,
+    /// Values that would have been prompted for must be given as arguments,
+    /// and yes/no questions are answered with their recommended answer
+    NonInteractive,
+}
+
+impl Interactivity {
+    pub fn is_interactive(self) -> bool {
+        self == Interactivity::Interactive
+    }
+
+    /// Raises an error naming the given argument if prompts are disabled
+    pub fn ensure_interactive(self, argument: &str) -> ScaffoldResult<()> {
+        if !self.is_interactive() {
+            return Err(ScaffoldError::MissingArgument(argument.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Raises an error with the given prompt if prompts are disabled, for prompts that have no matching argument
+    pub fn ensure_can_prompt(self, prompt: &str) -> ScaffoldResult<()> {
+        if !self.is_interactive() {
+            return Err(ScaffoldError::PromptInNonInteractiveMode(
+                prompt.trim().to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub fn choose_directory_path(
+    prompt: &str,
+    app_file_tree: &FileTree,
+    interactivity: Interactivity,
+) -> ScaffoldResult<PathBuf> {
+    interactivity.ensure_can_prompt(prompt)?;
+
     let mut chosen_directory: Option<PathBuf> = None;
     let mut current_path = PathBuf::new();
 
@@ -75,7 +116,18 @@ fn get_folder_names(folder: &BTreeMap<OsString, FileTree>) -> Vec<String> {
 
 #[inline]
 /// "yes" or "no" input dialog, with the option to specify a recommended answer (yes = true, no = false)
-pub fn input_yes_or_no(prompt: &str, recommended: Option<bool>) -> ScaffoldResult<bool> {
+///
+/// In non-interactive mode the recommended answer is returned, and questions with no recommended answer raise an error
+pub fn input_yes_or_no(
+    prompt: &str,
+    recommended: Option<bool>,
+    interactivity: Interactivity,
+) -> ScaffoldResult<bool> {
+    if !interactivity.is_interactive() {
+        return recommended
+            .ok_or_else(|| ScaffoldError::PromptInNonInteractiveMode(prompt.trim().to_string()));
+    }
+
     let yes_recommended = (recommended == Some(true))
         .then_some("(recommended)")
         .unwrap_or_default();
@@ -102,10 +154,18 @@ pub fn input_with_custom_validation<V>(
     prompt: &str,
     initial_text: Option<&str>,
     validator: V,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<String>
 where
     V: Fn(String) -> Result<(), String>,
 {
+    if !interactivity.is_interactive() {
+        let initial_text = initial_text
+            .ok_or_else(|| ScaffoldError::PromptInNonInteractiveMode(prompt.trim().to_string()))?;
+        validator(initial_text.to_string()).map_err(ScaffoldError::InvalidStringFormat)?;
+        return Ok(initial_text.to_string());
+    }
+
     let mut input: String = Input::with_theme(&ColorfulTheme::default())
         .with_prompt(prompt)
         .with_initial_text(initial_text.unwrap_or_default())
@@ -127,7 +187,15 @@ pub fn input_with_case(
     prompt: &str,
     initial_text: Option<&str>,
     case: Case,
+    interactivity: Interactivity,
 ) -> ScaffoldResult<String> {
+    if !interactivity.is_interactive() {
+        let initial_text = initial_text
+            .ok_or_else(|| ScaffoldError::PromptInNonInteractiveMode(prompt.trim().to_string()))?;
+        check_case(initial_text, "Input", case)?;
+        return Ok(initial_text.to_string());
+    }
+
     let mut input: String = Input::with_theme(&ColorfulTheme::default())
         .with_prompt(prompt)
         .with_initial_text(initial_text.unwrap_or_default())
@@ -144,7 +212,9 @@ pub fn input_with_case(
 }
 
 #[inline]
-pub fn input_no_whitespace(prompt: &str) -> ScaffoldResult<String> {
+pub fn input_no_whitespace(prompt: &str, interactivity: Interactivity) -> ScaffoldResult<String> {
+    interactivity.ensure_can_prompt(prompt)?;
+
     let mut input: String = Input::with_theme(&ColorfulTheme::default())
         .with_prompt(prompt)
         .interact_text()?;
@@ -388,20 +458,55 @@ fn format_nested<'a>(
     Ok(Cow::Borrowed(raw))
 }
 
-/// Runs `cargo fmt` if it's available in the current Rust toolchain otherwise will exit
+/// Runs `cargo fmt` in the given directory if it's available in the current Rust toolchain otherwise will exit
 /// gracefully
-pub fn run_cargo_fmt_if_available() -> ScaffoldResult<()> {
-    let cargo_fmt_available = Command::new("cargo").arg("fmt").arg("--version").output();
+pub fn run_cargo_fmt_if_available(dir: &Path) -> ScaffoldResult<()> {
+    let cargo_fmt_available = Command::new("cargo")
+        .arg("fmt")
+        .arg("--version")
+        .current_dir(dir)
+        .output();
 
     match cargo_fmt_available {
         Ok(output) if output.status.success() => {
-            Command::new("cargo").arg("fmt").status()?;
+            Command::new("cargo")
+                .arg("fmt")
+                .current_dir(dir)
+                .stdout(Stdio::from(std::io::stderr()))
+                .status()?;
         }
         _ => {}
     }
     Ok(())
 }
 
+/// Formats the given rust code with `rustfmt` if it's available in the current Rust toolchain, so that
+/// it matches the files formatted by `cargo fmt`, otherwise returns it unchanged
+pub fn format_rust_code_if_available(code: &str) -> String {
+    let Ok(mut rustfmt) = Command::new("rustfmt")
+        .args(["--edition", "2021", "--emit", "stdout"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return code.to_owned();
+    };
+
+    if let Some(mut stdin) = rustfmt.stdin.take() {
+        if stdin.write_all(code.as_bytes()).is_err() {
+            return code.to_owned();
+        }
+    }
+
+    match rustfmt.wait_with_output() {
+        Ok(output) if output.status.success() => {
+            String::from_utf8(output.stdout).unwrap_or_else(|_| code.to_owned())
+        }
+        _ => code.to_owned(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -519,4 +624,28 @@ let greeting = { message: "Hello, world!" };
 "#;
         assert_eq!(formatted_code, expected_output);
     }
+
+    #[test]
+    fn test_non_interactive_prompts() {
+        let non_interactive = Interactivity::NonInteractive;
+
+        assert!(input_yes_or_no("Continue?", Some(true), non_interactive).unwrap());
+        assert!(!input_yes_or_no("Continue?", Some(false), non_interactive).unwrap());
+        assert!(matches!(
+            input_yes_or_no("Continue?", None, non_interactive),
+            Err(ScaffoldError::PromptInNonInteractiveMode(prompt)) if prompt == "Continue?"
+        ));
+        assert_eq!(
+            input_with_case("Name:", Some("my_dna"), Case::Snake, non_interactive).unwrap(),
+            "my_dna"
+        );
+        assert!(matches!(
+            input_with_case("Name:", None, Case::Snake, non_interactive),
+            Err(ScaffoldError::PromptInNonInteractiveMode(_))
+        ));
+        assert!(matches!(
+            non_interactive.ensure_interactive("--crud"),
+            Err(ScaffoldError::MissingArgument(flag)) if flag == "--crud"
+        ));
+    }
 }